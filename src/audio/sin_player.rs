@@ -25,6 +25,12 @@ impl PlayerTrait for SinPlayer {
     }
 }
 
+impl Default for SinPlayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl SinPlayer {
     pub fn new() -> Self {
         Self {
@@ -0,0 +1,12 @@
+use chrono::{DateTime, FixedOffset};
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Commit {
+    pub hash: String,
+    pub time: DateTime<FixedOffset>,
+    pub author_email: String,
+    /// Ref the commit was reached from, e.g. `refs/heads/ABC-12-login`.
+    pub source_ref: Option<String>,
+    pub subject: String,
+    pub body: String,
+}
@@ -0,0 +1,15 @@
+use crate::jira::Worklog;
+use chrono::NaiveDate;
+
+#[cfg(test)]
+use mockall::{automock, predicate::*};
+
+#[cfg_attr(test, automock)]
+pub trait DraftGeneratorTrait {
+    fn generate(&self, start_date: NaiveDate, end_date: NaiveDate) -> anyhow::Result<Vec<Worklog>>;
+
+    /// Problems the last `generate` worked around, e.g. input it had to skip.
+    fn warnings(&self) -> Vec<String> {
+        vec![]
+    }
+}
@@ -0,0 +1,52 @@
+use chrono::Duration;
+
+/// How commit timestamps are turned into time spent.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct EstimationHeuristics {
+    /// Commits further apart than this start a new work session.
+    pub session_gap: Duration,
+    /// Time credited to the first commit of a session, which has no predecessor.
+    pub first_commit_time: Duration,
+    /// Estimates are rounded up to a multiple of this.
+    pub rounding: Duration,
+}
+
+impl EstimationHeuristics {
+    pub fn new() -> Self {
+        Self {
+            session_gap: Duration::hours(2),
+            first_commit_time: Duration::minutes(30),
+            rounding: Duration::minutes(15),
+        }
+    }
+
+    pub fn with_session_gap(mut self, session_gap: Duration) -> Self {
+        self.session_gap = session_gap;
+        self
+    }
+
+    pub fn with_first_commit_time(mut self, first_commit_time: Duration) -> Self {
+        self.first_commit_time = first_commit_time;
+        self
+    }
+
+    pub fn with_rounding(mut self, rounding: Duration) -> Self {
+        self.rounding = rounding;
+        self
+    }
+
+    pub fn round(&self, duration: Duration) -> Duration {
+        let step = self.rounding.num_seconds();
+        if step <= 0 {
+            return duration;
+        }
+        let seconds = duration.num_seconds();
+        Duration::seconds((seconds + step - 1) / step * step)
+    }
+}
+
+impl Default for EstimationHeuristics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,208 @@
+use super::WorklogTemplate;
+use crate::jira::Worklog;
+use crate::time_tracker::{UserWorklogsSummary, WorkSchedule};
+use chrono::Duration;
+use chrono_tz::Tz;
+
+/// Picks drafts for days where logged time falls short of the schedule.
+pub struct GapFiller {
+    schedule: WorkSchedule,
+    templates: Vec<WorklogTemplate>,
+    timezone: Tz,
+}
+
+impl GapFiller {
+    pub fn new(schedule: WorkSchedule) -> Self {
+        Self {
+            schedule,
+            templates: vec![],
+            timezone: chrono_tz::UTC,
+        }
+    }
+
+    pub fn with_templates(mut self, templates: &[WorklogTemplate]) -> Self {
+        self.templates = templates.to_vec();
+        self
+    }
+
+    /// Timezone of the summary's days: drafts are matched to days and templated worklogs
+    /// start in it, UTC by default.
+    pub fn with_timezone(mut self, timezone: Tz) -> Self {
+        self.timezone = timezone;
+        self
+    }
+
+    /// Templates occurring on a day are logged first unless already tagged in that day's
+    /// worklogs. Drafts for issues already logged on that day are skipped; the rest are
+    /// taken largest first and trimmed so the day does not exceed its expected time.
+    pub fn fill(&self, summary: &UserWorklogsSummary, drafts: &[Worklog]) -> Vec<Worklog> {
+        let mut result = vec![];
+        for (date, worklog_summary) in summary.iter() {
+            let mut gap = self.schedule.expected_time(date) - worklog_summary.spent_time;
+            for template in &self.templates {
+                if template.is_logged_in(&worklog_summary.worklogs) {
+                    continue;
+                }
+                if let Some(worklog) = template.worklog_for(date, &self.schedule, &self.timezone) {
+                    gap = gap - worklog.time_spent;
+                    result.push(worklog);
+                }
+            }
+            let mut day_drafts: Vec<&Worklog> = drafts
+                .iter()
+                .filter(|draft| draft.date_in(&self.timezone) == *date)
+                .filter(|draft| {
+                    !worklog_summary
+                        .worklogs
+                        .iter()
+                        .any(|worklog| worklog.issue_key == draft.issue_key)
+                })
+                .collect();
+            day_drafts.sort_by_key(|draft| -draft.time_spent);
+
+            for draft in day_drafts {
+                if gap <= Duration::zero() {
+                    break;
+                }
+                let mut filled = draft.clone();
+                filled.time_spent = draft.time_spent.min(gap);
+                gap = gap - filled.time_spent;
+                result.push(filled);
+            }
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::drafts::TemplateRecurrence;
+    use crate::test_helpers::started_at;
+    use crate::time_tracker::WorklogSummary;
+    use chrono::{NaiveDate, Weekday};
+
+    #[test]
+    fn fill_uses_drafts_only_up_to_expected_time() {
+        let short_day = NaiveDate::from_ymd(2022, 9, 15);
+        let full_day = NaiveDate::from_ymd(2022, 9, 16);
+        let logged = Worklog::new(started_at(short_day), "ABC-1", "", Duration::hours(5));
+        let mut summary = UserWorklogsSummary::new();
+        summary.insert(
+            short_day,
+            WorklogSummary {
+                spent_time: Duration::hours(5),
+                worklogs: vec![logged],
+            },
+        );
+        summary.insert(
+            full_day,
+            WorklogSummary {
+                spent_time: Duration::hours(8),
+                worklogs: vec![],
+            },
+        );
+        let drafts = [
+            Worklog::new(started_at(short_day), "ABC-1", "", Duration::hours(1)),
+            Worklog::new(started_at(short_day), "ABC-2", "", Duration::hours(1)),
+            Worklog::new(started_at(short_day), "ABC-3", "", Duration::hours(4)),
+            Worklog::new(started_at(full_day), "ABC-4", "", Duration::hours(1)),
+        ];
+
+        let uut = GapFiller::new(WorkSchedule::new(Duration::hours(8)));
+
+        assert_eq!(
+            uut.fill(&summary, &drafts),
+            vec![Worklog::new(
+                started_at(short_day),
+                "ABC-3",
+                "",
+                Duration::hours(3)
+            )]
+        );
+    }
+
+    #[test]
+    fn fill_logs_templates_once_per_day() {
+        let monday = NaiveDate::from_ymd(2022, 9, 12);
+        let tuesday = NaiveDate::from_ymd(2022, 9, 13);
+        let template = WorklogTemplate::new(
+            "standup",
+            "ABC-1",
+            Duration::minutes(15),
+            TemplateRecurrence::Weekdays(vec![Weekday::Mon, Weekday::Tue]),
+        );
+        let logged = template
+            .worklog_for(
+                &monday,
+                &WorkSchedule::new(Duration::hours(8)),
+                &chrono_tz::UTC,
+            )
+            .unwrap();
+        let mut summary = UserWorklogsSummary::new();
+        summary.insert(
+            monday,
+            WorklogSummary {
+                spent_time: Duration::minutes(15),
+                worklogs: vec![logged],
+            },
+        );
+        summary.insert(
+            tuesday,
+            WorklogSummary {
+                spent_time: Duration::hours(7),
+                worklogs: vec![],
+            },
+        );
+        let drafts = [Worklog::new(
+            started_at(tuesday),
+            "ABC-2",
+            "",
+            Duration::hours(2),
+        )];
+
+        let uut = GapFiller::new(WorkSchedule::new(Duration::hours(8))).with_templates(&[template]);
+        let filled = uut.fill(&summary, &drafts);
+
+        assert_eq!(filled.len(), 2);
+        assert_eq!(filled[0].issue_key, "ABC-1");
+        assert_eq!(filled[0].date(), tuesday);
+        assert_eq!(
+            filled[1],
+            Worklog::new(started_at(tuesday), "ABC-2", "", Duration::minutes(45))
+        );
+    }
+
+    #[test]
+    fn fill_matches_drafts_to_days_in_configured_timezone() {
+        let date = NaiveDate::from_ymd(2022, 9, 15);
+        let mut summary = UserWorklogsSummary::new();
+        summary.insert(
+            date,
+            WorklogSummary {
+                spent_time: Duration::hours(7),
+                worklogs: vec![],
+            },
+        );
+        let started = |time| chrono::DateTime::parse_from_rfc3339(time).unwrap();
+        let drafts = [
+            Worklog::new(
+                started("2022-09-14T23:30:00+00:00"),
+                "ABC-1",
+                "",
+                Duration::hours(1),
+            ),
+            Worklog::new(
+                started("2022-09-15T22:30:00+00:00"),
+                "ABC-2",
+                "",
+                Duration::hours(1),
+            ),
+        ];
+
+        let uut = GapFiller::new(WorkSchedule::new(Duration::hours(8)))
+            .with_timezone(chrono_tz::Europe::Warsaw);
+
+        assert_eq!(uut.fill(&summary, &drafts), vec![drafts[0].clone()]);
+    }
+}
@@ -0,0 +1,131 @@
+use super::{commit::Commit, git_history_trait::GitHistoryTrait};
+use anyhow::anyhow;
+use chrono::{DateTime, Duration, NaiveDate};
+use std::{path::Path, process::Command};
+
+const FIELD_SEPARATOR: char = '\u{1f}';
+const RECORD_SEPARATOR: char = '\u{1e}';
+const LOG_FORMAT: &str = "--format=%H%x1f%aI%x1f%ae%x1f%S%x1f%s%x1f%b%x1e";
+
+/// Reads commit history by running the `git` executable.
+#[derive(Debug, Default)]
+pub struct GitCli;
+
+impl GitCli {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// `author` is matched literally, so `+` or `.` in an email aren't regex operators.
+    fn log_args(author: &str, start_date: NaiveDate, end_date: NaiveDate) -> Vec<String> {
+        [
+            "log",
+            "--all",
+            "--source",
+            "--no-merges",
+            LOG_FORMAT,
+            "--fixed-strings",
+        ]
+        .into_iter()
+        .map(|arg| arg.to_string())
+        .chain([
+            format!("--author={}", author),
+            format!("--since={}T00:00:00", start_date),
+            format!("--until={}T00:00:00", end_date + Duration::days(1)),
+        ])
+        .collect()
+    }
+
+    fn parse_log(output: &str) -> anyhow::Result<Vec<Commit>> {
+        output
+            .split(RECORD_SEPARATOR)
+            .map(|record| record.trim_start_matches('\n'))
+            .filter(|record| !record.is_empty())
+            .map(|record| {
+                let fields: Vec<&str> = record.split(FIELD_SEPARATOR).collect();
+                let [hash, time, author_email, source_ref, subject, body] = fields.as_slice()
+                else {
+                    return Err(anyhow!("Can't parse git log record '{}'", record));
+                };
+                Ok(Commit {
+                    hash: hash.to_string(),
+                    time: DateTime::parse_from_rfc3339(time)?,
+                    author_email: author_email.to_string(),
+                    source_ref: Some(source_ref.to_string()).filter(|source| !source.is_empty()),
+                    subject: subject.to_string(),
+                    body: body.trim().to_string(),
+                })
+            })
+            .collect()
+    }
+}
+
+impl GitHistoryTrait for GitCli {
+    fn commits(
+        &self,
+        repository: &Path,
+        author: &str,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> anyhow::Result<Vec<Commit>> {
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(repository)
+            .args(Self::log_args(author, start_date, end_date))
+            .output()?;
+        if !output.status.success() {
+            return Err(anyhow!(
+                "git log failed in {}: {}",
+                repository.display(),
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+        Self::parse_log(&String::from_utf8_lossy(&output.stdout))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn log_args_match_author_literally() {
+        let args = GitCli::log_args(
+            "jan+work@example.com",
+            NaiveDate::from_ymd(2022, 9, 15),
+            NaiveDate::from_ymd(2022, 9, 16),
+        );
+
+        assert!(args.contains(&"--fixed-strings".to_string()));
+        assert!(args.contains(&"--author=jan+work@example.com".to_string()));
+        assert!(args.contains(&"--until=2022-09-17T00:00:00".to_string()));
+    }
+
+    #[test]
+    fn parse_log_splits_records_and_fields() {
+        let output = "abc\u{1f}2022-09-15T09:30:00+02:00\u{1f}jan@example.com\u{1f}refs/heads/ABC-1-login\u{1f}Fix login\u{1f}Details\n\u{1e}\n\
+                      def\u{1f}2022-09-15T11:00:00+02:00\u{1f}jan@example.com\u{1f}\u{1f}ABC-2 docs\u{1f}\u{1e}\n";
+
+        assert_eq!(
+            GitCli::parse_log(output).unwrap(),
+            vec![
+                Commit {
+                    hash: "abc".to_string(),
+                    time: DateTime::parse_from_rfc3339("2022-09-15T09:30:00+02:00").unwrap(),
+                    author_email: "jan@example.com".to_string(),
+                    source_ref: Some("refs/heads/ABC-1-login".to_string()),
+                    subject: "Fix login".to_string(),
+                    body: "Details".to_string(),
+                },
+                Commit {
+                    hash: "def".to_string(),
+                    time: DateTime::parse_from_rfc3339("2022-09-15T11:00:00+02:00").unwrap(),
+                    author_email: "jan@example.com".to_string(),
+                    source_ref: None,
+                    subject: "ABC-2 docs".to_string(),
+                    body: "".to_string(),
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,313 @@
+use super::{
+    commit::Commit, draft_generator_trait::DraftGeneratorTrait,
+    estimation_heuristics::EstimationHeuristics, git_history_trait::GitHistoryTrait,
+    DRAFT_SOURCE_METADATA_KEY,
+};
+use crate::jira::{Worklog, DEFAULT_ISSUE_KEY_PATTERN};
+use chrono::{DateTime, Duration, FixedOffset, NaiveDate};
+use chrono_tz::Tz;
+use itertools::Itertools;
+use regex::Regex;
+use std::{collections::BTreeMap, path::PathBuf};
+
+struct DraftAccumulator {
+    started: DateTime<FixedOffset>,
+    time_spent: Duration,
+    subjects: Vec<String>,
+}
+
+/// Suggests worklogs from the user's commits in local git repositories.
+pub struct GitDraftGenerator<GitHistoryType>
+where
+    GitHistoryType: GitHistoryTrait,
+{
+    git_history: GitHistoryType,
+    repositories: Vec<PathBuf>,
+    author: String,
+    issue_key_regex: Regex,
+    heuristics: EstimationHeuristics,
+    timezone: Option<Tz>,
+}
+
+impl<GitHistoryType> DraftGeneratorTrait for GitDraftGenerator<GitHistoryType>
+where
+    GitHistoryType: GitHistoryTrait,
+{
+    fn generate(&self, start_date: NaiveDate, end_date: NaiveDate) -> anyhow::Result<Vec<Worklog>> {
+        // Git reads the dates in the machine's timezone, so ask for a day more on each side
+        // and keep the commits whose day in the configured timezone is in range.
+        let mut commits = vec![];
+        for repository in &self.repositories {
+            commits.extend(self.git_history.commits(
+                repository,
+                &self.author,
+                start_date - Duration::days(1),
+                end_date + Duration::days(1),
+            )?);
+        }
+        let commits: Vec<Commit> = commits
+            .into_iter()
+            .unique_by(|commit| commit.hash.clone())
+            .sorted_by_key(|commit| commit.time)
+            .collect();
+
+        // Commits' offsets can alternate between days, e.g. around DST changes, so collect
+        // each day's commits before estimating it.
+        let mut commits_by_date: BTreeMap<NaiveDate, Vec<&Commit>> = BTreeMap::new();
+        for commit in &commits {
+            commits_by_date
+                .entry(self.date_of(commit))
+                .or_default()
+                .push(commit);
+        }
+        let mut drafts = vec![];
+        for (_, day_commits) in commits_by_date.range(start_date..=end_date) {
+            drafts.extend(self.estimate_day(day_commits.iter().copied()));
+        }
+        drafts.sort_by_key(|draft: &Worklog| draft.started);
+        Ok(drafts)
+    }
+}
+
+impl<GitHistoryType> GitDraftGenerator<GitHistoryType>
+where
+    GitHistoryType: GitHistoryTrait,
+{
+    pub fn new(git_history: GitHistoryType, author: &str) -> Self {
+        Self {
+            git_history,
+            repositories: vec![],
+            author: author.to_string(),
+            issue_key_regex: Regex::new(DEFAULT_ISSUE_KEY_PATTERN).unwrap(),
+            heuristics: EstimationHeuristics::default(),
+            timezone: None,
+        }
+    }
+
+    pub fn with_repository(mut self, repository: impl Into<PathBuf>) -> Self {
+        self.repositories.push(repository.into());
+        self
+    }
+
+    pub fn with_issue_key_regex(mut self, issue_key_regex: Regex) -> Self {
+        self.issue_key_regex = issue_key_regex;
+        self
+    }
+
+    pub fn with_heuristics(mut self, heuristics: EstimationHeuristics) -> Self {
+        self.heuristics = heuristics;
+        self
+    }
+
+    pub fn with_timezone(mut self, timezone: Tz) -> Self {
+        self.timezone = Some(timezone);
+        self
+    }
+
+    fn date_of(&self, commit: &Commit) -> NaiveDate {
+        match &self.timezone {
+            Some(timezone) => commit.time.with_timezone(timezone).naive_local().date(),
+            None => commit.time.naive_local().date(),
+        }
+    }
+
+    /// Message keys win over branch keys, so merges of other branches are attributed right.
+    fn issue_key_of(&self, commit: &Commit) -> Option<String> {
+        [
+            Some(commit.subject.as_str()),
+            Some(commit.body.as_str()),
+            commit.source_ref.as_deref(),
+        ]
+        .into_iter()
+        .flatten()
+        .find_map(|text| self.issue_key_regex.find(text))
+        .map(|issue_key| issue_key.as_str().to_string())
+    }
+
+    /// Each commit is credited with the time since the previous commit of the same session.
+    fn estimate_day<'a>(&self, commits: impl Iterator<Item = &'a Commit>) -> Vec<Worklog> {
+        let mut accumulators: BTreeMap<String, DraftAccumulator> = BTreeMap::new();
+        let mut previous_time: Option<DateTime<FixedOffset>> = None;
+
+        for commit in commits {
+            let estimate = match previous_time {
+                Some(previous_time)
+                    if commit.time - previous_time <= self.heuristics.session_gap =>
+                {
+                    commit.time - previous_time
+                }
+                _ => self.heuristics.first_commit_time,
+            };
+            previous_time = Some(commit.time);
+
+            if let Some(issue_key) = self.issue_key_of(commit) {
+                let accumulator =
+                    accumulators
+                        .entry(issue_key)
+                        .or_insert_with(|| DraftAccumulator {
+                            started: commit.time - estimate,
+                            time_spent: Duration::zero(),
+                            subjects: vec![],
+                        });
+                accumulator.time_spent = accumulator.time_spent + estimate;
+                accumulator.subjects.push(commit.subject.clone());
+            }
+        }
+
+        accumulators
+            .into_iter()
+            .map(|(issue_key, accumulator)| {
+                Worklog::new(
+                    accumulator.started,
+                    &issue_key,
+                    "",
+                    self.heuristics.round(accumulator.time_spent),
+                )
+                .with_comment(&accumulator.subjects.join("; "))
+                .with_metadata(DRAFT_SOURCE_METADATA_KEY, "git")
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::drafts::testing::MockGitHistoryTrait;
+    use std::path::Path;
+
+    fn commit(hash: &str, time: &str, source_ref: &str, subject: &str) -> Commit {
+        Commit {
+            hash: hash.to_string(),
+            time: DateTime::parse_from_rfc3339(time).unwrap(),
+            author_email: "jan@example.com".to_string(),
+            source_ref: Some(source_ref.to_string()),
+            subject: subject.to_string(),
+            body: "".to_string(),
+        }
+    }
+
+    #[test]
+    fn generate_estimates_time_per_issue_from_commit_sessions() {
+        let date = NaiveDate::from_ymd(2022, 9, 15);
+        let mut git_history = MockGitHistoryTrait::new();
+        git_history
+            .expect_commits()
+            .withf(|repository, author, _, _| {
+                repository == Path::new("/src/web") && author == "jan@example.com"
+            })
+            .return_once(|_, _, _, _| {
+                Ok(vec![
+                    commit(
+                        "1",
+                        "2022-09-15T09:00:00+02:00",
+                        "refs/heads/ABC-1-login",
+                        "Start login",
+                    ),
+                    commit(
+                        "2",
+                        "2022-09-15T09:50:00+02:00",
+                        "refs/heads/ABC-1-login",
+                        "Finish login",
+                    ),
+                    commit(
+                        "3",
+                        "2022-09-15T10:20:00+02:00",
+                        "refs/heads/main",
+                        "XYZ-9 hotfix",
+                    ),
+                    commit(
+                        "4",
+                        "2022-09-15T10:25:00+02:00",
+                        "refs/heads/main",
+                        "Bump version",
+                    ),
+                    commit(
+                        "5",
+                        "2022-09-15T15:00:00+02:00",
+                        "refs/heads/ABC-1-login",
+                        "Review fixes",
+                    ),
+                ])
+            });
+
+        let uut =
+            GitDraftGenerator::new(git_history, "jan@example.com").with_repository("/src/web");
+
+        assert_eq!(
+            uut.generate(date, date).unwrap(),
+            vec![
+                Worklog::new(
+                    DateTime::parse_from_rfc3339("2022-09-15T08:30:00+02:00").unwrap(),
+                    "ABC-1",
+                    "",
+                    Duration::minutes(120),
+                )
+                .with_comment("Start login; Finish login; Review fixes")
+                .with_metadata(DRAFT_SOURCE_METADATA_KEY, "git"),
+                Worklog::new(
+                    DateTime::parse_from_rfc3339("2022-09-15T09:50:00+02:00").unwrap(),
+                    "XYZ-9",
+                    "",
+                    Duration::minutes(30),
+                )
+                .with_comment("XYZ-9 hotfix")
+                .with_metadata(DRAFT_SOURCE_METADATA_KEY, "git"),
+            ]
+        );
+    }
+
+    #[test]
+    fn generate_assigns_late_commits_to_days_in_configured_timezone() {
+        let date = NaiveDate::from_ymd(2022, 9, 15);
+        let mut git_history = MockGitHistoryTrait::new();
+        git_history
+            .expect_commits()
+            .withf(|_, _, start_date, end_date| {
+                *start_date == NaiveDate::from_ymd(2022, 9, 14)
+                    && *end_date == NaiveDate::from_ymd(2022, 9, 16)
+            })
+            .return_once(|_, _, _, _| {
+                Ok(vec![
+                    commit("1", "2022-09-14T23:30:00+00:00", "", "ABC-1 early"),
+                    commit("2", "2022-09-15T22:30:00+00:00", "", "ABC-2 late"),
+                ])
+            });
+
+        let uut = GitDraftGenerator::new(git_history, "jan@example.com")
+            .with_repository("/src/web")
+            .with_timezone(chrono_tz::Europe::Warsaw);
+
+        let drafts = uut.generate(date, date).unwrap();
+        assert_eq!(
+            drafts
+                .iter()
+                .map(|draft| draft.issue_key.as_str())
+                .collect::<Vec<_>>(),
+            vec!["ABC-1"]
+        );
+    }
+
+    #[test]
+    fn generate_drafts_once_per_issue_and_day_when_commit_offsets_alternate() {
+        let date = NaiveDate::from_ymd(2022, 9, 15);
+        let mut git_history = MockGitHistoryTrait::new();
+        git_history.expect_commits().return_once(|_, _, _, _| {
+            Ok(vec![
+                commit("1", "2022-09-15T23:00:00+00:00", "", "ABC-1 start"),
+                commit("2", "2022-09-16T01:10:00+02:00", "", "ABC-1 other machine"),
+                commit("3", "2022-09-15T23:20:00+00:00", "", "ABC-1 finish"),
+            ])
+        });
+
+        let uut =
+            GitDraftGenerator::new(git_history, "jan@example.com").with_repository("/src/web");
+
+        let drafts = uut.generate(date, date).unwrap();
+        assert_eq!(drafts.len(), 1);
+        assert_eq!(
+            drafts[0].comment.as_deref(),
+            Some("ABC-1 start; ABC-1 finish")
+        );
+    }
+}
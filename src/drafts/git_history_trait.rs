@@ -0,0 +1,17 @@
+use super::commit::Commit;
+use chrono::NaiveDate;
+use std::path::Path;
+
+#[cfg(test)]
+use mockall::{automock, predicate::*};
+
+#[cfg_attr(test, automock)]
+pub trait GitHistoryTrait {
+    fn commits(
+        &self,
+        repository: &Path,
+        author: &str,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> anyhow::Result<Vec<Commit>>;
+}
@@ -0,0 +1,749 @@
+use super::windows_timezones::parse_tzid;
+use crate::jira::checked_seconds;
+use anyhow::anyhow;
+use chrono::{
+    DateTime, Datelike, Duration, FixedOffset, NaiveDate, NaiveDateTime, Offset, TimeZone, Weekday,
+};
+use chrono_tz::Tz;
+use std::{collections::BTreeMap, fmt::Display};
+
+const MAX_OCCURRENCES: usize = 10_000;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+/// A BYDAY entry; `ordinal` picks the n-th weekday of the month, counted from its end
+/// when negative, and is only allowed in monthly rules.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct ByDay {
+    pub ordinal: Option<i32>,
+    pub weekday: Weekday,
+}
+
+/// Supports FREQ, INTERVAL, COUNT, UNTIL and BYDAY (not in yearly rules); rules with other
+/// parts are rejected, so their events are skipped instead of expanded on wrong dates.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct RecurrenceRule {
+    pub frequency: Frequency,
+    pub interval: u32,
+    pub count: Option<usize>,
+    pub until: Option<DateTime<FixedOffset>>,
+    pub by_day: Vec<ByDay>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct IcsEvent {
+    pub uid: String,
+    pub summary: String,
+    pub start: DateTime<FixedOffset>,
+    pub end: DateTime<FixedOffset>,
+    /// Timezone of DTSTART, used to expand recurrence.
+    pub timezone: Tz,
+    pub all_day: bool,
+    pub cancelled: bool,
+    /// Attendee e-mails that declined the event.
+    pub declined_by: Vec<String>,
+    pub rrule: Option<RecurrenceRule>,
+    pub exdates: Vec<DateTime<FixedOffset>>,
+    /// Set on instances overriding a single occurrence of a recurring event.
+    pub recurrence_id: Option<DateTime<FixedOffset>>,
+}
+
+impl IcsEvent {
+    pub fn is_declined_by(&self, email: &str) -> bool {
+        self.declined_by
+            .iter()
+            .any(|declined| declined.eq_ignore_ascii_case(email))
+    }
+
+    /// Start times of all occurrences starting on or before `until`. Recurrence is
+    /// expanded in the event's timezone, so occurrences keep local time across DST changes.
+    /// Fails instead of truncating when that takes more than `MAX_OCCURRENCES` periods.
+    pub fn occurrences(&self, until: NaiveDate) -> anyhow::Result<Vec<DateTime<FixedOffset>>> {
+        let rrule = match &self.rrule {
+            Some(rrule) => rrule,
+            None => return Ok(vec![self.start]),
+        };
+        let local_start = self.start.with_timezone(&self.timezone).naive_local();
+        let interval = rrule.interval as i64;
+
+        let mut occurrences = vec![];
+        let mut emitted = 0;
+        for period in 0..MAX_OCCURRENCES as i64 {
+            let Some(steps) = period.checked_mul(interval) else {
+                return Ok(occurrences);
+            };
+            let period_start = match rrule.frequency {
+                Frequency::Daily => add_seconds(local_start, steps, 24 * 3600),
+                Frequency::Weekly => add_seconds(local_start, steps, 7 * 24 * 3600),
+                Frequency::Monthly if !rrule.by_day.is_empty() => month_start(local_start, steps),
+                Frequency::Monthly | Frequency::Yearly => {
+                    let months = match rrule.frequency {
+                        Frequency::Yearly => steps.checked_mul(12),
+                        _ => Some(steps),
+                    };
+                    match months.and_then(|months| month_start(local_start, months)) {
+                        Some(month_start) => match month_start.with_day(local_start.day()) {
+                            Some(period_start) => Some(period_start),
+                            None => continue,
+                        },
+                        None => None,
+                    }
+                }
+            };
+            // Later periods only start further out of chrono's range, so none can follow.
+            let Some(period_start) = period_start else {
+                return Ok(occurrences);
+            };
+            let candidates = match rrule.frequency {
+                Frequency::Weekly if !rrule.by_day.is_empty() => {
+                    let week_start = period_start
+                        - Duration::days(period_start.weekday().num_days_from_monday() as i64);
+                    let mut days: Vec<NaiveDateTime> = rrule
+                        .by_day
+                        .iter()
+                        .filter_map(|by_day| {
+                            week_start.checked_add_signed(Duration::days(
+                                by_day.weekday.num_days_from_monday() as i64,
+                            ))
+                        })
+                        .filter(|candidate| *candidate >= local_start)
+                        .collect();
+                    days.sort();
+                    days
+                }
+                Frequency::Daily if !rrule.by_day.is_empty() => {
+                    match rrule
+                        .by_day
+                        .iter()
+                        .any(|by_day| by_day.weekday == period_start.weekday())
+                    {
+                        true => vec![period_start],
+                        false => vec![],
+                    }
+                }
+                Frequency::Monthly if !rrule.by_day.is_empty() => {
+                    let mut days: Vec<NaiveDateTime> = rrule
+                        .by_day
+                        .iter()
+                        .flat_map(|by_day| month_days(period_start.date(), by_day))
+                        .map(|date| date.and_time(local_start.time()))
+                        .filter(|candidate| *candidate >= local_start)
+                        .collect();
+                    days.sort();
+                    days.dedup();
+                    days
+                }
+                _ => vec![period_start],
+            };
+
+            for candidate in candidates {
+                if candidate.date() > until {
+                    return Ok(occurrences);
+                }
+                let Some(candidate) = self.timezone.from_local_datetime(&candidate).earliest()
+                else {
+                    continue;
+                };
+                let candidate = candidate.with_timezone(&candidate.offset().fix());
+                if rrule.until.is_some_and(|rule_until| candidate > rule_until)
+                    || rrule.count.is_some_and(|count| emitted >= count)
+                {
+                    return Ok(occurrences);
+                }
+                emitted += 1;
+                if !self.exdates.contains(&candidate) {
+                    occurrences.push(candidate);
+                }
+            }
+        }
+        Err(anyhow!(
+            "Recurrence needs more than {} periods to reach {}",
+            MAX_OCCURRENCES,
+            until
+        ))
+    }
+}
+
+/// A VEVENT left out of the calendar because it couldn't be read or expanded.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SkippedEvent {
+    pub uid: String,
+    pub summary: String,
+    pub reason: String,
+}
+
+impl Display for SkippedEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Skipped event '{}' ({}): {}",
+            self.summary, self.uid, self.reason
+        )
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct IcsCalendar {
+    pub events: Vec<IcsEvent>,
+    pub skipped: Vec<SkippedEvent>,
+}
+
+/// `date_time` moved by `steps` times `step_seconds`, `None` when out of range.
+fn add_seconds(date_time: NaiveDateTime, steps: i64, step_seconds: i64) -> Option<NaiveDateTime> {
+    let duration = checked_seconds(steps.checked_mul(step_seconds)?)?;
+    date_time.checked_add_signed(duration)
+}
+
+/// First day of the month `months` after the one of `date_time`, at its time; `None`
+/// when out of range.
+fn month_start(date_time: NaiveDateTime, months: i64) -> Option<NaiveDateTime> {
+    let month_index = (date_time.month0() as i64).checked_add(months)?;
+    let year = i32::try_from(month_index / 12)
+        .ok()
+        .and_then(|years| date_time.year().checked_add(years))?;
+    NaiveDate::from_ymd_opt(year, u32::try_from(month_index % 12).ok()? + 1, 1)
+        .map(|date| date.and_time(date_time.time()))
+}
+
+/// Days of the month starting at `first_day` matching a BYDAY entry.
+fn month_days(first_day: NaiveDate, by_day: &ByDay) -> Vec<NaiveDate> {
+    let days: Vec<NaiveDate> = first_day
+        .iter_days()
+        .take_while(|date| date.month() == first_day.month())
+        .filter(|date| date.weekday() == by_day.weekday)
+        .collect();
+    match by_day.ordinal {
+        None => days,
+        Some(ordinal) if ordinal > 0 => days
+            .get(ordinal as usize - 1)
+            .into_iter()
+            .copied()
+            .collect(),
+        Some(ordinal) => days
+            .len()
+            .checked_sub(ordinal.unsigned_abs() as usize)
+            .and_then(|index| days.get(index))
+            .into_iter()
+            .copied()
+            .collect(),
+    }
+}
+
+type Property = (String, BTreeMap<String, String>, String);
+
+/// Parses VEVENTs of an iCalendar file; floating times are interpreted in `timezone`.
+/// Events that can't be read are reported in `skipped` instead of failing the calendar.
+pub fn parse_calendar(content: &str, timezone: &Tz) -> IcsCalendar {
+    let mut calendar = IcsCalendar::default();
+    let mut properties: Option<Vec<Property>> = None;
+
+    for line in unfold(content) {
+        if line == "BEGIN:VEVENT" {
+            properties = Some(vec![]);
+        } else if line == "END:VEVENT" {
+            if let Some(properties) = properties.take() {
+                match create_event(&properties, timezone) {
+                    Ok(event) => calendar.events.push(event),
+                    Err(error) => calendar.skipped.push(SkippedEvent {
+                        uid: property_text(&properties, "UID"),
+                        summary: property_text(&properties, "SUMMARY"),
+                        reason: error.to_string(),
+                    }),
+                }
+            }
+        } else if let Some(properties) = properties.as_mut() {
+            if let Some(property) = parse_property(&line) {
+                properties.push(property);
+            }
+        }
+    }
+    calendar
+}
+
+fn property_text(properties: &[Property], name: &str) -> String {
+    properties
+        .iter()
+        .find(|(key, _, _)| key == name)
+        .map(|(_, _, value)| unescape(value))
+        .unwrap_or_default()
+}
+
+fn unfold(content: &str) -> Vec<String> {
+    let mut lines: Vec<String> = vec![];
+    for line in content.lines() {
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(continuation), Some(last)) => last.push_str(continuation),
+            _ => lines.push(line.trim_end_matches('\r').to_string()),
+        }
+    }
+    lines
+}
+
+fn parse_property(line: &str) -> Option<Property> {
+    let (head, value) = line.split_once(':')?;
+    let mut parts = head.split(';');
+    let name = parts.next()?.to_ascii_uppercase();
+    let parameters = parts
+        .filter_map(|parameter| parameter.split_once('='))
+        .map(|(key, value)| {
+            (
+                key.to_ascii_uppercase(),
+                value.trim_matches('"').to_string(),
+            )
+        })
+        .collect();
+    Some((name, parameters, value.to_string()))
+}
+
+fn unescape(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut characters = text.chars();
+    while let Some(character) = characters.next() {
+        match (character, characters.clone().next()) {
+            ('\\', Some('n' | 'N')) => result.push(' '),
+            ('\\', Some(escaped @ (',' | ';' | '\\'))) => result.push(escaped),
+            _ => {
+                result.push(character);
+                continue;
+            }
+        }
+        characters.next();
+    }
+    result
+}
+
+fn parse_date_time(
+    value: &str,
+    parameters: &BTreeMap<String, String>,
+    timezone: &Tz,
+) -> anyhow::Result<(DateTime<FixedOffset>, bool, Tz)> {
+    let to_fixed = |date_time: DateTime<Tz>| date_time.with_timezone(&date_time.offset().fix());
+    let local_timezone = match parameters.get("TZID") {
+        Some(tzid) => parse_tzid(tzid).ok_or_else(|| anyhow!("Unknown TZID '{}'", tzid))?,
+        None => *timezone,
+    };
+
+    if parameters.get("VALUE").map(String::as_str) == Some("DATE") || value.len() == 8 {
+        let date = NaiveDate::parse_from_str(value, "%Y%m%d")?;
+        let start = local_timezone
+            .from_local_datetime(&date.and_hms(0, 0, 0))
+            .earliest()
+            .ok_or(anyhow!("Invalid date '{}'", value))?;
+        return Ok((to_fixed(start), true, local_timezone));
+    }
+    if let Some(utc_value) = value.strip_suffix('Z') {
+        let date_time = NaiveDateTime::parse_from_str(utc_value, "%Y%m%dT%H%M%S")?;
+        return Ok((
+            chrono::Utc.from_utc_datetime(&date_time).into(),
+            false,
+            chrono_tz::UTC,
+        ));
+    }
+    let date_time = NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S")?;
+    let start = local_timezone
+        .from_local_datetime(&date_time)
+        .earliest()
+        .ok_or(anyhow!("Invalid local time '{}'", value))?;
+    Ok((to_fixed(start), false, local_timezone))
+}
+
+fn parse_duration(value: &str) -> anyhow::Result<Duration> {
+    let invalid = || anyhow!("Invalid duration '{}'", value);
+    let (negative, unsigned) = match value.strip_prefix('-') {
+        Some(unsigned) => (true, unsigned),
+        None => (false, value.strip_prefix('+').unwrap_or(value)),
+    };
+    let mut seconds: i64 = 0;
+    let mut number = String::new();
+    for character in unsigned.strip_prefix('P').ok_or_else(invalid)?.chars() {
+        match character {
+            '0'..='9' => number.push(character),
+            'T' => {}
+            unit => {
+                let amount: i64 = number.parse().map_err(|_| invalid())?;
+                number.clear();
+                let unit_seconds = match unit {
+                    'W' => 7 * 24 * 3600,
+                    'D' => 24 * 3600,
+                    'H' => 3600,
+                    'M' => 60,
+                    'S' => 1,
+                    _ => return Err(invalid()),
+                };
+                seconds = amount
+                    .checked_mul(unit_seconds)
+                    .and_then(|unit_total| seconds.checked_add(unit_total))
+                    .ok_or_else(invalid)?;
+            }
+        }
+    }
+    if !number.is_empty() {
+        return Err(invalid());
+    }
+    checked_seconds(if negative { -seconds } else { seconds }).ok_or_else(invalid)
+}
+
+fn parse_by_day(value: &str) -> anyhow::Result<ByDay> {
+    let invalid = || anyhow!("Invalid BYDAY '{}'", value);
+    let weekday_index = value.len().checked_sub(2).ok_or_else(invalid)?;
+    let weekday = match value.get(weekday_index..).ok_or_else(invalid)? {
+        "MO" => Weekday::Mon,
+        "TU" => Weekday::Tue,
+        "WE" => Weekday::Wed,
+        "TH" => Weekday::Thu,
+        "FR" => Weekday::Fri,
+        "SA" => Weekday::Sat,
+        "SU" => Weekday::Sun,
+        _ => return Err(invalid()),
+    };
+    let ordinal = match &value[..weekday_index] {
+        "" => None,
+        ordinal => Some(
+            ordinal
+                .parse::<i32>()
+                .ok()
+                .filter(|ordinal| *ordinal != 0 && ordinal.abs() <= 5)
+                .ok_or_else(invalid)?,
+        ),
+    };
+    Ok(ByDay { ordinal, weekday })
+}
+
+fn parse_rrule(value: &str, timezone: &Tz) -> anyhow::Result<RecurrenceRule> {
+    let parts: BTreeMap<&str, &str> = value
+        .split(';')
+        .filter_map(|part| part.split_once('='))
+        .collect();
+    let frequency = match parts.get("FREQ").copied() {
+        Some("DAILY") => Frequency::Daily,
+        Some("WEEKLY") => Frequency::Weekly,
+        Some("MONTHLY") => Frequency::Monthly,
+        Some("YEARLY") => Frequency::Yearly,
+        other => return Err(anyhow!("Unsupported RRULE frequency {:?}", other)),
+    };
+    if let Some(part) = parts
+        .keys()
+        .find(|part| part.starts_with("BY") && **part != "BYDAY")
+    {
+        return Err(anyhow!("Unsupported RRULE part {}", part));
+    }
+    let by_day = parts
+        .get("BYDAY")
+        .map(|by_day| by_day.split(',').map(parse_by_day).collect())
+        .transpose()?
+        .unwrap_or_else(Vec::new);
+    if frequency == Frequency::Yearly && !by_day.is_empty() {
+        return Err(anyhow!("Unsupported RRULE part BYDAY in a yearly rule"));
+    }
+    if frequency != Frequency::Monthly && by_day.iter().any(|by_day| by_day.ordinal.is_some()) {
+        return Err(anyhow!(
+            "BYDAY ordinals are only supported in monthly rules"
+        ));
+    }
+    let interval = parts
+        .get("INTERVAL")
+        .map(|interval| interval.parse())
+        .transpose()?
+        .unwrap_or(1);
+    if interval == 0 {
+        return Err(anyhow!("RRULE INTERVAL must be positive"));
+    }
+    Ok(RecurrenceRule {
+        frequency,
+        interval,
+        count: parts.get("COUNT").map(|count| count.parse()).transpose()?,
+        until: parts
+            .get("UNTIL")
+            .map(|until| parse_date_time(until, &BTreeMap::new(), timezone))
+            .transpose()?
+            .map(|(until, all_day, _)| {
+                if all_day {
+                    until + Duration::days(1)
+                } else {
+                    until
+                }
+            }),
+        by_day,
+    })
+}
+
+fn create_event(properties: &[Property], timezone: &Tz) -> anyhow::Result<IcsEvent> {
+    let property = |name: &str| properties.iter().find(|(key, _, _)| key == name);
+
+    let (_, start_parameters, start_value) =
+        property("DTSTART").ok_or(anyhow!("VEVENT without DTSTART"))?;
+    let (start, all_day, start_timezone) =
+        parse_date_time(start_value, start_parameters, timezone)?;
+    let end = match (property("DTEND"), property("DURATION")) {
+        (Some((_, parameters, value)), _) => parse_date_time(value, parameters, timezone)?.0,
+        (None, Some((_, _, value))) => start + parse_duration(value)?,
+        (None, None) if all_day => start + Duration::days(1),
+        (None, None) => start,
+    };
+
+    let mut exdates = vec![];
+    for (_, parameters, value) in properties.iter().filter(|(key, _, _)| key == "EXDATE") {
+        for exdate in value.split(',') {
+            exdates.push(parse_date_time(exdate, parameters, timezone)?.0);
+        }
+    }
+
+    Ok(IcsEvent {
+        uid: property_text(properties, "UID"),
+        summary: property_text(properties, "SUMMARY"),
+        start,
+        end,
+        timezone: start_timezone,
+        all_day,
+        cancelled: property("STATUS").is_some_and(|(_, _, value)| value == "CANCELLED"),
+        declined_by: properties
+            .iter()
+            .filter(|(key, parameters, _)| {
+                key == "ATTENDEE"
+                    && parameters.get("PARTSTAT").map(String::as_str) == Some("DECLINED")
+            })
+            .map(|(_, _, value)| {
+                value
+                    .trim_start_matches("mailto:")
+                    .trim_start_matches("MAILTO:")
+                    .to_string()
+            })
+            .collect(),
+        rrule: property("RRULE")
+            .map(|(_, _, value)| parse_rrule(value, timezone))
+            .transpose()?,
+        exdates,
+        recurrence_id: property("RECURRENCE-ID")
+            .map(|(_, parameters, value)| parse_date_time(value, parameters, timezone))
+            .transpose()?
+            .map(|(recurrence_id, _, _)| recurrence_id),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_calendar_reads_folded_lines_timezones_and_recurrence() {
+        let content = "BEGIN:VCALENDAR\r\n\
+BEGIN:VEVENT\r\n\
+UID:standup\r\n\
+SUMMARY:Daily standup\\, team\r\n\
+\x20\x20A\r\n\
+DTSTART;TZID=Europe/Warsaw:20220912T093000\r\n\
+DURATION:PT15M\r\n\
+RRULE:FREQ=WEEKLY;BYDAY=MO,WE,FR;COUNT=4\r\n\
+EXDATE;TZID=Europe/Warsaw:20220914T093000\r\n\
+ATTENDEE;PARTSTAT=DECLINED;CN=Jan:mailto:jan@example.com\r\n\
+END:VEVENT\r\n\
+END:VCALENDAR\r\n";
+
+        let events = parse_calendar(content, &chrono_tz::UTC).events;
+
+        assert_eq!(events.len(), 1);
+        let event = &events[0];
+        assert_eq!(event.summary, "Daily standup, team A");
+        assert_eq!(
+            event.start,
+            DateTime::parse_from_rfc3339("2022-09-12T09:30:00+02:00").unwrap()
+        );
+        assert_eq!(event.end - event.start, Duration::minutes(15));
+        assert!(event.is_declined_by("JAN@example.com"));
+        assert_eq!(
+            event
+                .occurrences(NaiveDate::from_ymd(2022, 12, 31))
+                .unwrap(),
+            ["2022-09-12", "2022-09-16", "2022-09-19"]
+                .iter()
+                .map(
+                    |date| DateTime::parse_from_rfc3339(&format!("{}T09:30:00+02:00", date))
+                        .unwrap()
+                )
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn occurrences_keep_local_time_across_dst_change() {
+        let content = "BEGIN:VEVENT\nDTSTART;TZID=Europe/Warsaw:20221026T100000\nRRULE:FREQ=WEEKLY;INTERVAL=2\nEND:VEVENT\n";
+        let event = parse_calendar(content, &chrono_tz::UTC).events.remove(0);
+
+        assert_eq!(
+            event.occurrences(NaiveDate::from_ymd(2022, 11, 9)).unwrap(),
+            vec![
+                DateTime::parse_from_rfc3339("2022-10-26T10:00:00+02:00").unwrap(),
+                DateTime::parse_from_rfc3339("2022-11-09T10:00:00+01:00").unwrap(),
+            ]
+        );
+    }
+
+    #[test]
+    fn occurrences_supports_intervals_until_and_monthly_rules() {
+        let event = |rrule: &str| {
+            let content = format!(
+                "BEGIN:VEVENT\nDTSTART:20220131T100000Z\nDTEND:20220131T110000Z\nRRULE:{}\nEND:VEVENT\n",
+                rrule
+            );
+            parse_calendar(&content, &chrono_tz::UTC).events.remove(0)
+        };
+        let dates = |event: IcsEvent| {
+            event
+                .occurrences(NaiveDate::from_ymd(2022, 4, 30))
+                .unwrap()
+                .iter()
+                .map(|occurrence| occurrence.date_naive().to_string())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            dates(event("FREQ=DAILY;INTERVAL=2;UNTIL=20220204")),
+            vec!["2022-01-31", "2022-02-02", "2022-02-04"]
+        );
+        assert_eq!(
+            dates(event("FREQ=MONTHLY;COUNT=3")),
+            vec!["2022-01-31", "2022-03-31"]
+        );
+    }
+
+    #[test]
+    fn parse_calendar_skips_unreadable_events_and_maps_windows_timezones() {
+        let content = "BEGIN:VEVENT\nUID:broken\nSUMMARY:Broken\nDTSTART;TZID=Mars/Olympus:20220912T100000\nEND:VEVENT\n\
+BEGIN:VEVENT\nUID:review\nSUMMARY:Review\nDTSTART;TZID=W. Europe Standard Time:20210228T100000\nRRULE:FREQ=YEARLY;INTERVAL=1\nEND:VEVENT\n";
+
+        let calendar = parse_calendar(content, &chrono_tz::UTC);
+
+        assert_eq!(
+            calendar.skipped,
+            vec![SkippedEvent {
+                uid: "broken".to_string(),
+                summary: "Broken".to_string(),
+                reason: "Unknown TZID 'Mars/Olympus'".to_string(),
+            }]
+        );
+        assert_eq!(calendar.events.len(), 1);
+        assert_eq!(calendar.events[0].timezone, chrono_tz::Europe::Berlin);
+        assert_eq!(
+            calendar.events[0]
+                .occurrences(NaiveDate::from_ymd(2023, 3, 1))
+                .unwrap(),
+            vec![
+                DateTime::parse_from_rfc3339("2021-02-28T10:00:00+01:00").unwrap(),
+                DateTime::parse_from_rfc3339("2022-02-28T10:00:00+01:00").unwrap(),
+                DateTime::parse_from_rfc3339("2023-02-28T10:00:00+01:00").unwrap(),
+            ]
+        );
+    }
+
+    fn dates_until(rrule: &str, dtstart: &str, until: NaiveDate) -> anyhow::Result<Vec<String>> {
+        let content = format!(
+            "BEGIN:VEVENT\nUID:rule\nDTSTART:{}\nRRULE:{}\nEND:VEVENT\n",
+            dtstart, rrule
+        );
+        let mut calendar = parse_calendar(&content, &chrono_tz::UTC);
+        if let Some(skipped) = calendar.skipped.pop() {
+            return Err(anyhow!(skipped.reason));
+        }
+        Ok(calendar
+            .events
+            .remove(0)
+            .occurrences(until)?
+            .iter()
+            .map(|occurrence| occurrence.date_naive().to_string())
+            .collect())
+    }
+
+    #[test]
+    fn occurrences_honour_by_day_in_daily_and_monthly_rules() {
+        assert_eq!(
+            dates_until(
+                "FREQ=DAILY;BYDAY=MO,TU,WE,TH,FR",
+                "20220915T090000Z",
+                NaiveDate::from_ymd(2022, 9, 20)
+            )
+            .unwrap(),
+            vec!["2022-09-15", "2022-09-16", "2022-09-19", "2022-09-20"]
+        );
+        assert_eq!(
+            dates_until(
+                "FREQ=MONTHLY;BYDAY=1MO;COUNT=3",
+                "20220905T090000Z",
+                NaiveDate::from_ymd(2022, 12, 31)
+            )
+            .unwrap(),
+            vec!["2022-09-05", "2022-10-03", "2022-11-07"]
+        );
+        assert_eq!(
+            dates_until(
+                "FREQ=MONTHLY;BYDAY=-1FR",
+                "20220930T090000Z",
+                NaiveDate::from_ymd(2022, 11, 30)
+            )
+            .unwrap(),
+            vec!["2022-09-30", "2022-10-28", "2022-11-25"]
+        );
+    }
+
+    #[test]
+    fn parse_calendar_skips_rules_with_unsupported_parts() {
+        let until = NaiveDate::from_ymd(2022, 12, 31);
+
+        for rrule in [
+            "FREQ=MONTHLY;BYMONTHDAY=15",
+            "FREQ=MONTHLY;BYDAY=MO,TU;BYSETPOS=-1",
+            "FREQ=YEARLY;BYDAY=1MO",
+            "FREQ=WEEKLY;BYDAY=2MO",
+            "FREQ=WEEKLY;BYDAY=XX",
+        ] {
+            assert!(
+                dates_until(rrule, "20220905T090000Z", until).is_err(),
+                "{}",
+                rrule
+            );
+        }
+    }
+
+    #[test]
+    fn occurrences_stop_at_periods_out_of_range_for_huge_intervals() {
+        let until = NaiveDate::from_ymd(9999, 12, 31);
+
+        for rrule in [
+            "FREQ=DAILY;INTERVAL=4000000000",
+            "FREQ=WEEKLY;INTERVAL=4000000000",
+            "FREQ=WEEKLY;INTERVAL=4000000000;BYDAY=MO",
+            "FREQ=MONTHLY;INTERVAL=4000000000",
+            "FREQ=MONTHLY;INTERVAL=4000000000;BYDAY=1MO",
+            "FREQ=YEARLY;INTERVAL=4000000000",
+        ] {
+            assert_eq!(
+                dates_until(rrule, "20220905T090000Z", until).unwrap(),
+                vec!["2022-09-05"],
+                "{}",
+                rrule
+            );
+        }
+        assert!(dates_until("FREQ=DAILY;INTERVAL=0", "20220905T090000Z", until).is_err());
+    }
+
+    #[test]
+    fn parse_duration_reads_signs_and_rejects_malformed_values() {
+        assert_eq!(parse_duration("PT1H30M").unwrap(), Duration::minutes(90));
+        assert_eq!(parse_duration("+P1D").unwrap(), Duration::days(1));
+        assert_eq!(parse_duration("-PT15M").unwrap(), -Duration::minutes(15));
+        assert!(parse_duration("PT15").is_err());
+        assert!(parse_duration("15M").is_err());
+        assert!(parse_duration("P99999999999999999W").is_err());
+    }
+
+    #[test]
+    fn unescape_handles_escaped_backslash_before_n() {
+        assert_eq!(unescape("a\\\\nb"), "a\\nb");
+        assert_eq!(unescape("line\\nnext\\, more\\;"), "line next, more;");
+    }
+}
@@ -0,0 +1,324 @@
+use super::{
+    draft_generator_trait::DraftGeneratorTrait,
+    ics_calendar::{parse_calendar, IcsEvent, SkippedEvent},
+    DRAFT_SOURCE_METADATA_KEY,
+};
+use crate::jira::Worklog;
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveTime, Offset, TimeZone};
+use chrono_tz::Tz;
+use regex::Regex;
+use std::{cell::RefCell, path::Path};
+
+#[derive(Debug, Clone)]
+pub struct EventRule {
+    pub title_pattern: Regex,
+    pub issue_key: String,
+}
+
+/// Turns meetings from an exported .ics calendar into draft worklogs. Combine with
+/// `GapFiller` to only keep drafts for the days the summary reports as short.
+pub struct IcsDraftGenerator {
+    calendar: String,
+    attendee_email: Option<String>,
+    rules: Vec<EventRule>,
+    fallback_issue_key: Option<String>,
+    working_hours: (NaiveTime, NaiveTime),
+    timezone: Tz,
+    warnings: RefCell<Vec<String>>,
+}
+
+impl DraftGeneratorTrait for IcsDraftGenerator {
+    fn generate(&self, start_date: NaiveDate, end_date: NaiveDate) -> anyhow::Result<Vec<Worklog>> {
+        let calendar = parse_calendar(&self.calendar, &self.timezone);
+        let events = calendar.events;
+        let mut warnings: Vec<String> = calendar
+            .skipped
+            .iter()
+            .map(|skipped| skipped.to_string())
+            .collect();
+        let overridden: Vec<(&str, DateTime<FixedOffset>)> = events
+            .iter()
+            .filter_map(|event| Some((event.uid.as_str(), event.recurrence_id?)))
+            .collect();
+
+        let mut drafts = vec![];
+        for event in events.iter().filter(|event| self.is_attended(event)) {
+            let Some(issue_key) = self.issue_key_of(&event.summary) else {
+                continue;
+            };
+            let occurrences = match event.recurrence_id {
+                Some(_) => vec![event.start],
+                None => match event.occurrences(end_date) {
+                    Ok(occurrences) => occurrences
+                        .into_iter()
+                        .filter(|occurrence| {
+                            !overridden.contains(&(event.uid.as_str(), *occurrence))
+                        })
+                        .collect(),
+                    Err(error) => {
+                        let skipped = SkippedEvent {
+                            uid: event.uid.clone(),
+                            summary: event.summary.clone(),
+                            reason: error.to_string(),
+                        };
+                        warnings.push(skipped.to_string());
+                        continue;
+                    }
+                },
+            };
+            for occurrence in occurrences {
+                if let Some((started, time_spent)) =
+                    self.clip_to_working_hours(occurrence, occurrence + (event.end - event.start))
+                {
+                    let date = started.with_timezone(&self.timezone).date_naive();
+                    if date >= start_date && date <= end_date {
+                        drafts.push(
+                            Worklog::new(started, issue_key, "", time_spent)
+                                .with_comment(&event.summary)
+                                .with_metadata(DRAFT_SOURCE_METADATA_KEY, "calendar"),
+                        );
+                    }
+                }
+            }
+        }
+        drafts.sort_by_key(|draft| draft.started);
+        self.warnings.replace(warnings);
+        Ok(drafts)
+    }
+
+    fn warnings(&self) -> Vec<String> {
+        self.warnings.borrow().clone()
+    }
+}
+
+impl IcsDraftGenerator {
+    pub fn new(calendar: &str) -> Self {
+        Self {
+            calendar: calendar.to_string(),
+            attendee_email: None,
+            rules: vec![],
+            fallback_issue_key: None,
+            working_hours: (NaiveTime::from_hms(9, 0, 0), NaiveTime::from_hms(17, 0, 0)),
+            timezone: chrono_tz::UTC,
+            warnings: RefCell::new(vec![]),
+        }
+    }
+
+    pub fn from_file(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        Ok(Self::new(&std::fs::read_to_string(path)?))
+    }
+
+    /// Events this attendee declined are skipped.
+    pub fn with_attendee(mut self, email: &str) -> Self {
+        self.attendee_email = Some(email.to_string());
+        self
+    }
+
+    /// Rules are checked in order; the first matching title wins.
+    pub fn with_rule(mut self, title_pattern: Regex, issue_key: &str) -> Self {
+        self.rules.push(EventRule {
+            title_pattern,
+            issue_key: issue_key.to_string(),
+        });
+        self
+    }
+
+    pub fn with_fallback_issue_key(mut self, issue_key: &str) -> Self {
+        self.fallback_issue_key = Some(issue_key.to_string());
+        self
+    }
+
+    pub fn with_working_hours(mut self, start: NaiveTime, end: NaiveTime) -> Self {
+        self.working_hours = (start, end);
+        self
+    }
+
+    /// Timezone of working hours and of floating event times, UTC by default.
+    pub fn with_timezone(mut self, timezone: Tz) -> Self {
+        self.timezone = timezone;
+        self
+    }
+
+    fn is_attended(&self, event: &IcsEvent) -> bool {
+        !event.cancelled
+            && !event.all_day
+            && !self
+                .attendee_email
+                .as_deref()
+                .is_some_and(|email| event.is_declined_by(email))
+    }
+
+    fn issue_key_of(&self, title: &str) -> Option<&str> {
+        self.rules
+            .iter()
+            .find(|rule| rule.title_pattern.is_match(title))
+            .map(|rule| rule.issue_key.as_str())
+            .or(self.fallback_issue_key.as_deref())
+    }
+
+    fn clip_to_working_hours(
+        &self,
+        start: DateTime<FixedOffset>,
+        end: DateTime<FixedOffset>,
+    ) -> Option<(DateTime<FixedOffset>, chrono::Duration)> {
+        let date = start.with_timezone(&self.timezone).date_naive();
+        let at = |time: NaiveTime| {
+            self.timezone
+                .from_local_datetime(&date.and_time(time))
+                .earliest()
+                .map(|date_time| date_time.with_timezone(&date_time.offset().fix()))
+        };
+        let clipped_start = start.max(at(self.working_hours.0)?);
+        let clipped_end = end.min(at(self.working_hours.1)?);
+        (clipped_end > clipped_start).then(|| (clipped_start, clipped_end - clipped_start))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    const CALENDAR: &str = "BEGIN:VCALENDAR
+BEGIN:VEVENT
+UID:standup
+SUMMARY:Daily standup
+DTSTART;TZID=Europe/Warsaw:20220912T083000
+DTEND;TZID=Europe/Warsaw:20220912T091500
+RRULE:FREQ=DAILY;COUNT=3
+END:VEVENT
+BEGIN:VEVENT
+UID:standup
+RECURRENCE-ID;TZID=Europe/Warsaw:20220913T083000
+SUMMARY:Daily standup
+DTSTART;TZID=Europe/Warsaw:20220913T100000
+DTEND;TZID=Europe/Warsaw:20220913T101500
+END:VEVENT
+BEGIN:VEVENT
+UID:sales
+SUMMARY:Sales call
+DTSTART:20220912T120000Z
+DTEND:20220912T130000Z
+ATTENDEE;PARTSTAT=DECLINED:mailto:jan@example.com
+END:VEVENT
+BEGIN:VEVENT
+UID:retro
+SUMMARY:Retro
+DTSTART:20220914T140000Z
+DTEND:20220914T150000Z
+END:VEVENT
+BEGIN:VEVENT
+UID:holiday
+SUMMARY:Holiday
+DTSTART;VALUE=DATE:20220914
+END:VEVENT
+END:VCALENDAR
+";
+
+    fn at(date_time: &str) -> DateTime<FixedOffset> {
+        DateTime::parse_from_rfc3339(date_time).unwrap()
+    }
+
+    #[test]
+    fn generate_maps_clips_and_skips_declined_events() {
+        let uut = IcsDraftGenerator::new(CALENDAR)
+            .with_attendee("jan@example.com")
+            .with_rule(Regex::new("(?i)standup").unwrap(), "ABC-1")
+            .with_fallback_issue_key("MEET-1")
+            .with_timezone(chrono_tz::Europe::Warsaw);
+
+        let drafts = uut
+            .generate(
+                NaiveDate::from_ymd(2022, 9, 12),
+                NaiveDate::from_ymd(2022, 9, 14),
+            )
+            .unwrap();
+
+        let draft = |started: &str, issue_key: &str, minutes: i64, title: &str| {
+            Worklog::new(at(started), issue_key, "", Duration::minutes(minutes))
+                .with_comment(title)
+                .with_metadata(DRAFT_SOURCE_METADATA_KEY, "calendar")
+        };
+        assert_eq!(
+            drafts,
+            vec![
+                draft("2022-09-12T09:00:00+02:00", "ABC-1", 15, "Daily standup"),
+                draft("2022-09-13T10:00:00+02:00", "ABC-1", 15, "Daily standup"),
+                draft("2022-09-14T09:00:00+02:00", "ABC-1", 15, "Daily standup"),
+                draft("2022-09-14T16:00:00+02:00", "MEET-1", 60, "Retro"),
+            ]
+        );
+    }
+
+    #[test]
+    fn generate_skips_events_without_matching_rule_or_fallback() {
+        let uut = IcsDraftGenerator::new(CALENDAR).with_rule(Regex::new("Retro").unwrap(), "ABC-2");
+
+        let drafts = uut
+            .generate(
+                NaiveDate::from_ymd(2022, 9, 12),
+                NaiveDate::from_ymd(2022, 9, 14),
+            )
+            .unwrap();
+
+        assert_eq!(drafts.len(), 1);
+        assert_eq!(drafts[0].issue_key, "ABC-2");
+    }
+
+    #[test]
+    fn generate_reports_skipped_events_and_keeps_the_rest() {
+        let calendar = "BEGIN:VCALENDAR
+BEGIN:VEVENT
+UID:review
+SUMMARY:Yearly review
+DTSTART;TZID=W. Europe Standard Time:20210913T100000
+DTEND;TZID=W. Europe Standard Time:20210913T110000
+RRULE:FREQ=YEARLY
+END:VEVENT
+BEGIN:VEVENT
+UID:sync
+SUMMARY:Hourly sync
+DTSTART:20220913T100000Z
+RRULE:FREQ=HOURLY
+END:VEVENT
+BEGIN:VEVENT
+UID:ping
+SUMMARY:Ping
+DTSTART:19000101T100000Z
+DTEND:19000101T101500Z
+RRULE:FREQ=DAILY
+END:VEVENT
+END:VCALENDAR
+";
+        let uut = IcsDraftGenerator::new(calendar)
+            .with_fallback_issue_key("MEET-1")
+            .with_timezone(chrono_tz::Europe::Warsaw);
+
+        let drafts = uut
+            .generate(
+                NaiveDate::from_ymd(2022, 9, 12),
+                NaiveDate::from_ymd(2022, 9, 14),
+            )
+            .unwrap();
+
+        assert_eq!(
+            drafts,
+            vec![Worklog::new(
+                at("2022-09-13T10:00:00+02:00"),
+                "MEET-1",
+                "",
+                Duration::hours(1)
+            )
+            .with_comment("Yearly review")
+            .with_metadata(DRAFT_SOURCE_METADATA_KEY, "calendar")]
+        );
+        assert_eq!(
+            uut.warnings(),
+            vec![
+                "Skipped event 'Hourly sync' (sync): Unsupported RRULE frequency Some(\"HOURLY\")".to_string(),
+                "Skipped event 'Ping' (ping): Recurrence needs more than 10000 periods to reach 2022-09-14"
+                    .to_string(),
+            ]
+        );
+    }
+}
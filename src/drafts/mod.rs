@@ -0,0 +1,32 @@
+mod commit;
+mod draft_generator_trait;
+mod estimation_heuristics;
+mod gap_filler;
+mod git_cli;
+mod git_draft_generator;
+mod git_history_trait;
+mod ics_calendar;
+mod ics_draft_generator;
+mod windows_timezones;
+mod worklog_template;
+
+pub use commit::Commit;
+pub use draft_generator_trait::DraftGeneratorTrait;
+pub use estimation_heuristics::EstimationHeuristics;
+pub use gap_filler::GapFiller;
+pub use git_cli::GitCli;
+pub use git_draft_generator::GitDraftGenerator;
+pub use git_history_trait::GitHistoryTrait;
+pub use ics_calendar::{
+    parse_calendar, ByDay, Frequency, IcsCalendar, IcsEvent, RecurrenceRule, SkippedEvent,
+};
+pub use ics_draft_generator::{EventRule, IcsDraftGenerator};
+pub use worklog_template::{TemplateRecurrence, WorklogTemplate};
+
+pub const DRAFT_SOURCE_METADATA_KEY: &str = "draft.source";
+
+#[cfg(test)]
+pub mod testing {
+    pub use super::draft_generator_trait::MockDraftGeneratorTrait;
+    pub use super::git_history_trait::MockGitHistoryTrait;
+}
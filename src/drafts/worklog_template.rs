@@ -0,0 +1,218 @@
+use super::DRAFT_SOURCE_METADATA_KEY;
+use crate::jira::Worklog;
+use crate::time_tracker::WorkSchedule;
+use chrono::{Datelike, Duration, NaiveDate, NaiveTime, Offset, TimeZone, Weekday};
+use chrono_tz::Tz;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum TemplateRecurrence {
+    Weekdays(Vec<Weekday>),
+    /// Every `interval` weeks on `weekday`, counting from the week of `anchor`.
+    EveryNWeeks {
+        weekday: Weekday,
+        interval: u32,
+        anchor: NaiveDate,
+    },
+    /// Last working day of the month.
+    MonthEnd,
+}
+
+impl TemplateRecurrence {
+    pub fn occurs_on(&self, date: &NaiveDate, schedule: &WorkSchedule) -> bool {
+        match self {
+            TemplateRecurrence::Weekdays(weekdays) => weekdays.contains(&date.weekday()),
+            TemplateRecurrence::EveryNWeeks {
+                weekday,
+                interval,
+                anchor,
+            } => {
+                let weeks = (week_start(date) - week_start(anchor)).num_weeks();
+                date.weekday() == *weekday
+                    && weeks >= 0
+                    && weeks % i64::from((*interval).max(1)) == 0
+            }
+            TemplateRecurrence::MonthEnd => {
+                schedule.is_working_day(date)
+                    && date
+                        .iter_days()
+                        .skip(1)
+                        .take_while(|next| next.month() == date.month())
+                        .all(|next| !schedule.is_working_day(&next))
+            }
+        }
+    }
+}
+
+fn week_start(date: &NaiveDate) -> NaiveDate {
+    *date - Duration::days(date.weekday().num_days_from_monday() as i64)
+}
+
+/// Worklog logged automatically on every day matching its recurrence, e.g. a daily standup.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct WorklogTemplate {
+    pub name: String,
+    pub issue_key: String,
+    pub time_spent: Duration,
+    pub start_time: NaiveTime,
+    pub comment: Option<String>,
+    pub recurrence: TemplateRecurrence,
+}
+
+impl WorklogTemplate {
+    pub fn new(
+        name: &str,
+        issue_key: &str,
+        time_spent: Duration,
+        recurrence: TemplateRecurrence,
+    ) -> Self {
+        Self {
+            name: name.to_string(),
+            issue_key: issue_key.to_string(),
+            time_spent,
+            start_time: NaiveTime::from_hms(9, 0, 0),
+            comment: None,
+            recurrence,
+        }
+    }
+
+    pub fn with_start_time(mut self, start_time: NaiveTime) -> Self {
+        self.start_time = start_time;
+        self
+    }
+
+    pub fn with_comment(mut self, comment: &str) -> Self {
+        self.comment = Some(comment.to_string());
+        self
+    }
+
+    /// Marker appended to the comment, used to recognize already logged occurrences.
+    pub fn tag(&self) -> String {
+        format!("[template:{}]", self.name)
+    }
+
+    pub fn is_logged_in(&self, worklogs: &[Worklog]) -> bool {
+        let tag = self.tag();
+        worklogs.iter().any(|worklog| {
+            worklog
+                .comment
+                .as_deref()
+                .is_some_and(|comment| comment.contains(&tag))
+        })
+    }
+
+    /// Worklog for `date`, or `None` when the template does not occur on that day
+    /// or the day is not a working one (weekend, holiday, absence).
+    pub fn worklog_for(
+        &self,
+        date: &NaiveDate,
+        schedule: &WorkSchedule,
+        timezone: &Tz,
+    ) -> Option<Worklog> {
+        if !schedule.is_working_day(date) || !self.recurrence.occurs_on(date, schedule) {
+            return None;
+        }
+        let started = timezone
+            .from_local_datetime(&date.and_time(self.start_time))
+            .earliest()?;
+        let comment = match &self.comment {
+            Some(comment) => format!("{} {}", comment, self.tag()),
+            None => format!("{} {}", self.name, self.tag()),
+        };
+        Some(
+            Worklog::new(
+                started.with_timezone(&started.offset().fix()),
+                &self.issue_key,
+                "",
+                self.time_spent,
+            )
+            .with_comment(&comment)
+            .with_metadata(DRAFT_SOURCE_METADATA_KEY, "template"),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_schedule() -> WorkSchedule {
+        WorkSchedule::new(Duration::hours(8))
+            .with_working_days(&[
+                Weekday::Mon,
+                Weekday::Tue,
+                Weekday::Wed,
+                Weekday::Thu,
+                Weekday::Fri,
+            ])
+            .with_holidays(&[NaiveDate::from_ymd(2022, 11, 1)])
+    }
+
+    fn occurrences(recurrence: TemplateRecurrence, start: NaiveDate, days: usize) -> Vec<String> {
+        let schedule = create_schedule();
+        start
+            .iter_days()
+            .take(days)
+            .filter(|date| schedule.is_working_day(date) && recurrence.occurs_on(date, &schedule))
+            .map(|date| date.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn occurs_on_supports_every_n_weeks_and_month_end() {
+        assert_eq!(
+            occurrences(
+                TemplateRecurrence::EveryNWeeks {
+                    weekday: Weekday::Mon,
+                    interval: 2,
+                    anchor: NaiveDate::from_ymd(2022, 10, 5),
+                },
+                NaiveDate::from_ymd(2022, 9, 26),
+                36
+            ),
+            vec!["2022-10-03", "2022-10-17", "2022-10-31"]
+        );
+        assert_eq!(
+            occurrences(
+                TemplateRecurrence::MonthEnd,
+                NaiveDate::from_ymd(2022, 9, 1),
+                61
+            ),
+            vec!["2022-09-30", "2022-10-31"]
+        );
+    }
+
+    #[test]
+    fn worklog_for_skips_holidays_and_tags_comment() {
+        let uut = WorklogTemplate::new(
+            "standup",
+            "ABC-1",
+            Duration::minutes(15),
+            TemplateRecurrence::Weekdays(vec![Weekday::Tue, Weekday::Wed]),
+        )
+        .with_comment("Daily standup")
+        .with_start_time(NaiveTime::from_hms(9, 30, 0));
+        let schedule = create_schedule();
+
+        assert_eq!(
+            uut.worklog_for(
+                &NaiveDate::from_ymd(2022, 11, 1),
+                &schedule,
+                &chrono_tz::UTC
+            ),
+            None
+        );
+        let worklog = uut
+            .worklog_for(
+                &NaiveDate::from_ymd(2022, 11, 2),
+                &schedule,
+                &chrono_tz::Europe::Warsaw,
+            )
+            .unwrap();
+        assert_eq!(worklog.started.to_rfc3339(), "2022-11-02T09:30:00+01:00");
+        assert_eq!(
+            worklog.comment.as_deref(),
+            Some("Daily standup [template:standup]")
+        );
+        assert!(uut.is_logged_in(&[worklog]));
+    }
+}
@@ -0,0 +1,5 @@
+mod user_worklogs_fetcher;
+
+/// GitLab API client authenticating with a personal access token.
+pub type Client = crate::jira::BearerClient;
+pub use user_worklogs_fetcher::UserWorklogsFetcher;
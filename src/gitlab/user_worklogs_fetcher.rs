@@ -0,0 +1,470 @@
+use crate::jira::{
+    checked_seconds, ClientTrait, IssueInfo, UserWorklogs, UserWorklogsFetcherTrait, Worklog,
+    WorklogAuthor,
+};
+use anyhow::anyhow;
+use chrono::{DateTime, Duration, NaiveDate};
+use chrono_tz::Tz;
+use reqwest::IntoUrl;
+
+const PAGE_SIZE: usize = 100;
+const TIMELOGS_QUERY: &str = r###"query($username: String!, $startDate: Time, $endDate: Time, $first: Int, $after: String) {
+  timelogs(username: $username, startDate: $startDate, endDate: $endDate, first: $first, after: $after) {
+    nodes {
+      id
+      spentAt
+      timeSpent
+      summary
+      note { body }
+      user { username name }
+      issue { iid title webUrl reference(full: true) }
+      mergeRequest { iid title webUrl reference(full: true) }
+    }
+    pageInfo { hasNextPage endCursor }
+  }
+}"###;
+
+/// Reads time logged with `/spend` through GitLab's GraphQL `timelogs` query.
+pub struct UserWorklogsFetcher<UrlType, ClientType>
+where
+    UrlType: IntoUrl,
+    ClientType: ClientTrait,
+{
+    gitlab_root_url: UrlType,
+    gitlab_client: ClientType,
+    timezone: Option<Tz>,
+}
+
+impl<UrlType, ClientType> UserWorklogsFetcherTrait for UserWorklogsFetcher<UrlType, ClientType>
+where
+    UrlType: IntoUrl,
+    ClientType: ClientTrait,
+{
+    fn fetch(
+        &self,
+        user_name: &str,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> anyhow::Result<UserWorklogs> {
+        let mut worklogs = vec![];
+        let mut cursor: Option<String> = None;
+
+        loop {
+            let response = self
+                .fetch_timelogs(user_name, &start_date, &end_date, cursor.as_deref())?
+                .error_for_status()?
+                .json::<serde_json::Value>()?;
+            if let Some(errors) = response["errors"].as_array() {
+                return Err(anyhow!(
+                    "GitLab GraphQL error: {}",
+                    errors
+                        .iter()
+                        .filter_map(|error| error["message"].as_str())
+                        .collect::<Vec<_>>()
+                        .join("; ")
+                ));
+            }
+
+            let timelogs = &response["data"]["timelogs"];
+            for node in timelogs["nodes"]
+                .as_array()
+                .ok_or(anyhow!("Can't parse 'timelogs' nodes as array"))?
+            {
+                let worklog = self.create_worklog(node)?;
+                let date = match &self.timezone {
+                    Some(timezone) => worklog.date_in(timezone),
+                    None => worklog.date(),
+                };
+                if date >= start_date && date <= end_date {
+                    worklogs.push(worklog);
+                }
+            }
+
+            cursor = match timelogs["pageInfo"]["hasNextPage"].as_bool() {
+                Some(true) => timelogs["pageInfo"]["endCursor"]
+                    .as_str()
+                    .map(|cursor| cursor.to_string()),
+                _ => None,
+            };
+            if cursor.is_none() {
+                return Ok(UserWorklogs::new(user_name, start_date, end_date, worklogs));
+            }
+        }
+    }
+
+    fn timezone(&self) -> Option<Tz> {
+        self.timezone
+    }
+}
+
+impl<UrlType, ClientType> UserWorklogsFetcher<UrlType, ClientType>
+where
+    UrlType: IntoUrl,
+    ClientType: ClientTrait,
+{
+    pub fn new(gitlab_root_url: UrlType, gitlab_client: ClientType) -> Self {
+        Self {
+            gitlab_root_url,
+            gitlab_client,
+            timezone: None,
+        }
+    }
+
+    /// Assigns timelogs to days in `timezone` instead of the offset of their `spentAt`.
+    pub fn with_timezone(mut self, timezone: Tz) -> Self {
+        self.timezone = Some(timezone);
+        self
+    }
+
+    fn create_gitlab_graphql_endpoint(&self) -> String {
+        "/api/graphql".to_string()
+    }
+
+    fn fetch_timelogs(
+        &self,
+        user_name: &str,
+        start_date: &NaiveDate,
+        end_date: &NaiveDate,
+        cursor: Option<&str>,
+    ) -> reqwest::Result<reqwest::blocking::Response> {
+        // GitLab compares the dates with UTC midnight and `endDate` excludes its own day, so
+        // ask for a day more on each side and filter by the local day afterwards.
+        let body = serde_json::json!({
+            "query": TIMELOGS_QUERY,
+            "variables": {
+                "username": user_name,
+                "startDate": (*start_date - Duration::days(1)).format("%Y-%m-%d").to_string(),
+                "endDate": (*end_date + Duration::days(2)).format("%Y-%m-%d").to_string(),
+                "first": PAGE_SIZE,
+                "after": cursor,
+            }
+        });
+        self.gitlab_client.request_post(
+            format!(
+                "{}{}",
+                self.gitlab_root_url.as_str(),
+                self.create_gitlab_graphql_endpoint()
+            ),
+            &body,
+        )
+    }
+
+    fn create_worklog(&self, node: &serde_json::Value) -> anyhow::Result<Worklog> {
+        let target = if node["issue"].is_object() {
+            &node["issue"]
+        } else {
+            &node["mergeRequest"]
+        };
+        let reference = target["reference"]
+            .as_str()
+            .ok_or(anyhow!("Can't parse GitLab timelog issue reference"))?;
+        let spent_at = DateTime::parse_from_rfc3339(
+            node["spentAt"]
+                .as_str()
+                .ok_or(anyhow!("Can't parse GitLab timelog 'spentAt'"))?,
+        )?;
+        let time_spent = node["timeSpent"]
+            .as_i64()
+            .and_then(checked_seconds)
+            .ok_or(anyhow!("Can't parse GitLab timelog 'timeSpent'"))?;
+        let comment = node["summary"]
+            .as_str()
+            .or(node["note"]["body"].as_str())
+            .filter(|comment| !comment.trim().is_empty());
+        let title = target["title"]
+            .as_str()
+            .filter(|title| !title.trim().is_empty())
+            .or(comment)
+            .unwrap_or_default();
+
+        let mut worklog = Worklog::new(spent_at, reference, title, time_spent)
+            .with_author(WorklogAuthor {
+                name: node["user"]["username"]
+                    .as_str()
+                    .map(|name| name.to_string()),
+                display_name: node["user"]["name"].as_str().map(|name| name.to_string()),
+                ..Default::default()
+            })
+            .with_issue(IssueInfo {
+                project_key: Some(project_path(reference).to_string()),
+                ..Default::default()
+            });
+        worklog.id = node["id"].as_str().map(|id| id.to_string());
+        worklog.comment = comment.map(|comment| comment.to_string());
+        if let Some(web_url) = target["webUrl"].as_str() {
+            worklog = worklog.with_metadata("gitlab.webUrl", web_url);
+        }
+        Ok(worklog)
+    }
+}
+
+/// Project part of a full reference, `group/project` of `group/project#12` or `group/project!7`.
+fn project_path(reference: &str) -> &str {
+    reference
+        .rfind(['#', '!'])
+        .map_or(reference, |index| &reference[..index])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gitlab::Client;
+
+    const USER_NAME: &str = "jdoe";
+
+    fn create_uut() -> UserWorklogsFetcher<String, Client> {
+        UserWorklogsFetcher::new(mockito::server_url(), Client::new("dummy_token"))
+    }
+
+    fn create_graphql_endpoint_mock(after: Option<&str>, body: &str) -> mockito::Mock {
+        mockito::mock("POST", "/api/graphql")
+            .match_header("authorization", "Bearer dummy_token")
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                "variables": {
+                    "username": USER_NAME,
+                    "startDate": "2022-09-09",
+                    "endDate": "2022-09-19",
+                    "after": after,
+                }
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(body)
+            .create()
+    }
+
+    #[test]
+    fn fetch_maps_timelogs_of_issues_and_merge_requests_across_pages() {
+        let uut = create_uut();
+        let start_date = NaiveDate::from_ymd(2022, 9, 10);
+        let end_date = NaiveDate::from_ymd(2022, 9, 17);
+
+        let _first_page_mock = create_graphql_endpoint_mock(
+            None,
+            r###"{"data": {"timelogs": {
+                "nodes": [{
+                    "id": "gid://gitlab/Timelog/1",
+                    "spentAt": "2022-09-15T10:00:00Z",
+                    "timeSpent": 5400,
+                    "summary": "Review",
+                    "user": { "username": "jdoe", "name": "John Doe" },
+                    "issue": { "iid": "12", "title": "Fix login", "webUrl": "https://gitlab.local/g/p/-/issues/12", "reference": "g/p#12" },
+                    "mergeRequest": null
+                }],
+                "pageInfo": { "hasNextPage": true, "endCursor": "cursor-1" }
+            }}}"###,
+        );
+        let _second_page_mock = create_graphql_endpoint_mock(
+            Some("cursor-1"),
+            r###"{"data": {"timelogs": {
+                "nodes": [{
+                    "id": "gid://gitlab/Timelog/2",
+                    "spentAt": "2022-09-16T08:00:00+02:00",
+                    "timeSpent": 1800,
+                    "summary": null,
+                    "note": null,
+                    "user": { "username": "jdoe", "name": "John Doe" },
+                    "issue": null,
+                    "mergeRequest": { "iid": "7", "title": "Add cache", "reference": "g/p!7" }
+                }],
+                "pageInfo": { "hasNextPage": false, "endCursor": null }
+            }}}"###,
+        );
+
+        let result = uut.fetch(USER_NAME, start_date, end_date).unwrap();
+
+        let author = WorklogAuthor {
+            name: Some("jdoe".to_string()),
+            display_name: Some("John Doe".to_string()),
+            ..Default::default()
+        };
+        let project = IssueInfo {
+            project_key: Some("g/p".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            result.worklogs,
+            vec![
+                Worklog::new(
+                    DateTime::parse_from_rfc3339("2022-09-15T10:00:00Z").unwrap(),
+                    "g/p#12",
+                    "Fix login",
+                    Duration::minutes(90),
+                )
+                .with_id("gid://gitlab/Timelog/1")
+                .with_comment("Review")
+                .with_author(author.clone())
+                .with_issue(project.clone())
+                .with_metadata("gitlab.webUrl", "https://gitlab.local/g/p/-/issues/12"),
+                Worklog::new(
+                    DateTime::parse_from_rfc3339("2022-09-16T08:00:00+02:00").unwrap(),
+                    "g/p!7",
+                    "Add cache",
+                    Duration::minutes(30),
+                )
+                .with_id("gid://gitlab/Timelog/2")
+                .with_author(author)
+                .with_issue(project),
+            ]
+        );
+    }
+
+    #[test]
+    fn fetch_returns_error_if_graphql_reports_errors() {
+        let uut = create_uut();
+
+        let _graphql_mock = create_graphql_endpoint_mock(
+            None,
+            r###"{"errors": [{"message": "Provide at least one argument"}]}"###,
+        );
+
+        let result = uut.fetch(
+            USER_NAME,
+            NaiveDate::from_ymd(2022, 9, 10),
+            NaiveDate::from_ymd(2022, 9, 17),
+        );
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("Provide at least one argument"));
+    }
+
+    #[test]
+    fn fetch_filters_by_day_in_configured_timezone() {
+        let body = r###"{"data": {"timelogs": {
+            "nodes": [
+                {
+                    "spentAt": "2022-09-09T22:30:00Z",
+                    "timeSpent": 600,
+                    "issue": { "title": "Early", "reference": "g/p#1" }
+                },
+                {
+                    "spentAt": "2022-09-17T22:30:00Z",
+                    "timeSpent": 600,
+                    "issue": { "title": "Late", "reference": "g/p#2" }
+                }
+            ],
+            "pageInfo": { "hasNextPage": false, "endCursor": null }
+        }}}"###;
+        let start_date = NaiveDate::from_ymd(2022, 9, 10);
+        let end_date = NaiveDate::from_ymd(2022, 9, 17);
+        let issue_keys = |user_worklogs: UserWorklogs| {
+            user_worklogs
+                .worklogs
+                .into_iter()
+                .map(|worklog| worklog.issue_key)
+                .collect::<Vec<_>>()
+        };
+
+        let _graphql_mock = create_graphql_endpoint_mock(None, body).expect(2);
+
+        let uut = create_uut().with_timezone(chrono_tz::Europe::Warsaw);
+        assert_eq!(uut.timezone(), Some(chrono_tz::Europe::Warsaw));
+        assert_eq!(
+            issue_keys(uut.fetch(USER_NAME, start_date, end_date).unwrap()),
+            vec!["g/p#1"]
+        );
+        assert_eq!(
+            issue_keys(create_uut().fetch(USER_NAME, start_date, end_date).unwrap()),
+            vec!["g/p#2"]
+        );
+    }
+
+    #[test]
+    fn fetch_uses_project_path_of_reference_as_project_key() {
+        let uut = create_uut();
+
+        let _graphql_mock = create_graphql_endpoint_mock(
+            None,
+            r###"{"data": {"timelogs": {
+                "nodes": [
+                    {
+                        "spentAt": "2022-09-15T10:00:00Z",
+                        "timeSpent": 600,
+                        "issue": { "title": "Fix login", "reference": "group/my-project#12" }
+                    },
+                    {
+                        "spentAt": "2022-09-15T11:00:00Z",
+                        "timeSpent": 600,
+                        "mergeRequest": { "title": "Add cache", "reference": "group/sub/my-project!7" }
+                    }
+                ],
+                "pageInfo": { "hasNextPage": false, "endCursor": null }
+            }}}"###,
+        );
+
+        let result = uut
+            .fetch(
+                USER_NAME,
+                NaiveDate::from_ymd(2022, 9, 10),
+                NaiveDate::from_ymd(2022, 9, 17),
+            )
+            .unwrap();
+
+        assert_eq!(
+            result
+                .worklogs
+                .iter()
+                .map(|worklog| worklog.project_key())
+                .collect::<Vec<_>>(),
+            vec!["group/my-project", "group/sub/my-project"]
+        );
+    }
+
+    #[test]
+    fn fetch_falls_back_to_timelog_note_if_title_is_empty() {
+        let uut = create_uut();
+
+        let _graphql_mock = create_graphql_endpoint_mock(
+            None,
+            r###"{"data": {"timelogs": {
+                "nodes": [{
+                    "spentAt": "2022-09-15T10:00:00Z",
+                    "timeSpent": 600,
+                    "summary": null,
+                    "note": { "body": "Pairing on login" },
+                    "issue": { "title": "", "reference": "g/p#12" }
+                }],
+                "pageInfo": { "hasNextPage": false, "endCursor": null }
+            }}}"###,
+        );
+
+        let result = uut
+            .fetch(
+                USER_NAME,
+                NaiveDate::from_ymd(2022, 9, 10),
+                NaiveDate::from_ymd(2022, 9, 17),
+            )
+            .unwrap();
+
+        assert_eq!(result.worklogs[0].issue_summary, "Pairing on login");
+        assert_eq!(
+            result.worklogs[0].comment.as_deref(),
+            Some("Pairing on login")
+        );
+    }
+
+    #[test]
+    fn fetch_returns_error_if_time_spent_is_out_of_range() {
+        let uut = create_uut();
+
+        let _graphql_mock = create_graphql_endpoint_mock(
+            None,
+            r###"{"data": {"timelogs": {
+                "nodes": [{
+                    "spentAt": "2022-09-15T10:00:00Z",
+                    "timeSpent": 9223372036854775807,
+                    "issue": { "title": "Fix login", "reference": "g/p#12" }
+                }],
+                "pageInfo": { "hasNextPage": false, "endCursor": null }
+            }}}"###,
+        );
+
+        let result = uut.fetch(
+            USER_NAME,
+            NaiveDate::from_ymd(2022, 9, 10),
+            NaiveDate::from_ymd(2022, 9, 17),
+        );
+        assert!(result.is_err());
+    }
+}
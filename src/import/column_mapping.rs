@@ -0,0 +1,195 @@
+use crate::jira::{checked_fractional_seconds, checked_seconds, TimeTrackingConfig};
+use anyhow::anyhow;
+use chrono::Duration;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum DurationFormat {
+    /// `H:MM:SS` or `H:MM`; minutes and seconds must be below 60.
+    Clock,
+    /// Hours as unsigned decimal number with a `.` or `,` fraction, e.g. `1.5`.
+    DecimalHours,
+    /// Jira notation, e.g. `1d 2h 30m`, with days and weeks as configured.
+    Jira(TimeTrackingConfig),
+}
+
+impl DurationFormat {
+    pub fn parse(&self, text: &str) -> anyhow::Result<Duration> {
+        let text = text.trim();
+        match self {
+            DurationFormat::Clock => {
+                let invalid = || anyhow!("Invalid duration '{}'", text);
+                let parts = text
+                    .split(':')
+                    .map(|part| match part.chars().all(|c| c.is_ascii_digit()) {
+                        true => part.parse::<i64>().ok(),
+                        false => None,
+                    })
+                    .collect::<Option<Vec<_>>>()
+                    .ok_or_else(invalid)?;
+                if !(2..=3).contains(&parts.len()) || parts[1..].iter().any(|part| *part > 59) {
+                    return Err(invalid());
+                }
+                let seconds = parts
+                    .iter()
+                    .zip([3600, 60, 1])
+                    .try_fold(0i64, |total, (part, unit_seconds)| {
+                        part.checked_mul(unit_seconds)?.checked_add(total)
+                    })
+                    .ok_or_else(invalid)?;
+                checked_seconds(seconds).ok_or_else(invalid)
+            }
+            DurationFormat::DecimalHours => {
+                let invalid = || anyhow!("Invalid duration '{}'", text);
+                let is_digits =
+                    |part: &str| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit());
+                let (whole, fraction) = text.split_once(['.', ',']).unwrap_or((text, "0"));
+                if !is_digits(whole) || !is_digits(fraction) {
+                    return Err(invalid());
+                }
+                let hours = format!("{}.{}", whole, fraction)
+                    .parse::<f64>()
+                    .map_err(|_| invalid())?;
+                checked_fractional_seconds(hours * 3600.0).ok_or_else(invalid)
+            }
+            DurationFormat::Jira(config) => config.parse(text),
+        }
+    }
+}
+
+/// Which CSV columns hold worklog data and how to parse them.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ColumnMapping {
+    pub description: String,
+    pub start_date: String,
+    pub start_time: Option<String>,
+    pub duration: String,
+    /// Additional columns searched for issue keys after the description.
+    pub key_columns: Vec<String>,
+    pub date_format: String,
+    pub time_format: String,
+    pub duration_format: DurationFormat,
+}
+
+impl ColumnMapping {
+    pub fn new(description: &str, start_date: &str, duration: &str) -> Self {
+        Self {
+            description: description.to_string(),
+            start_date: start_date.to_string(),
+            start_time: None,
+            duration: duration.to_string(),
+            key_columns: vec![],
+            date_format: "%Y-%m-%d".to_string(),
+            time_format: "%H:%M:%S".to_string(),
+            duration_format: DurationFormat::Clock,
+        }
+    }
+
+    /// Toggl Track "Detailed report" CSV export.
+    pub fn toggl() -> Self {
+        Self::new("Description", "Start date", "Duration")
+            .with_start_time("Start time")
+            .with_key_column("Task")
+            .with_key_column("Project")
+    }
+
+    /// Clockify "Detailed report" CSV export with US date and 12-hour time formats.
+    pub fn clockify() -> Self {
+        Self::new("Description", "Start Date", "Duration (h)")
+            .with_start_time("Start Time")
+            .with_key_column("Task")
+            .with_key_column("Project")
+            .with_date_format("%m/%d/%Y")
+            .with_time_format("%I:%M:%S %p")
+    }
+
+    pub fn with_start_time(mut self, column: &str) -> Self {
+        self.start_time = Some(column.to_string());
+        self
+    }
+
+    pub fn with_key_column(mut self, column: &str) -> Self {
+        self.key_columns.push(column.to_string());
+        self
+    }
+
+    pub fn with_date_format(mut self, format: &str) -> Self {
+        self.date_format = format.to_string();
+        self
+    }
+
+    pub fn with_time_format(mut self, format: &str) -> Self {
+        self.time_format = format.to_string();
+        self
+    }
+
+    pub fn with_duration_format(mut self, duration_format: DurationFormat) -> Self {
+        self.duration_format = duration_format;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn duration_format_parses_clock_decimal_hours_and_jira() {
+        assert_eq!(
+            DurationFormat::Clock.parse("1:30:15").unwrap(),
+            Duration::seconds(5415)
+        );
+        assert_eq!(
+            DurationFormat::Clock.parse("0:45").unwrap(),
+            Duration::minutes(45)
+        );
+        assert_eq!(
+            DurationFormat::DecimalHours.parse("1,25").unwrap(),
+            Duration::minutes(75)
+        );
+        assert_eq!(
+            DurationFormat::DecimalHours.parse("2").unwrap(),
+            Duration::hours(2)
+        );
+        assert!(DurationFormat::DecimalHours.parse("-1.5").is_err());
+        assert!(DurationFormat::DecimalHours.parse("+1").is_err());
+        assert!(DurationFormat::DecimalHours.parse("1e2").is_err());
+        assert!(DurationFormat::DecimalHours.parse("1.").is_err());
+        assert!(DurationFormat::DecimalHours.parse("1.2.3").is_err());
+        assert!(DurationFormat::Clock.parse("1h").is_err());
+        assert!(DurationFormat::Clock.parse("1").is_err());
+        assert_eq!(
+            DurationFormat::Jira(TimeTrackingConfig::default())
+                .parse("1d 30m")
+                .unwrap(),
+            Duration::minutes(510)
+        );
+    }
+
+    #[test]
+    fn duration_format_rejects_non_finite_and_out_of_range_values() {
+        assert!(DurationFormat::DecimalHours.parse("inf").is_err());
+        assert!(DurationFormat::DecimalHours.parse("NaN").is_err());
+        assert!(DurationFormat::DecimalHours.parse("1e30").is_err());
+        assert!(DurationFormat::DecimalHours.parse("-NaN").is_err());
+        assert!(DurationFormat::DecimalHours.parse(&"9".repeat(30)).is_err());
+        assert!(DurationFormat::Clock
+            .parse("9223372036854775807:00")
+            .is_err());
+        assert!(DurationFormat::Clock.parse("3000000000000:00").is_err());
+    }
+
+    #[test]
+    fn duration_format_rejects_negative_and_overflowing_clock_components() {
+        assert!(DurationFormat::Clock.parse("01:75:00").is_err());
+        assert!(DurationFormat::Clock.parse("01:00:60").is_err());
+        assert!(DurationFormat::Clock.parse("1:60").is_err());
+        assert!(DurationFormat::Clock.parse("-1:30").is_err());
+        assert!(DurationFormat::Clock.parse("1:-30").is_err());
+        assert!(DurationFormat::Clock.parse("+1:30").is_err());
+        assert!(DurationFormat::Clock.parse("1::30").is_err());
+        assert_eq!(
+            DurationFormat::Clock.parse("25:59:59").unwrap(),
+            Duration::seconds(25 * 3600 + 59 * 60 + 59)
+        );
+    }
+}
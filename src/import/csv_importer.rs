@@ -0,0 +1,279 @@
+use super::{
+    column_mapping::{ColumnMapping, DurationFormat},
+    import_preview::{ImportPreview, UnmatchedRow},
+};
+use crate::jira::{Worklog, DEFAULT_ISSUE_KEY_PATTERN};
+use anyhow::anyhow;
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveTime, Offset, TimeZone};
+use chrono_tz::Tz;
+use regex::Regex;
+use std::io::Read;
+
+/// Converts CSV time tracking exports into worklogs, matching rows to Jira issues by key.
+pub struct CsvImporter {
+    mapping: ColumnMapping,
+    issue_key_regex: Regex,
+    timezone: Tz,
+}
+
+struct ColumnIndexes {
+    description: usize,
+    start_date: usize,
+    start_time: Option<usize>,
+    duration: usize,
+    key_columns: Vec<usize>,
+}
+
+impl CsvImporter {
+    pub fn new(mapping: ColumnMapping) -> Self {
+        Self {
+            mapping,
+            issue_key_regex: Regex::new(DEFAULT_ISSUE_KEY_PATTERN).unwrap(),
+            timezone: chrono_tz::UTC,
+        }
+    }
+
+    pub fn with_issue_key_regex(mut self, issue_key_regex: Regex) -> Self {
+        self.issue_key_regex = issue_key_regex;
+        self
+    }
+
+    /// Timezone of the local times in the export, UTC by default.
+    pub fn with_timezone(mut self, timezone: Tz) -> Self {
+        self.timezone = timezone;
+        self
+    }
+
+    pub fn import<Reader: Read>(&self, reader: Reader) -> anyhow::Result<ImportPreview> {
+        let mut csv_reader = csv::ReaderBuilder::new().flexible(true).from_reader(reader);
+        let indexes = self.column_indexes(csv_reader.headers()?)?;
+
+        let mut preview = ImportPreview::default();
+        if let DurationFormat::Jira(time_tracking) = self.mapping.duration_format {
+            preview = preview.with_time_tracking(time_tracking);
+        }
+        for record in csv_reader.records() {
+            let record = record?;
+            let line = record.position().map_or(0, |position| position.line());
+            let field = |index: usize| record.get(index).unwrap_or_default().trim();
+            let description = field(indexes.description).to_string();
+
+            let issue_key = std::iter::once(indexes.description)
+                .chain(indexes.key_columns.iter().copied())
+                .find_map(|index| self.issue_key_regex.find(field(index)));
+            let worklog = match issue_key {
+                Some(issue_key) => self
+                    .parse_started(field(indexes.start_date), indexes.start_time.map(field))
+                    .and_then(|started| {
+                        Ok(Worklog::new(
+                            started,
+                            issue_key.as_str(),
+                            "",
+                            self.mapping
+                                .duration_format
+                                .parse(field(indexes.duration))?,
+                        ))
+                    }),
+                None => Err(anyhow!("no issue key")),
+            };
+
+            match worklog {
+                Ok(worklog) if description.is_empty() => preview.worklogs.push(worklog),
+                Ok(worklog) => preview.worklogs.push(worklog.with_comment(&description)),
+                Err(error) => preview.unmatched.push(UnmatchedRow {
+                    line,
+                    description,
+                    reason: error.to_string(),
+                }),
+            }
+        }
+        Ok(preview)
+    }
+
+    fn column_indexes(&self, headers: &csv::StringRecord) -> anyhow::Result<ColumnIndexes> {
+        let index_of = |column: &str| {
+            headers
+                .iter()
+                .position(|header| header.trim() == column)
+                .ok_or(anyhow!("Missing column '{}'", column))
+        };
+        Ok(ColumnIndexes {
+            description: index_of(&self.mapping.description)?,
+            start_date: index_of(&self.mapping.start_date)?,
+            start_time: self
+                .mapping
+                .start_time
+                .as_deref()
+                .map(index_of)
+                .transpose()?,
+            duration: index_of(&self.mapping.duration)?,
+            key_columns: self
+                .mapping
+                .key_columns
+                .iter()
+                .filter_map(|column| index_of(column).ok())
+                .collect(),
+        })
+    }
+
+    fn parse_started(
+        &self,
+        date: &str,
+        time: Option<&str>,
+    ) -> anyhow::Result<DateTime<FixedOffset>> {
+        let date = NaiveDate::parse_from_str(date, &self.mapping.date_format)
+            .map_err(|_| anyhow!("invalid date '{}'", date))?;
+        let time = match time {
+            Some(time) => NaiveTime::parse_from_str(time, &self.mapping.time_format)
+                .map_err(|_| anyhow!("invalid time '{}'", time))?,
+            None => NaiveTime::from_hms(0, 0, 0),
+        };
+        let started = self
+            .timezone
+            .from_local_datetime(&date.and_time(time))
+            .earliest()
+            .ok_or(anyhow!("nonexistent local time"))?;
+        Ok(started.with_timezone(&started.offset().fix()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jira::TimeTrackingConfig;
+    use chrono::Duration;
+
+    #[test]
+    fn import_parses_toggl_export_and_reports_unmatched_rows() {
+        let export = "\
+User,Email,Client,Project,Task,Description,Billable,Start date,Start time,End date,End time,Duration,Tags
+Jan,jan@example.com,ACME,Web,,ABC-12 fix login,Yes,2022-09-15,09:00:00,2022-09-15,10:30:00,01:30:00,
+Jan,jan@example.com,ACME,Web,XYZ-7,Review,Yes,2022-09-15,11:00:00,2022-09-15,11:20:00,00:20:00,
+Jan,jan@example.com,ACME,Web,,Lunch,No,2022-09-15,12:00:00,2022-09-15,12:30:00,00:30:00,
+Jan,jan@example.com,ACME,Web,,ABC-13 docs,No,15.09.2022,13:00:00,2022-09-15,13:30:00,00:30:00,
+";
+        let uut = CsvImporter::new(ColumnMapping::toggl()).with_timezone(chrono_tz::Europe::Warsaw);
+
+        let preview = uut.import(export.as_bytes()).unwrap();
+
+        assert_eq!(
+            preview.worklogs,
+            vec![
+                Worklog::new(
+                    DateTime::parse_from_rfc3339("2022-09-15T09:00:00+02:00").unwrap(),
+                    "ABC-12",
+                    "",
+                    Duration::minutes(90)
+                )
+                .with_comment("ABC-12 fix login"),
+                Worklog::new(
+                    DateTime::parse_from_rfc3339("2022-09-15T11:00:00+02:00").unwrap(),
+                    "XYZ-7",
+                    "",
+                    Duration::minutes(20)
+                )
+                .with_comment("Review"),
+            ]
+        );
+        assert_eq!(
+            preview.unmatched,
+            vec![
+                UnmatchedRow {
+                    line: 4,
+                    description: "Lunch".to_string(),
+                    reason: "no issue key".to_string(),
+                },
+                UnmatchedRow {
+                    line: 5,
+                    description: "ABC-13 docs".to_string(),
+                    reason: "invalid date '15.09.2022'".to_string(),
+                },
+            ]
+        );
+        assert_eq!(
+            preview.to_string(),
+            [
+                "Worklogs to import (2):",
+                "  2022-09-15 09:00  ABC-12       1h 30m  ABC-12 fix login",
+                "  2022-09-15 11:00  XYZ-7           20m  Review",
+                "Unmatched rows (2):",
+                "  line 4: Lunch (no issue key)",
+                "  line 5: ABC-13 docs (invalid date '15.09.2022')",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn import_reports_invalid_clock_durations_as_unmatched() {
+        let export =
+            "when,what,spent\n2022-09-16,ABC-3 planning,01:75:00\n2022-09-16,ABC-4 review,-0:30\n";
+        let preview = CsvImporter::new(ColumnMapping::new("what", "when", "spent"))
+            .import(export.as_bytes())
+            .unwrap();
+
+        assert!(preview.worklogs.is_empty());
+        assert_eq!(
+            preview
+                .unmatched
+                .iter()
+                .map(|row| row.reason.as_str())
+                .collect::<Vec<_>>(),
+            vec!["Invalid duration '01:75:00'", "Invalid duration '-0:30'"]
+        );
+    }
+
+    #[test]
+    fn import_parses_clockify_and_generic_exports() {
+        let clockify_export = "\
+Project,Client,Description,Task,User,Start Date,Start Time,End Date,End Time,Duration (h),Duration (decimal)
+Web,ACME,Standup,ABC-1,Jan,09/15/2022,09:15:00 AM,09/15/2022,09:30:00 AM,00:15:00,0.25
+";
+        let clockify_preview = CsvImporter::new(ColumnMapping::clockify())
+            .import(clockify_export.as_bytes())
+            .unwrap();
+        assert_eq!(clockify_preview.worklogs.len(), 1);
+        assert_eq!(clockify_preview.worklogs[0].issue_key, "ABC-1");
+        assert_eq!(
+            clockify_preview.worklogs[0].started,
+            DateTime::parse_from_rfc3339("2022-09-15T09:15:00+00:00").unwrap()
+        );
+
+        let generic_export = "when,what,hours\n2022-09-16,support for ops#42,1.5\n";
+        let generic_preview = CsvImporter::new(
+            ColumnMapping::new("what", "when", "hours")
+                .with_duration_format(DurationFormat::DecimalHours),
+        )
+        .with_issue_key_regex(Regex::new(r"ops#\d+").unwrap())
+        .import(generic_export.as_bytes())
+        .unwrap();
+        assert_eq!(generic_preview.worklogs[0].issue_key, "ops#42");
+        assert_eq!(
+            generic_preview.worklogs[0].time_spent,
+            Duration::minutes(90)
+        );
+    }
+
+    #[test]
+    fn import_previews_jira_durations_in_jira_notation() {
+        let export = "when,what,spent\n2022-09-16,ABC-3 planning,1d 30m\n";
+        let preview = CsvImporter::new(
+            ColumnMapping::new("what", "when", "spent").with_duration_format(DurationFormat::Jira(
+                TimeTrackingConfig::new(6.0, 5.0).unwrap(),
+            )),
+        )
+        .import(export.as_bytes())
+        .unwrap();
+
+        assert_eq!(preview.worklogs[0].time_spent, Duration::minutes(390));
+        assert!(preview
+            .to_string()
+            .contains("  2022-09-16 00:00  ABC-3        1d 30m  ABC-3 planning"));
+    }
+
+    #[test]
+    fn import_returns_error_if_mapped_column_is_missing() {
+        let uut = CsvImporter::new(ColumnMapping::toggl());
+        assert!(uut.import("Description,Duration\n".as_bytes()).is_err());
+    }
+}
@@ -0,0 +1,51 @@
+use crate::jira::{format_duration, TimeTrackingConfig, Worklog};
+use std::fmt::Display;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct UnmatchedRow {
+    pub line: u64,
+    pub description: String,
+    pub reason: String,
+}
+
+/// Result of parsing an export, shown to the user before anything is uploaded.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct ImportPreview {
+    pub worklogs: Vec<Worklog>,
+    pub unmatched: Vec<UnmatchedRow>,
+    pub time_tracking: Option<TimeTrackingConfig>,
+}
+
+impl ImportPreview {
+    /// Shows durations in Jira notation instead of hours and minutes.
+    pub fn with_time_tracking(mut self, time_tracking: TimeTrackingConfig) -> Self {
+        self.time_tracking = Some(time_tracking);
+        self
+    }
+}
+
+impl Display for ImportPreview {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Worklogs to import ({}):", self.worklogs.len())?;
+        for worklog in &self.worklogs {
+            let time_spent = format_duration(worklog.time_spent, self.time_tracking.as_ref());
+            writeln!(
+                f,
+                "  {}  {:<12} {:>6}  {}",
+                worklog.started.format("%Y-%m-%d %H:%M"),
+                worklog.issue_key,
+                time_spent,
+                worklog.comment.as_deref().unwrap_or_default()
+            )?;
+        }
+        write!(f, "Unmatched rows ({}):", self.unmatched.len())?;
+        for row in &self.unmatched {
+            write!(
+                f,
+                "\n  line {}: {} ({})",
+                row.line, row.description, row.reason
+            )?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,7 @@
+mod column_mapping;
+mod csv_importer;
+mod import_preview;
+
+pub use column_mapping::{ColumnMapping, DurationFormat};
+pub use csv_importer::CsvImporter;
+pub use import_preview::{ImportPreview, UnmatchedRow};
@@ -0,0 +1,85 @@
+use serde_json::Value;
+
+const BLOCK_NODES: [&str; 6] = [
+    "paragraph",
+    "heading",
+    "listItem",
+    "blockquote",
+    "codeBlock",
+    "tableRow",
+];
+
+pub fn to_plain_text(document: &Value) -> String {
+    let mut text = String::new();
+    append_node_text(document, &mut text);
+    text.trim().to_string()
+}
+
+fn append_node_text(node: &Value, text: &mut String) {
+    match node["type"].as_str() {
+        Some("text") => text.push_str(node["text"].as_str().unwrap_or_default()),
+        Some("hardBreak") => text.push('\n'),
+        Some("mention") => text.push_str(node["attrs"]["text"].as_str().unwrap_or_default()),
+        Some("emoji") => text.push_str(node["attrs"]["shortName"].as_str().unwrap_or_default()),
+        node_type => {
+            if let Some(content) = node["content"].as_array() {
+                content
+                    .iter()
+                    .for_each(|child| append_node_text(child, text));
+            }
+            if node_type.is_some_and(|node_type| BLOCK_NODES.contains(&node_type))
+                && !text.ends_with('\n')
+            {
+                text.push('\n');
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn to_plain_text_extracts_text_from_paragraphs() {
+        let document = json!({
+            "type": "doc",
+            "version": 1,
+            "content": [
+                {
+                    "type": "paragraph",
+                    "content": [
+                        { "type": "text", "text": "Fixed " },
+                        { "type": "text", "text": "login", "marks": [{ "type": "strong" }] },
+                        { "type": "hardBreak" },
+                        { "type": "mention", "attrs": { "id": "123", "text": "@John" } }
+                    ]
+                },
+                {
+                    "type": "bulletList",
+                    "content": [
+                        {
+                            "type": "listItem",
+                            "content": [
+                                {
+                                    "type": "paragraph",
+                                    "content": [{ "type": "text", "text": "reviewed" }]
+                                }
+                            ]
+                        }
+                    ]
+                }
+            ]
+        });
+
+        assert_eq!(to_plain_text(&document), "Fixed login\n@John\nreviewed");
+    }
+
+    #[test]
+    fn to_plain_text_returns_empty_string_for_empty_document() {
+        let document = json!({ "type": "doc", "version": 1, "content": [] });
+
+        assert_eq!(to_plain_text(&document), "");
+    }
+}
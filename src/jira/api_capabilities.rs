@@ -0,0 +1,108 @@
+use super::{
+    client_trait::ClientTrait,
+    server_info::{DeploymentType, ServerInfo},
+};
+
+const SERVER_INFO_ENDPOINT: &str = "/rest/api/2/serverInfo";
+
+/// What the detected Jira instance supports and which REST flavor to talk to it.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ApiCapabilities {
+    pub server_info: ServerInfo,
+    pub api_version: u8,
+    pub adf_comments: bool,
+    pub account_ids: bool,
+    pub jql_parse: bool,
+}
+
+impl ApiCapabilities {
+    pub fn from_server_info(server_info: ServerInfo) -> Self {
+        let is_cloud = server_info.is_cloud();
+        Self {
+            server_info,
+            api_version: if is_cloud { 3 } else { 2 },
+            adf_comments: is_cloud,
+            account_ids: is_cloud,
+            jql_parse: is_cloud,
+        }
+    }
+
+    /// Probes `serverInfo` of the Jira instance at `jira_base_url` (without `/rest/api/...`).
+    pub fn detect<ClientType: ClientTrait>(
+        jira_client: &ClientType,
+        jira_base_url: &str,
+    ) -> anyhow::Result<Self> {
+        let server_info_response = jira_client
+            .request_get(format!(
+                "{}{}",
+                jira_base_url.trim_end_matches('/'),
+                SERVER_INFO_ENDPOINT
+            ))?
+            .error_for_status()?;
+        let server_info = ServerInfo::from_json(&server_info_response.json()?)?;
+        Ok(Self::from_server_info(server_info))
+    }
+
+    pub fn deployment_type(&self) -> DeploymentType {
+        self.server_info.deployment_type
+    }
+
+    /// REST root to pass as `jira_api_root_url` of the fetchers.
+    pub fn api_root_url(&self, jira_base_url: &str) -> String {
+        format!(
+            "{}/rest/api/{}",
+            jira_base_url.trim_end_matches('/'),
+            self.api_version
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jira::Client;
+
+    #[test]
+    fn detect_selects_v3_for_cloud() {
+        let _server_info_mock = mockito::mock("GET", "/rest/api/2/serverInfo")
+            .with_status(200)
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .with_body(
+                r###"{
+                    "baseUrl": "https://example.atlassian.net",
+                    "version": "1001.0.0-SNAPSHOT",
+                    "versionNumbers": [1001, 0, 0],
+                    "deploymentType": "Cloud",
+                    "buildNumber": 100204
+                }"###,
+            )
+            .create();
+
+        let uut = ApiCapabilities::detect(&Client::new("user", "password"), &mockito::server_url())
+            .unwrap();
+
+        assert_eq!(uut.deployment_type(), DeploymentType::Cloud);
+        assert_eq!(uut.api_version, 3);
+        assert!(uut.adf_comments && uut.account_ids && uut.jql_parse);
+        assert_eq!(
+            uut.api_root_url("https://example.atlassian.net/"),
+            "https://example.atlassian.net/rest/api/3"
+        );
+    }
+
+    #[test]
+    fn from_server_info_selects_v2_for_server() {
+        let server_info = ServerInfo::from_json(&serde_json::json!({
+            "version": "8.20.10",
+            "deploymentType": "Server"
+        }))
+        .unwrap();
+
+        assert_eq!(server_info.version_numbers, vec![8, 20, 10]);
+
+        let uut = ApiCapabilities::from_server_info(server_info);
+        assert_eq!(uut.deployment_type(), DeploymentType::Server);
+        assert_eq!(uut.api_version, 2);
+        assert!(!uut.adf_comments && !uut.account_ids && !uut.jql_parse);
+    }
+}
@@ -40,4 +40,56 @@ impl ClientTrait for Client {
             .basic_auth(&self.login, Some(&self.password))
             .send()
     }
+
+    fn request_post<Url: reqwest::IntoUrl + 'static>(
+        &self,
+        url: Url,
+        body: &serde_json::Value,
+    ) -> reqwest::Result<reqwest::blocking::Response> {
+        reqwest::blocking::Client::new()
+            .post(url)
+            .headers(constants::HTTP_HEADER.clone())
+            .basic_auth(&self.login, Some(&self.password))
+            .json(body)
+            .send()
+    }
+}
+
+/// Client for REST APIs authenticating with a bearer token (Tempo, GitLab, Jira PATs).
+pub struct BearerClient {
+    pub token: String,
+}
+
+impl BearerClient {
+    pub fn new(token: &str) -> Self {
+        Self {
+            token: token.to_string(),
+        }
+    }
+}
+
+impl ClientTrait for BearerClient {
+    fn request_get<Url: reqwest::IntoUrl + 'static>(
+        &self,
+        url: Url,
+    ) -> reqwest::Result<reqwest::blocking::Response> {
+        reqwest::blocking::Client::new()
+            .get(url)
+            .headers(constants::HTTP_HEADER.clone())
+            .bearer_auth(&self.token)
+            .send()
+    }
+
+    fn request_post<Url: reqwest::IntoUrl + 'static>(
+        &self,
+        url: Url,
+        body: &serde_json::Value,
+    ) -> reqwest::Result<reqwest::blocking::Response> {
+        reqwest::blocking::Client::new()
+            .post(url)
+            .headers(constants::HTTP_HEADER.clone())
+            .bearer_auth(&self.token)
+            .json(body)
+            .send()
+    }
 }
@@ -6,4 +6,9 @@ use mockall::{automock, predicate::*};
 #[cfg_attr(test, automock)]
 pub trait ClientTrait {
     fn request_get<Url: IntoUrl + 'static>(&self, url: Url) -> Result<Response>;
+    fn request_post<Url: IntoUrl + 'static>(
+        &self,
+        url: Url,
+        body: &serde_json::Value,
+    ) -> Result<Response>;
 }
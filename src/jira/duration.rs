@@ -0,0 +1,304 @@
+use super::client_trait::ClientTrait;
+use anyhow::anyhow;
+use chrono::Duration;
+use serde_json::Value;
+
+const TIME_TRACKING_ENDPOINT: &str = "/configuration/timetracking";
+
+/// Length of Jira's working day and week, used to read and write durations like `1w 2d 3h 30m`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct TimeTrackingConfig {
+    pub working_day: Duration,
+    pub working_week: Duration,
+}
+
+impl Default for TimeTrackingConfig {
+    /// Jira defaults: 8 hours per day, 5 days per week.
+    fn default() -> Self {
+        Self {
+            working_day: Duration::hours(8),
+            working_week: Duration::hours(40),
+        }
+    }
+}
+
+impl TimeTrackingConfig {
+    /// Fails unless both lengths are positive and fit in a `Duration`.
+    pub fn new(hours_per_day: f64, days_per_week: f64) -> anyhow::Result<Self> {
+        let invalid = || {
+            anyhow!(
+                "Invalid time tracking configuration: {} hours per day, {} days per week",
+                hours_per_day,
+                days_per_week
+            )
+        };
+        let working_day = checked_fractional_seconds(hours_per_day * 3600.0)
+            .filter(|working_day| *working_day > Duration::zero())
+            .ok_or_else(invalid)?;
+        let working_week =
+            checked_fractional_seconds(working_day.num_seconds() as f64 * days_per_week)
+                .filter(|working_week| *working_week > Duration::zero())
+                .ok_or_else(invalid)?;
+        Ok(Self {
+            working_day,
+            working_week,
+        })
+    }
+
+    pub fn from_json(json: &Value) -> anyhow::Result<Self> {
+        let read = |field: &str| {
+            json[field]
+                .as_f64()
+                .ok_or_else(|| anyhow!("Time tracking configuration is missing '{}'", field))
+        };
+        Self::new(read("workingHoursPerDay")?, read("workingDaysPerWeek")?)
+    }
+
+    /// Reads the configuration from `/configuration/timetracking` of the REST root.
+    pub fn fetch<ClientType: ClientTrait>(
+        jira_client: &ClientType,
+        jira_api_root_url: &str,
+    ) -> anyhow::Result<Self> {
+        let response = jira_client
+            .request_get(format!(
+                "{}{}",
+                jira_api_root_url.trim_end_matches('/'),
+                TIME_TRACKING_ENDPOINT
+            ))?
+            .error_for_status()?;
+        Self::from_json(&response.json()?)
+    }
+
+    fn units(&self) -> [(&'static str, Duration); 5] {
+        [
+            ("w", self.working_week),
+            ("d", self.working_day),
+            ("h", Duration::hours(1)),
+            ("m", Duration::minutes(1)),
+            ("s", Duration::seconds(1)),
+        ]
+    }
+
+    /// Parses `1w 2d 3h 30m`, `1.5h` or `90m`; a bare number means minutes, as in Jira.
+    /// Signs and exponents are rejected.
+    pub fn parse(&self, text: &str) -> anyhow::Result<Duration> {
+        let invalid = || anyhow!("Invalid duration '{}'", text);
+        let trimmed = text.trim();
+        if !trimmed.is_empty()
+            && trimmed
+                .chars()
+                .all(|c| c.is_ascii_digit() || c == '.' || c == ',')
+        {
+            let minutes = trimmed
+                .replace(',', ".")
+                .parse::<f64>()
+                .map_err(|_| invalid())?;
+            return checked_fractional_seconds(minutes * 60.0).ok_or_else(invalid);
+        }
+
+        let mut seconds = 0.0;
+        let mut rest = trimmed;
+        while !rest.is_empty() {
+            let number_length = rest
+                .find(|c: char| !c.is_ascii_digit() && c != '.' && c != ',')
+                .ok_or_else(invalid)?;
+            let value = rest[..number_length]
+                .replace(',', ".")
+                .parse::<f64>()
+                .map_err(|_| invalid())?;
+            let unit_text = rest[number_length..].trim_start();
+            let unit = unit_text.chars().next().ok_or_else(invalid)?;
+            let (_, unit_length) = self
+                .units()
+                .into_iter()
+                .find(|(name, _)| name.starts_with(unit.to_ascii_lowercase()))
+                .ok_or_else(invalid)?;
+            seconds += value * unit_length.num_seconds() as f64;
+            rest = unit_text[unit.len_utf8()..].trim_start();
+        }
+        if trimmed.is_empty() {
+            return Err(invalid());
+        }
+        checked_fractional_seconds(seconds).ok_or_else(invalid)
+    }
+
+    /// Formats like Jira does, largest units first, e.g. `1d 2h 30m`; zero is `0m`.
+    pub fn format(&self, duration: Duration) -> String {
+        format_in_units(duration, &self.units())
+    }
+}
+
+/// Formats in hours, minutes and seconds only, e.g. `26h 30m`, for when no Jira instance's
+/// working day is known; zero is `0m`.
+pub fn format_neutral(duration: Duration) -> String {
+    format_in_units(
+        duration,
+        &[
+            ("h", Duration::hours(1)),
+            ("m", Duration::minutes(1)),
+            ("s", Duration::seconds(1)),
+        ],
+    )
+}
+
+/// Formats in Jira notation when the instance's time tracking is known, else neutrally.
+pub fn format_duration(duration: Duration, time_tracking: Option<&TimeTrackingConfig>) -> String {
+    match time_tracking {
+        Some(time_tracking) => time_tracking.format(duration),
+        None => format_neutral(duration),
+    }
+}
+
+fn format_in_units(duration: Duration, units: &[(&str, Duration)]) -> String {
+    let mut remaining = duration.num_seconds().abs();
+    let mut parts = vec![];
+    for (name, unit_length) in units {
+        let unit_seconds = unit_length.num_seconds();
+        if unit_seconds > 0 && remaining >= unit_seconds {
+            parts.push(format!("{}{}", remaining / unit_seconds, name));
+            remaining %= unit_seconds;
+        }
+    }
+    if parts.is_empty() {
+        return "0m".to_string();
+    }
+    let sign = if duration < Duration::zero() { "-" } else { "" };
+    format!("{}{}", sign, parts.join(" "))
+}
+
+/// `Duration::seconds` returning `None` instead of panicking when out of range.
+pub(crate) fn checked_seconds(seconds: i64) -> Option<Duration> {
+    (seconds.checked_abs()? <= Duration::max_value().num_seconds())
+        .then(|| Duration::seconds(seconds))
+}
+
+/// Rounds to whole seconds; `None` for NaN, infinities and values out of range.
+pub(crate) fn checked_fractional_seconds(seconds: f64) -> Option<Duration> {
+    if !seconds.is_finite() || seconds.abs() >= i64::MAX as f64 {
+        return None;
+    }
+    checked_seconds(seconds.round() as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jira::Client;
+
+    #[test]
+    fn parse_reads_jira_style_durations() {
+        let uut = TimeTrackingConfig::default();
+
+        assert_eq!(
+            uut.parse("1w 2d 3h 30m").unwrap(),
+            Duration::hours(40 + 16 + 3) + Duration::minutes(30)
+        );
+        assert_eq!(uut.parse("1.5h").unwrap(), Duration::minutes(90));
+        assert_eq!(uut.parse("90m").unwrap(), Duration::minutes(90));
+        assert_eq!(uut.parse("2h15m").unwrap(), Duration::minutes(135));
+        assert_eq!(uut.parse("45").unwrap(), Duration::minutes(45));
+        assert!(uut.parse("").is_err());
+        assert!(uut.parse("3x").is_err());
+        assert!(uut.parse("h").is_err());
+    }
+
+    #[test]
+    fn parse_rejects_non_finite_and_out_of_range_values() {
+        let uut = TimeTrackingConfig::default();
+
+        assert!(uut.parse("NaN").is_err());
+        assert!(uut.parse("inf").is_err());
+        assert!(uut.parse("-inf").is_err());
+        assert!(uut.parse("1e30").is_err());
+        assert!(uut.parse("99999999999999999w").is_err());
+        assert!(uut.parse("99999999999999999999999999999999999h").is_err());
+    }
+
+    #[test]
+    fn parse_rejects_signs_and_exponents() {
+        let uut = TimeTrackingConfig::default();
+
+        assert!(uut.parse("-45").is_err());
+        assert!(uut.parse("+45").is_err());
+        assert!(uut.parse("1e3").is_err());
+        assert!(uut.parse("1e3h").is_err());
+        assert!(uut.parse("-1h").is_err());
+        assert_eq!(uut.parse("1,5").unwrap(), Duration::seconds(90));
+    }
+
+    #[test]
+    fn new_rejects_invalid_working_day_and_week() {
+        assert!(TimeTrackingConfig::new(f64::NAN, 5.0).is_err());
+        assert!(TimeTrackingConfig::new(0.0, 5.0).is_err());
+        assert!(TimeTrackingConfig::new(-8.0, 5.0).is_err());
+        assert!(TimeTrackingConfig::new(8.0, 0.0).is_err());
+        assert!(TimeTrackingConfig::new(8.0, f64::INFINITY).is_err());
+        assert!(TimeTrackingConfig::new(1e300, 5.0).is_err());
+        assert!(TimeTrackingConfig::new(8.0, 1e300).is_err());
+        assert_eq!(
+            TimeTrackingConfig::new(8.0, 5.0).unwrap(),
+            TimeTrackingConfig::default()
+        );
+    }
+
+    #[test]
+    fn from_json_rejects_out_of_range_configuration() {
+        assert!(TimeTrackingConfig::from_json(&serde_json::json!({
+            "workingHoursPerDay": 1e300,
+            "workingDaysPerWeek": 5.0
+        }))
+        .is_err());
+    }
+
+    #[test]
+    fn format_neutral_uses_hours_minutes_and_seconds() {
+        assert_eq!(format_neutral(Duration::hours(26)), "26h");
+        assert_eq!(
+            format_neutral(Duration::minutes(90) + Duration::seconds(5)),
+            "1h 30m 5s"
+        );
+        assert_eq!(format_neutral(-Duration::minutes(45)), "-45m");
+        assert_eq!(format_neutral(Duration::zero()), "0m");
+    }
+
+    #[test]
+    fn format_uses_configured_day_and_week() {
+        let uut = TimeTrackingConfig::new(7.5, 4.0).unwrap();
+
+        assert_eq!(uut.format(Duration::hours(30)), "1w");
+        assert_eq!(
+            uut.format(Duration::hours(39) + Duration::seconds(5)),
+            "1w 1d 1h 30m 5s"
+        );
+        assert_eq!(uut.format(-Duration::minutes(90)), "-1h 30m");
+        assert_eq!(uut.format(Duration::zero()), "0m");
+        assert_eq!(
+            uut.parse(&uut.format(Duration::hours(39))).unwrap(),
+            Duration::hours(39)
+        );
+    }
+
+    #[test]
+    fn fetch_reads_time_tracking_configuration() {
+        let _mock = mockito::mock("GET", "/rest/api/2/configuration/timetracking")
+            .with_status(200)
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .with_body(
+                r###"{
+                    "workingHoursPerDay": 6.0,
+                    "workingDaysPerWeek": 4.5,
+                    "timeFormat": "pretty",
+                    "defaultUnit": "minute"
+                }"###,
+            )
+            .create();
+        let client = Client::new("user", "token");
+
+        let config =
+            TimeTrackingConfig::fetch(&client, &format!("{}/rest/api/2", mockito::server_url()))
+                .unwrap();
+
+        assert_eq!(config, TimeTrackingConfig::new(6.0, 4.5).unwrap());
+        assert_eq!(config.working_week, Duration::hours(27));
+    }
+}
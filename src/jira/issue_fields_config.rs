@@ -0,0 +1,52 @@
+const DEFAULT_FIELDS: [&str; 8] = [
+    "summary",
+    "parent",
+    "project",
+    "issuetype",
+    "status",
+    "labels",
+    "components",
+    "assignee",
+];
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct IssueFieldsConfig {
+    pub fields: Vec<String>,
+    pub custom_fields: Vec<String>,
+    pub billing_account_field: Option<String>,
+}
+
+impl IssueFieldsConfig {
+    pub fn with_custom_field(mut self, name: &str) -> Self {
+        self.custom_fields.push(name.to_string());
+        self
+    }
+
+    pub fn with_billing_account_field(mut self, name: &str) -> Self {
+        self.billing_account_field = Some(name.to_string());
+        self
+    }
+
+    pub fn custom_field_names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.custom_fields.iter().map(String::as_str).collect();
+        if let Some(billing_account_field) = &self.billing_account_field {
+            if !names.contains(&billing_account_field.as_str()) {
+                names.push(billing_account_field);
+            }
+        }
+        names
+    }
+}
+
+impl Default for IssueFieldsConfig {
+    fn default() -> Self {
+        Self {
+            fields: DEFAULT_FIELDS
+                .iter()
+                .map(|field| field.to_string())
+                .collect(),
+            custom_fields: vec![],
+            billing_account_field: None,
+        }
+    }
+}
@@ -0,0 +1,47 @@
+use super::jql::JqlClause;
+
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct IssueFilterConfig {
+    pub extra_jql: Option<String>,
+    pub included_projects: Vec<String>,
+    pub excluded_projects: Vec<String>,
+}
+
+impl IssueFilterConfig {
+    pub fn with_extra_jql(mut self, jql: &str) -> Self {
+        self.extra_jql = Some(jql.to_string());
+        self
+    }
+
+    pub fn with_included_projects(mut self, projects: &[&str]) -> Self {
+        self.included_projects = projects.iter().map(|project| project.to_string()).collect();
+        self
+    }
+
+    pub fn with_excluded_projects(mut self, projects: &[&str]) -> Self {
+        self.excluded_projects = projects.iter().map(|project| project.to_string()).collect();
+        self
+    }
+
+    pub fn clauses(&self) -> Vec<JqlClause> {
+        let mut clauses = vec![];
+        if !self.included_projects.is_empty() {
+            clauses.push(JqlClause::in_list(
+                "project",
+                self.included_projects.iter().cloned(),
+            ));
+        }
+        if !self.excluded_projects.is_empty() {
+            clauses.push(JqlClause::not_in_list(
+                "project",
+                self.excluded_projects.iter().cloned(),
+            ));
+        }
+        if let Some(extra_jql) = self.extra_jql.as_deref().map(str::trim) {
+            if !extra_jql.is_empty() {
+                clauses.push(JqlClause::raw(extra_jql));
+            }
+        }
+        clauses
+    }
+}
@@ -0,0 +1,148 @@
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct IssueInfo {
+    pub project_key: Option<String>,
+    pub issue_type: Option<String>,
+    pub status: Option<String>,
+    pub status_category: Option<String>,
+    pub labels: Vec<String>,
+    pub components: Vec<String>,
+    pub parent_key: Option<String>,
+    pub assignee: Option<String>,
+    pub billing_account: Option<String>,
+    pub custom_fields: BTreeMap<String, Value>,
+}
+
+impl IssueInfo {
+    pub fn from_json(
+        issue: &Value,
+        custom_field_ids: &BTreeMap<String, String>,
+        billing_account_field: Option<&str>,
+    ) -> Self {
+        let fields = &issue["fields"];
+        let text = |value: &Value| value.as_str().map(|text| text.to_string());
+        let names = |values: &Value, name_field: Option<&str>| {
+            values
+                .as_array()
+                .map(|values| {
+                    values
+                        .iter()
+                        .filter_map(|value| match name_field {
+                            Some(name_field) => text(&value[name_field]),
+                            None => text(value),
+                        })
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+
+        let custom_fields: BTreeMap<_, _> = custom_field_ids
+            .iter()
+            .filter(|(_, id)| !fields[id.as_str()].is_null())
+            .map(|(name, id)| (name.clone(), fields[id.as_str()].clone()))
+            .collect();
+
+        Self {
+            project_key: text(&fields["project"]["key"]),
+            issue_type: text(&fields["issuetype"]["name"]),
+            status: text(&fields["status"]["name"]),
+            status_category: text(&fields["status"]["statusCategory"]["key"]),
+            labels: names(&fields["labels"], None),
+            components: names(&fields["components"], Some("name")),
+            parent_key: text(&fields["parent"]["key"]),
+            assignee: text(&fields["assignee"]["displayName"]),
+            billing_account: billing_account_field
+                .and_then(|name| custom_fields.get(name))
+                .and_then(Self::custom_field_text),
+            custom_fields,
+        }
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.status_category.as_deref() == Some("done")
+    }
+
+    fn custom_field_text(value: &Value) -> Option<String> {
+        match value {
+            Value::String(text) => Some(text.clone()),
+            Value::Number(number) => Some(number.to_string()),
+            Value::Object(_) => ["value", "name", "key"]
+                .iter()
+                .find_map(|field| value[*field].as_str())
+                .map(|text| text.to_string()),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn from_json_reads_issue_metadata() {
+        let issue = json!({
+            "key": "PROJ-12",
+            "fields": {
+                "summary": "Login page",
+                "project": { "key": "PROJ", "name": "Project" },
+                "issuetype": { "name": "Story" },
+                "status": { "name": "Closed", "statusCategory": { "key": "done" } },
+                "labels": ["frontend", "billable"],
+                "components": [{ "name": "Web" }, { "name": "Auth" }],
+                "parent": { "key": "PROJ-1" },
+                "assignee": { "displayName": "John Doe" },
+                "customfield_10100": { "id": "3", "value": "ACME-2022" },
+                "customfield_10200": 5
+            }
+        });
+        let custom_field_ids = BTreeMap::from([
+            (
+                "Billing account".to_string(),
+                "customfield_10100".to_string(),
+            ),
+            ("Story points".to_string(), "customfield_10200".to_string()),
+            ("Team".to_string(), "customfield_10300".to_string()),
+        ]);
+
+        let result = IssueInfo::from_json(&issue, &custom_field_ids, Some("Billing account"));
+
+        assert_eq!(
+            result,
+            IssueInfo {
+                project_key: Some("PROJ".to_string()),
+                issue_type: Some("Story".to_string()),
+                status: Some("Closed".to_string()),
+                status_category: Some("done".to_string()),
+                labels: vec!["frontend".to_string(), "billable".to_string()],
+                components: vec!["Web".to_string(), "Auth".to_string()],
+                parent_key: Some("PROJ-1".to_string()),
+                assignee: Some("John Doe".to_string()),
+                billing_account: Some("ACME-2022".to_string()),
+                custom_fields: BTreeMap::from([
+                    (
+                        "Billing account".to_string(),
+                        json!({ "id": "3", "value": "ACME-2022" })
+                    ),
+                    ("Story points".to_string(), json!(5)),
+                ]),
+            }
+        );
+        assert!(result.is_done());
+    }
+
+    #[test]
+    fn from_json_leaves_missing_fields_empty() {
+        let issue = json!({ "key": "PROJ-12", "fields": { "summary": "Login page" } });
+
+        assert_eq!(
+            IssueInfo::from_json(&issue, &BTreeMap::new(), None),
+            IssueInfo::default()
+        );
+    }
+}
@@ -0,0 +1,20 @@
+use super::issue_info::IssueInfo;
+use anyhow::Result;
+use std::collections::BTreeMap;
+
+#[cfg(test)]
+use mockall::{automock, predicate::*};
+
+/// Key, summary and fields of an issue looked up by its numeric id.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ResolvedIssue {
+    pub key: String,
+    pub summary: String,
+    pub info: IssueInfo,
+}
+
+#[cfg_attr(test, automock)]
+pub trait IssueResolverTrait {
+    /// Looks up issues by numeric id, keyed by id; ids Jira doesn't return are left out.
+    fn resolve_issues(&self, issue_ids: &[String]) -> Result<BTreeMap<String, ResolvedIssue>>;
+}
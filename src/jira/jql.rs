@@ -0,0 +1,355 @@
+use chrono::NaiveDate;
+use itertools::Itertools;
+use std::{
+    fmt::{self, Display},
+    ops::Not,
+};
+
+const JQL_DATE_FORMAT: &str = "%Y-%m-%d";
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum JqlValue {
+    Text(String),
+    Date(NaiveDate),
+    Number(i64),
+    /// JQL function call such as `currentUser()`, emitted verbatim.
+    Function(String),
+}
+
+impl From<&str> for JqlValue {
+    fn from(value: &str) -> Self {
+        Self::Text(value.to_string())
+    }
+}
+
+impl From<String> for JqlValue {
+    fn from(value: String) -> Self {
+        Self::Text(value)
+    }
+}
+
+impl From<NaiveDate> for JqlValue {
+    fn from(value: NaiveDate) -> Self {
+        Self::Date(value)
+    }
+}
+
+impl From<i64> for JqlValue {
+    fn from(value: i64) -> Self {
+        Self::Number(value)
+    }
+}
+
+impl Display for JqlValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JqlValue::Text(text) => write!(f, "{}", quote(text)),
+            JqlValue::Date(date) => write!(f, "\"{}\"", date.format(JQL_DATE_FORMAT)),
+            JqlValue::Number(number) => write!(f, "{}", number),
+            JqlValue::Function(function) => write!(f, "{}", function),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum JqlClause {
+    Compare {
+        field: String,
+        operator: &'static str,
+        value: JqlValue,
+    },
+    In {
+        field: String,
+        values: Vec<JqlValue>,
+        negated: bool,
+    },
+    And(Vec<JqlClause>),
+    Or(Vec<JqlClause>),
+    Not(Box<JqlClause>),
+    /// Trusted JQL fragment, always wrapped in parentheses when combined.
+    Raw(String),
+}
+
+impl JqlClause {
+    fn compare(field: &str, operator: &'static str, value: impl Into<JqlValue>) -> Self {
+        Self::Compare {
+            field: field.to_string(),
+            operator,
+            value: value.into(),
+        }
+    }
+
+    pub fn eq(field: &str, value: impl Into<JqlValue>) -> Self {
+        Self::compare(field, "=", value)
+    }
+
+    pub fn ne(field: &str, value: impl Into<JqlValue>) -> Self {
+        Self::compare(field, "!=", value)
+    }
+
+    pub fn gt(field: &str, value: impl Into<JqlValue>) -> Self {
+        Self::compare(field, ">", value)
+    }
+
+    pub fn ge(field: &str, value: impl Into<JqlValue>) -> Self {
+        Self::compare(field, ">=", value)
+    }
+
+    pub fn lt(field: &str, value: impl Into<JqlValue>) -> Self {
+        Self::compare(field, "<", value)
+    }
+
+    pub fn le(field: &str, value: impl Into<JqlValue>) -> Self {
+        Self::compare(field, "<=", value)
+    }
+
+    pub fn in_list<Value: Into<JqlValue>>(
+        field: &str,
+        values: impl IntoIterator<Item = Value>,
+    ) -> Self {
+        Self::In {
+            field: field.to_string(),
+            values: values.into_iter().map(Into::into).collect(),
+            negated: false,
+        }
+    }
+
+    pub fn not_in_list<Value: Into<JqlValue>>(
+        field: &str,
+        values: impl IntoIterator<Item = Value>,
+    ) -> Self {
+        Self::In {
+            field: field.to_string(),
+            values: values.into_iter().map(Into::into).collect(),
+            negated: true,
+        }
+    }
+
+    pub fn raw(fragment: &str) -> Self {
+        Self::Raw(fragment.to_string())
+    }
+
+    pub fn and(self, other: JqlClause) -> Self {
+        match self {
+            Self::And(mut clauses) => {
+                clauses.push(other);
+                Self::And(clauses)
+            }
+            clause => Self::And(vec![clause, other]),
+        }
+    }
+
+    pub fn or(self, other: JqlClause) -> Self {
+        match self {
+            Self::Or(mut clauses) => {
+                clauses.push(other);
+                Self::Or(clauses)
+            }
+            clause => Self::Or(vec![clause, other]),
+        }
+    }
+
+    fn is_compound(&self) -> bool {
+        matches!(self, Self::And(_) | Self::Or(_) | Self::Raw(_))
+    }
+
+    fn fmt_nested(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_compound() {
+            write!(f, "({})", self)
+        } else {
+            write!(f, "{}", self)
+        }
+    }
+
+    fn fmt_joined(
+        clauses: &[JqlClause],
+        separator: &str,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        for (index, clause) in clauses.iter().enumerate() {
+            if index > 0 {
+                write!(f, " {} ", separator)?;
+            }
+            clause.fmt_nested(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for JqlClause {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JqlClause::Compare {
+                field,
+                operator,
+                value,
+            } => write!(f, "{} {} {}", quote_field(field), operator, value),
+            // `IN ()` is rejected by Jira; an empty list matches nothing (or everything when negated).
+            JqlClause::In {
+                values, negated, ..
+            } if values.is_empty() => write!(
+                f,
+                "issuekey {}",
+                if *negated { "IS NOT EMPTY" } else { "IS EMPTY" }
+            ),
+            JqlClause::In {
+                field,
+                values,
+                negated,
+            } => write!(
+                f,
+                "{} {} ({})",
+                quote_field(field),
+                if *negated { "NOT IN" } else { "IN" },
+                values.iter().join(", ")
+            ),
+            JqlClause::And(clauses) => Self::fmt_joined(clauses, "AND", f),
+            JqlClause::Or(clauses) => Self::fmt_joined(clauses, "OR", f),
+            JqlClause::Not(clause) => {
+                write!(f, "NOT ")?;
+                clause.fmt_nested(f)
+            }
+            JqlClause::Raw(fragment) => write!(f, "{}", fragment),
+        }
+    }
+}
+
+impl Not for JqlClause {
+    type Output = Self;
+
+    fn not(self) -> Self::Output {
+        Self::Not(Box::new(self))
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum SortOrder {
+    Asc,
+    Desc,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct JqlQuery {
+    pub clauses: Vec<JqlClause>,
+    pub order_by: Vec<(String, SortOrder)>,
+}
+
+impl JqlQuery {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn and(mut self, clause: JqlClause) -> Self {
+        self.clauses.push(clause);
+        self
+    }
+
+    pub fn order_by(mut self, field: &str, order: SortOrder) -> Self {
+        self.order_by.push((field.to_string(), order));
+        self
+    }
+
+    pub fn url_encoded(&self) -> String {
+        form_urlencoded::byte_serialize(self.to_string().as_bytes()).collect()
+    }
+}
+
+impl Display for JqlQuery {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        JqlClause::fmt_joined(&self.clauses, "AND", f)?;
+        if !self.order_by.is_empty() {
+            if !self.clauses.is_empty() {
+                write!(f, " ")?;
+            }
+            write!(
+                f,
+                "ORDER BY {}",
+                self.order_by
+                    .iter()
+                    .map(|(field, order)| format!(
+                        "{} {}",
+                        quote_field(field),
+                        match order {
+                            SortOrder::Asc => "ASC",
+                            SortOrder::Desc => "DESC",
+                        }
+                    ))
+                    .join(", ")
+            )?;
+        }
+        Ok(())
+    }
+}
+
+fn quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn quote_field(field: &str) -> String {
+    let is_plain = field
+        .chars()
+        .all(|character| character.is_ascii_alphanumeric() || "_.[]".contains(character));
+    if is_plain && !field.is_empty() {
+        field.to_string()
+    } else {
+        quote(field)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn query_quotes_values_and_escapes_special_characters() {
+        let uut = JqlQuery::new()
+            .and(JqlClause::ge(
+                "worklogDate",
+                NaiveDate::from_ymd(2022, 9, 10),
+            ))
+            .and(JqlClause::in_list(
+                "worklogAuthor",
+                ["jan.kowalski", "o\"brien\\x", "Zażółć gęślą"],
+            ))
+            .and(!JqlClause::eq("project", "ABC").or(JqlClause::eq("Story Points", 3)))
+            .and(JqlClause::raw("labels = backend OR labels = api"))
+            .order_by("created", SortOrder::Desc);
+
+        assert_eq!(
+            uut.to_string(),
+            "worklogDate >= \"2022-09-10\" \
+             AND worklogAuthor IN (\"jan.kowalski\", \"o\\\"brien\\\\x\", \"Zażółć gęślą\") \
+             AND NOT (project = \"ABC\" OR \"Story Points\" = 3) \
+             AND (labels = backend OR labels = api) \
+             ORDER BY created DESC"
+        );
+    }
+
+    #[test]
+    fn empty_lists_render_constant_clauses_instead_of_empty_in() {
+        let empty: [&str; 0] = [];
+        let uut = JqlQuery::new()
+            .and(JqlClause::in_list("worklogAuthor", empty))
+            .and(JqlClause::not_in_list("project", empty));
+
+        assert_eq!(
+            uut.to_string(),
+            "issuekey IS EMPTY AND issuekey IS NOT EMPTY"
+        );
+    }
+
+    #[test]
+    fn url_encoded_escapes_reserved_and_non_ascii_characters() {
+        let uut = JqlQuery::new()
+            .and(JqlClause::eq(
+                "assignee",
+                JqlValue::Function("currentUser()".to_string()),
+            ))
+            .and(JqlClause::eq("summary", "a&b=ż"));
+
+        assert_eq!(
+            uut.url_encoded(),
+            "assignee+%3D+currentUser%28%29+AND+summary+%3D+%22a%26b%3D%C5%BC%22"
+        );
+    }
+}
@@ -1,18 +1,49 @@
+mod adf;
+mod api_capabilities;
 mod client;
 mod client_trait;
+mod duration;
+mod issue_fields_config;
+mod issue_filter_config;
+mod issue_info;
+mod issue_resolver_trait;
+mod jql;
+mod server_info;
+mod team_resolver;
+mod user_identity;
 mod user_worklogs;
 mod user_worklogs_fetcher;
 mod user_worklogs_fetcher_trait;
 mod worklog;
+mod worklog_author;
+mod worklog_uploader;
+mod worklog_visibility;
 
-pub use client::Client;
+pub use adf::to_plain_text as adf_to_plain_text;
+pub use api_capabilities::ApiCapabilities;
+pub use client::{BearerClient, Client};
+pub(crate) use client_trait::ClientTrait;
+pub(crate) use duration::{checked_fractional_seconds, checked_seconds};
+pub use duration::{format_duration, format_neutral, TimeTrackingConfig};
+pub use issue_fields_config::IssueFieldsConfig;
+pub use issue_filter_config::IssueFilterConfig;
+pub use issue_info::IssueInfo;
+pub use issue_resolver_trait::{IssueResolverTrait, ResolvedIssue};
+pub use jql::{JqlClause, JqlQuery, JqlValue, SortOrder};
+pub use server_info::{DeploymentType, ServerInfo};
+pub use team_resolver::{TeamResolver, TeamSource};
+pub use user_identity::UserIdentity;
 pub use user_worklogs::UserWorklogs;
 pub use user_worklogs_fetcher::UserWorklogsFetcher;
 pub use user_worklogs_fetcher_trait::UserWorklogsFetcherTrait;
-pub use worklog::Worklog;
+pub use worklog::{Worklog, DEFAULT_ISSUE_KEY_PATTERN, JIRA_WORKLOG_ID_METADATA_KEY};
+pub use worklog_author::WorklogAuthor;
+pub use worklog_uploader::WorklogUploader;
+pub use worklog_visibility::WorklogVisibility;
 
 #[cfg(test)]
 pub mod testing {
     pub use super::client_trait::MockClientTrait;
+    pub use super::issue_resolver_trait::MockIssueResolverTrait;
     pub use super::user_worklogs_fetcher_trait::MockUserWorklogsFetcherTrait;
 }
@@ -0,0 +1,53 @@
+use anyhow::anyhow;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum DeploymentType {
+    Cloud,
+    Server,
+    DataCenter,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ServerInfo {
+    pub base_url: Option<String>,
+    pub version: String,
+    pub version_numbers: Vec<u64>,
+    pub build_number: Option<u64>,
+    pub deployment_type: DeploymentType,
+}
+
+impl ServerInfo {
+    pub fn from_json(server_info: &serde_json::Value) -> anyhow::Result<Self> {
+        let version = server_info["version"]
+            .as_str()
+            .ok_or(anyhow!("Can't parse Jira server version"))?
+            .to_string();
+        let version_numbers = match server_info["versionNumbers"].as_array() {
+            Some(numbers) => numbers
+                .iter()
+                .filter_map(|number| number.as_u64())
+                .collect(),
+            None => version
+                .split('.')
+                .map_while(|number| number.parse().ok())
+                .collect(),
+        };
+        let deployment_type = match server_info["deploymentType"].as_str() {
+            Some("Cloud") => DeploymentType::Cloud,
+            Some("DataCenter") => DeploymentType::DataCenter,
+            _ => DeploymentType::Server,
+        };
+
+        Ok(Self {
+            base_url: server_info["baseUrl"].as_str().map(|url| url.to_string()),
+            version,
+            version_numbers,
+            build_number: server_info["buildNumber"].as_u64(),
+            deployment_type,
+        })
+    }
+
+    pub fn is_cloud(&self) -> bool {
+        self.deployment_type == DeploymentType::Cloud
+    }
+}
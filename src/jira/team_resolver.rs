@@ -0,0 +1,367 @@
+use super::{
+    api_capabilities::ApiCapabilities,
+    client_trait::ClientTrait,
+    user_identity::{search_user, UserIdentity},
+};
+use anyhow::anyhow;
+use reqwest::IntoUrl;
+
+const GROUP_MEMBERS_PAGE_SIZE: usize = 50;
+const USER_ROLE_ACTOR: &str = "atlassian-user-role-actor";
+const GROUP_ROLE_ACTOR: &str = "atlassian-group-role-actor";
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum TeamSource {
+    Group(String),
+    ProjectRole { project_key: String, role: String },
+}
+
+pub struct TeamResolver<UrlType, ClientType>
+where
+    UrlType: IntoUrl,
+    ClientType: ClientTrait,
+{
+    jira_api_root_url: UrlType,
+    jira_client: ClientType,
+    capabilities: Option<ApiCapabilities>,
+    sources: Vec<TeamSource>,
+    included_users: Vec<String>,
+    excluded_users: Vec<String>,
+}
+
+impl<UrlType, ClientType> TeamResolver<UrlType, ClientType>
+where
+    UrlType: IntoUrl,
+    ClientType: ClientTrait,
+{
+    pub fn new(jira_api_root_url: UrlType, jira_client: ClientType) -> Self {
+        Self {
+            jira_api_root_url,
+            jira_client,
+            capabilities: None,
+            sources: vec![],
+            included_users: vec![],
+            excluded_users: vec![],
+        }
+    }
+
+    /// Uses capabilities detected by `ApiCapabilities::detect` to search included users.
+    pub fn with_capabilities(mut self, capabilities: ApiCapabilities) -> Self {
+        self.capabilities = Some(capabilities);
+        self
+    }
+
+    pub fn with_group(mut self, group_name: &str) -> Self {
+        self.sources.push(TeamSource::Group(group_name.to_string()));
+        self
+    }
+
+    pub fn with_project_role(mut self, project_key: &str, role: &str) -> Self {
+        self.sources.push(TeamSource::ProjectRole {
+            project_key: project_key.to_string(),
+            role: role.to_string(),
+        });
+        self
+    }
+
+    /// Users added to the roster even if they are not members of any source; those that
+    /// aren't are looked up through the user search, like the worklogs fetcher does.
+    pub fn with_included_users(mut self, user_names: &[&str]) -> Self {
+        self.included_users = user_names.iter().map(|name| name.to_string()).collect();
+        self
+    }
+
+    /// Users removed from the roster, matched by account id, key, name, email or display name.
+    pub fn with_excluded_users(mut self, user_names: &[&str]) -> Self {
+        self.excluded_users = user_names.iter().map(|name| name.to_string()).collect();
+        self
+    }
+
+    /// Returns current team members in the order they were found, deduplicated by account id,
+    /// key or name; members without any of them are all kept.
+    pub fn resolve(&self) -> anyhow::Result<Vec<UserIdentity>> {
+        let mut members: Vec<UserIdentity> = vec![];
+        for source in &self.sources {
+            let source_members = match source {
+                TeamSource::Group(group_name) => self.fetch_group_members(group_name)?,
+                TeamSource::ProjectRole { project_key, role } => {
+                    self.fetch_project_role_members(project_key, role)?
+                }
+            };
+            for member in source_members {
+                if !members.iter().any(|known| known.matches(&member)) {
+                    members.push(member);
+                }
+            }
+        }
+
+        for user_name in &self.included_users {
+            if members
+                .iter()
+                .any(|member| member.is_referred_by(user_name))
+            {
+                continue;
+            }
+            let member = search_user(
+                &self.jira_client,
+                self.jira_api_root_url.as_str(),
+                self.capabilities.as_ref(),
+                user_name,
+            )?;
+            if !members.iter().any(|known| known.matches(&member)) {
+                members.push(member);
+            }
+        }
+        members.retain(|member| {
+            !self
+                .excluded_users
+                .iter()
+                .any(|user_name| member.is_referred_by(user_name))
+        });
+
+        Ok(members)
+    }
+
+    /// Same as `resolve` but returns the identifiers accepted by the worklogs fetcher.
+    pub fn resolve_user_names(&self) -> anyhow::Result<Vec<String>> {
+        Ok(self
+            .resolve()?
+            .iter()
+            .filter_map(|member| member.jql_value().map(|value| value.to_string()))
+            .collect())
+    }
+
+    fn create_jira_group_members_endpoint(&self, group_name: &str, start_at: usize) -> String {
+        format!(
+            "/group/member?groupname={}&startAt={}&maxResults={}",
+            form_urlencoded::byte_serialize(group_name.as_bytes()).collect::<String>(),
+            start_at,
+            GROUP_MEMBERS_PAGE_SIZE
+        )
+    }
+
+    fn create_jira_project_roles_endpoint(&self, project_key: &str) -> String {
+        format!(
+            "/project/{}/role",
+            form_urlencoded::byte_serialize(project_key.as_bytes()).collect::<String>()
+        )
+    }
+
+    fn request_json(&self, url: String) -> anyhow::Result<serde_json::Value> {
+        Ok(self
+            .jira_client
+            .request_get(url)?
+            .error_for_status()?
+            .json::<serde_json::Value>()?)
+    }
+
+    fn fetch_group_members(&self, group_name: &str) -> anyhow::Result<Vec<UserIdentity>> {
+        let mut members = vec![];
+        loop {
+            let page = self.request_json(format!(
+                "{}{}",
+                self.jira_api_root_url.as_str(),
+                self.create_jira_group_members_endpoint(group_name, members.len())
+            ))?;
+            let values = page["values"]
+                .as_array()
+                .ok_or(anyhow!("Can't parse members of group '{}'", group_name))?;
+            members.extend(values.iter().map(UserIdentity::from_json));
+
+            if values.is_empty() || page["isLast"].as_bool().unwrap_or(true) {
+                return Ok(members);
+            }
+        }
+    }
+
+    fn fetch_project_role_members(
+        &self,
+        project_key: &str,
+        role: &str,
+    ) -> anyhow::Result<Vec<UserIdentity>> {
+        let roles = self.request_json(format!(
+            "{}{}",
+            self.jira_api_root_url.as_str(),
+            self.create_jira_project_roles_endpoint(project_key)
+        ))?;
+        let role_url = roles[role].as_str().ok_or(anyhow!(
+            "Can't find role '{}' in project {}",
+            role,
+            project_key
+        ))?;
+        let role_body = self.request_json(role_url.to_string())?;
+
+        let mut members = vec![];
+        for actor in role_body["actors"]
+            .as_array()
+            .ok_or(anyhow!("Can't parse actors of role '{}'", role))?
+        {
+            match actor["type"].as_str() {
+                Some(USER_ROLE_ACTOR) => {
+                    let mut member = UserIdentity::from_json(&actor["actorUser"]);
+                    member.name = member.name.or(actor["name"].as_str().map(String::from));
+                    member.display_name = member
+                        .display_name
+                        .or(actor["displayName"].as_str().map(String::from));
+                    members.push(member);
+                }
+                Some(GROUP_ROLE_ACTOR) => {
+                    let group_name = actor["actorGroup"]["name"]
+                        .as_str()
+                        .or(actor["name"].as_str())
+                        .ok_or(anyhow!("Can't parse group actor of role '{}'", role))?;
+                    members.extend(self.fetch_group_members(group_name)?);
+                }
+                _ => {}
+            }
+        }
+        Ok(members)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jira::{Client, ServerInfo};
+
+    fn create_uut() -> TeamResolver<String, Client> {
+        TeamResolver::new(mockito::server_url(), Client::new("user", "password"))
+    }
+
+    fn create_get_endpoint_mock(endpoint: &str, body: &str) -> mockito::Mock {
+        mockito::mock("GET", endpoint)
+            .with_status(200)
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .with_body(body)
+            .create()
+    }
+
+    #[test]
+    fn resolve_pages_through_group_members_and_applies_overrides() {
+        let uut = create_uut()
+            .with_group("team devs")
+            .with_included_users(&["contractor"])
+            .with_excluded_users(&["bot@example.com"]);
+
+        let _first_page_mock = create_get_endpoint_mock(
+            "/group/member?groupname=team+devs&startAt=0&maxResults=50",
+            r###"{"isLast": false, "values": [
+                {"accountId": "id-1", "displayName": "Alice"},
+                {"accountId": "id-2", "emailAddress": "bot@example.com"}
+            ]}"###,
+        );
+        let _second_page_mock = create_get_endpoint_mock(
+            "/group/member?groupname=team+devs&startAt=2&maxResults=50",
+            r###"{"isLast": true, "values": [{"accountId": "id-3"}]}"###,
+        );
+        let _search_mock = create_get_endpoint_mock(
+            "/user/search?query=contractor",
+            r###"[{"key": "contractor", "name": "contractor"}]"###,
+        );
+
+        assert_eq!(
+            uut.resolve_user_names().unwrap(),
+            vec!["id-1", "id-3", "contractor"]
+        );
+    }
+
+    #[test]
+    fn resolve_expands_project_role_user_and_group_actors() {
+        let uut = create_uut().with_project_role("ABC", "Developers");
+
+        let _roles_mock = create_get_endpoint_mock(
+            "/project/ABC/role",
+            &format!(
+                r###"{{"Developers": "{}/project/ABC/role/10001"}}"###,
+                mockito::server_url()
+            ),
+        );
+        let _role_mock = create_get_endpoint_mock(
+            "/project/ABC/role/10001",
+            r###"{"actors": [
+                {"type": "atlassian-user-role-actor", "name": "alice", "displayName": "Alice"},
+                {"type": "atlassian-group-role-actor", "name": "qa"}
+            ]}"###,
+        );
+        let _group_mock = create_get_endpoint_mock(
+            "/group/member?groupname=qa&startAt=0&maxResults=50",
+            r###"{"isLast": true, "values": [{"name": "bob"}, {"name": "alice"}]}"###,
+        );
+
+        assert_eq!(uut.resolve_user_names().unwrap(), vec!["alice", "bob"]);
+    }
+
+    #[test]
+    fn resolve_searches_included_users_by_account_id_on_cloud() {
+        let capabilities = ApiCapabilities::from_server_info(
+            ServerInfo::from_json(&serde_json::json!({
+                "version": "1001.0.0",
+                "deploymentType": "Cloud"
+            }))
+            .unwrap(),
+        );
+        let uut = create_uut()
+            .with_capabilities(capabilities)
+            .with_group("cloud devs")
+            .with_included_users(&["Carol Contractor", "id-1"]);
+
+        let _group_mock = create_get_endpoint_mock(
+            "/group/member?groupname=cloud+devs&startAt=0&maxResults=50",
+            r###"{"isLast": true, "values": [{"accountId": "id-1", "displayName": "Alice"}]}"###,
+        );
+        let _search_mock = create_get_endpoint_mock(
+            "/user/search?query=Carol+Contractor",
+            r###"[{"accountId": "id-7", "displayName": "Carol Contractor"}]"###,
+        );
+
+        let members = uut.resolve().unwrap();
+        assert_eq!(
+            members
+                .iter()
+                .map(|member| member.jql_value().unwrap())
+                .collect::<Vec<_>>(),
+            vec!["id-1", "id-7"]
+        );
+        assert_eq!(members[1].name, None);
+    }
+
+    #[test]
+    fn resolve_returns_error_for_unknown_project_role() {
+        let uut = create_uut().with_project_role("ABC", "Unknown");
+
+        let _roles_mock = create_get_endpoint_mock("/project/ABC/role", r###"{}"###);
+
+        assert!(uut.resolve().is_err());
+    }
+
+    #[test]
+    fn resolve_keeps_members_without_identifiers_and_dedupes_the_rest() {
+        let uut = create_uut().with_group("devs").with_group("qa");
+
+        let _devs_mock = create_get_endpoint_mock(
+            "/group/member?groupname=devs&startAt=0&maxResults=50",
+            r###"{"isLast": true, "values": [
+                {"displayName": "Alice"},
+                {"displayName": "Bob"},
+                {"key": "carol", "name": "carol"}
+            ]}"###,
+        );
+        let _qa_mock = create_get_endpoint_mock(
+            "/group/member?groupname=qa&startAt=0&maxResults=50",
+            r###"{"isLast": true, "values": [{"key": "carol", "name": "carol"}]}"###,
+        );
+
+        assert_eq!(
+            uut.resolve()
+                .unwrap()
+                .iter()
+                .map(|member| member
+                    .display_name
+                    .as_deref()
+                    .or(member.name.as_deref())
+                    .unwrap())
+                .collect::<Vec<_>>(),
+            vec!["Alice", "Bob", "carol"]
+        );
+    }
+}
@@ -0,0 +1,61 @@
+use super::{
+    api_capabilities::ApiCapabilities, client_trait::ClientTrait, worklog_author::WorklogAuthor,
+};
+use anyhow::anyhow;
+
+/// A resolved Jira user; the same identifiers Jira reports as a worklog author.
+pub type UserIdentity = WorklogAuthor;
+
+pub(crate) fn user_search_endpoint(parameter: &str, user_name: &str) -> String {
+    format!(
+        "/user/search?{}={}",
+        parameter,
+        form_urlencoded::byte_serialize(user_name.as_bytes()).collect::<String>()
+    )
+}
+
+/// Finds the Jira user the name, email, display name, key or account id refers to.
+/// Without `capabilities` the Cloud search is tried first, then the Server/DC one.
+pub(crate) fn search_user<ClientType: ClientTrait>(
+    jira_client: &ClientType,
+    jira_api_root_url: &str,
+    capabilities: Option<&ApiCapabilities>,
+    user_name: &str,
+) -> anyhow::Result<UserIdentity> {
+    // Jira Cloud searches by `query`, Server/DC requires `username`.
+    let search_parameter = match capabilities {
+        Some(capabilities) if !capabilities.account_ids => "username",
+        _ => "query",
+    };
+    let mut users_response = jira_client.request_get(format!(
+        "{}{}",
+        jira_api_root_url,
+        user_search_endpoint(search_parameter, user_name)
+    ))?;
+    if !users_response.status().is_success() && capabilities.is_none() {
+        users_response = jira_client.request_get(format!(
+            "{}{}",
+            jira_api_root_url,
+            user_search_endpoint("username", user_name)
+        ))?;
+    }
+    let users_response = users_response.error_for_status()?;
+    let users_body = users_response.json::<serde_json::Value>()?;
+    let users = users_body
+        .as_array()
+        .ok_or(anyhow!("Can't parse users as array"))?
+        .iter()
+        .map(UserIdentity::from_json)
+        .collect::<Vec<_>>();
+
+    let referred = users
+        .iter()
+        .filter(|user| user.is_referred_by(user_name))
+        .collect::<Vec<_>>();
+    match (referred.as_slice(), users.as_slice()) {
+        ([user], _) => Ok((*user).clone()),
+        ([], [user]) => Ok(user.clone()),
+        ([], []) => Err(anyhow!("Can't find Jira user '{}'", user_name)),
+        _ => Err(anyhow!("Jira user '{}' is ambiguous", user_name)),
+    }
+}
@@ -2,6 +2,7 @@ use super::worklog::Worklog;
 use chrono::NaiveDate;
 
 #[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct UserWorklogs {
     pub user: String,
     pub start_date: NaiveDate,
@@ -18,9 +19,9 @@ impl UserWorklogs {
     ) -> Self {
         Self {
             user: user.to_string(),
-            start_date: start_date,
-            end_date: end_date,
-            worklogs: worklogs,
+            start_date,
+            end_date,
+            worklogs,
         }
     }
 }
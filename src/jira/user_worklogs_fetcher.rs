@@ -1,13 +1,32 @@
 use super::{
-    client_trait::ClientTrait, user_worklogs::UserWorklogs,
+    adf,
+    api_capabilities::ApiCapabilities,
+    client_trait::ClientTrait,
+    duration::{checked_seconds, TimeTrackingConfig},
+    issue_fields_config::IssueFieldsConfig,
+    issue_filter_config::IssueFilterConfig,
+    issue_info::IssueInfo,
+    issue_resolver_trait::{IssueResolverTrait, ResolvedIssue},
+    jql::{JqlClause, JqlQuery},
+    user_identity::{search_user, UserIdentity},
+    user_worklogs::UserWorklogs,
     user_worklogs_fetcher_trait::UserWorklogsFetcherTrait,
+    worklog_author::WorklogAuthor,
+    worklog_visibility::WorklogVisibility,
 };
-use crate::jira::worklog::Worklog;
+use crate::jira::worklog::{Worklog, JIRA_TIME_FORMAT};
 use anyhow::anyhow;
-use chrono::NaiveDate;
+use chrono::{DateTime, FixedOffset};
+use chrono_tz::Tz;
+use itertools::Itertools;
 use reqwest::IntoUrl;
+use std::{
+    cell::{Cell, RefCell},
+    collections::BTreeMap,
+};
 
-const JIRA_TIME_FORMAT: &'static str = "%Y-%m-%dT%H:%M:%S%.3f%z";
+const ISSUES_PAGE_SIZE: usize = 50;
+const CURRENT_USER: &str = "currentUser()";
 
 pub struct UserWorklogsFetcher<UrlType, ClientType>
 where
@@ -16,6 +35,13 @@ where
 {
     jira_api_root_url: UrlType,
     jira_client: ClientType,
+    timezone: Option<Tz>,
+    time_tracking: Option<TimeTrackingConfig>,
+    issue_fields: IssueFieldsConfig,
+    issue_filter: IssueFilterConfig,
+    issue_filter_validated: Cell<bool>,
+    capabilities: Option<ApiCapabilities>,
+    resolved_users: RefCell<BTreeMap<String, UserIdentity>>,
 }
 
 impl<UrlType, ClientType> UserWorklogsFetcherTrait for UserWorklogsFetcher<UrlType, ClientType>
@@ -29,22 +55,145 @@ where
         start_date: chrono::NaiveDate,
         end_date: chrono::NaiveDate,
     ) -> anyhow::Result<super::user_worklogs::UserWorklogs> {
-        let issues_response = self
-            .fetch_issues(user_name, &start_date, &end_date)?
-            .error_for_status()?;
-        let issues_body = issues_response.json::<serde_json::Value>()?;
+        self.fetch_team(&[user_name.to_string()], start_date, end_date)?
+            .remove(user_name)
+            .ok_or(anyhow!("Missing worklogs of Jira user '{}'", user_name))
+    }
 
-        let worklogs = issues_body["issues"]
-            .as_array()
-            .ok_or(anyhow!("Can't parse 'issues' as array"))?
-            .into_iter()
-            .map(|issue| self.process_issue(issue, user_name, &start_date, &end_date))
+    fn timezone(&self) -> Option<Tz> {
+        self.timezone
+    }
+
+    fn time_tracking(&self) -> Option<TimeTrackingConfig> {
+        self.time_tracking
+    }
+
+    fn fetch_team(
+        &self,
+        user_names: &[String],
+        start_date: chrono::NaiveDate,
+        end_date: chrono::NaiveDate,
+    ) -> anyhow::Result<BTreeMap<String, UserWorklogs>> {
+        let users = user_names
+            .iter()
+            .map(|user_name| self.resolve_user(user_name))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        let jql_users = users
+            .iter()
+            .zip(user_names)
+            .map(|(user, user_name)| {
+                user.jql_value().ok_or(anyhow!(
+                    "Jira user '{}' has no usable identifier",
+                    user_name
+                ))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        self.validate_issue_filter()?;
+        let custom_field_ids = self.resolve_custom_field_ids()?;
+        let issues =
+            self.fetch_all_issues(&jql_users, &start_date, &end_date, &custom_field_ids)?;
+
+        let worklogs: Vec<Worklog> = issues
+            .iter()
+            .map(|issue| {
+                let issue_info = IssueInfo::from_json(
+                    issue,
+                    &custom_field_ids,
+                    self.issue_fields.billing_account_field.as_deref(),
+                );
+                self.process_issue(issue, &issue_info, &users, &start_date, &end_date)
+            })
             .collect::<anyhow::Result<Vec<_>>>()?
             .into_iter()
             .flatten()
             .collect();
 
-        Ok(UserWorklogs::new(user_name, start_date, end_date, worklogs))
+        Ok(user_names
+            .iter()
+            .zip(&users)
+            .map(|(user_name, user)| {
+                let user_worklogs = worklogs
+                    .iter()
+                    .filter(|worklog| {
+                        worklog
+                            .author
+                            .as_ref()
+                            .is_some_and(|author| self.author_is(author, user))
+                    })
+                    .cloned()
+                    .collect();
+                (
+                    user_name.clone(),
+                    UserWorklogs::new(user_name, start_date, end_date, user_worklogs),
+                )
+            })
+            .collect())
+    }
+}
+
+impl<UrlType, ClientType> IssueResolverTrait for UserWorklogsFetcher<UrlType, ClientType>
+where
+    UrlType: IntoUrl,
+    ClientType: ClientTrait,
+{
+    fn resolve_issues(
+        &self,
+        issue_ids: &[String],
+    ) -> anyhow::Result<BTreeMap<String, ResolvedIssue>> {
+        let custom_field_ids = self.resolve_custom_field_ids()?;
+        let mut issues = BTreeMap::new();
+        for issue_ids in issue_ids.chunks(ISSUES_PAGE_SIZE) {
+            let page = self
+                .jira_client
+                .request_get(format!(
+                    "{}{}",
+                    self.jira_api_root_url.as_str(),
+                    self.create_jira_issues_by_id_endpoint(issue_ids, &custom_field_ids)
+                ))?
+                .error_for_status()?
+                .json::<serde_json::Value>()?;
+            for issue in page["issues"]
+                .as_array()
+                .ok_or(anyhow!("Can't parse 'issues' as array"))?
+            {
+                let issue_id = issue["id"]
+                    .as_str()
+                    .ok_or(anyhow!("Can't parse issue id"))?;
+                issues.insert(
+                    issue_id.to_string(),
+                    ResolvedIssue {
+                        key: issue["key"]
+                            .as_str()
+                            .ok_or_else(|| anyhow!("Missing key of Jira issue {}", issue_id))?
+                            .to_string(),
+                        summary: issue["fields"]["summary"]
+                            .as_str()
+                            .ok_or_else(|| anyhow!("Missing summary of Jira issue {}", issue_id))?
+                            .to_string(),
+                        info: IssueInfo::from_json(
+                            issue,
+                            &custom_field_ids,
+                            self.issue_fields.billing_account_field.as_deref(),
+                        ),
+                    },
+                );
+            }
+        }
+        Ok(issues)
+    }
+}
+
+impl<ClientType> UserWorklogsFetcher<String, ClientType>
+where
+    ClientType: ClientTrait,
+{
+    /// Detects the Jira flavor at `jira_base_url` and talks to the matching REST API version.
+    pub fn detect(jira_base_url: &str, jira_client: ClientType) -> anyhow::Result<Self> {
+        let capabilities = ApiCapabilities::detect(&jira_client, jira_base_url)?;
+        Ok(
+            Self::new(capabilities.api_root_url(jira_base_url), jira_client)
+                .with_capabilities(capabilities),
+        )
     }
 }
 
@@ -57,38 +206,310 @@ where
         Self {
             jira_api_root_url,
             jira_client,
+            timezone: None,
+            time_tracking: None,
+            issue_fields: IssueFieldsConfig::default(),
+            issue_filter: IssueFilterConfig::default(),
+            issue_filter_validated: Cell::new(false),
+            capabilities: None,
+            resolved_users: RefCell::new(BTreeMap::new()),
+        }
+    }
+
+    pub fn with_timezone(mut self, timezone: Tz) -> Self {
+        self.timezone = Some(timezone);
+        self
+    }
+
+    /// Uses the configuration read by `TimeTrackingConfig::fetch` when formatting durations.
+    pub fn with_time_tracking(mut self, time_tracking: TimeTrackingConfig) -> Self {
+        self.time_tracking = Some(time_tracking);
+        self
+    }
+
+    pub fn with_issue_fields(mut self, issue_fields: IssueFieldsConfig) -> Self {
+        self.issue_fields = issue_fields;
+        self
+    }
+
+    /// Uses capabilities detected by `ApiCapabilities::detect` instead of probing
+    /// alternative endpoints on every request.
+    pub fn with_capabilities(mut self, capabilities: ApiCapabilities) -> Self {
+        self.capabilities = Some(capabilities);
+        self
+    }
+
+    pub fn capabilities(&self) -> Option<&ApiCapabilities> {
+        self.capabilities.as_ref()
+    }
+
+    /// Seeds the user cache, e.g. with members returned by `TeamResolver`.
+    pub fn with_known_users(self, users: &[UserIdentity]) -> Self {
+        for user in users {
+            if let Some(user_name) = user.jql_value() {
+                self.resolved_users
+                    .borrow_mut()
+                    .insert(user_name.to_string(), user.clone());
+            }
         }
+        self
+    }
+
+    pub fn with_issue_filter(mut self, issue_filter: IssueFilterConfig) -> Self {
+        self.issue_filter = issue_filter;
+        self.issue_filter_validated.set(false);
+        self
     }
 
     fn create_jira_issues_endpoint(
         &self,
-        user_name: &str,
+        user_names: &[&str],
         start_date: &chrono::NaiveDate,
         end_date: &chrono::NaiveDate,
+        custom_field_ids: &BTreeMap<String, String>,
+        start_at: usize,
+    ) -> String {
+        let jql = JqlQuery::new()
+            .and(JqlClause::ge("worklogDate", *start_date))
+            .and(JqlClause::le("worklogDate", *end_date))
+            .and(JqlClause::in_list(
+                "worklogAuthor",
+                user_names.iter().copied(),
+            ));
+        let jql = self
+            .issue_filter
+            .clauses()
+            .into_iter()
+            .fold(jql, |jql, clause| jql.and(clause));
+        format!(
+            "/search?jql={}&fields={}&startAt={}&maxResults={}",
+            jql.url_encoded(),
+            self.requested_fields(custom_field_ids),
+            start_at,
+            ISSUES_PAGE_SIZE
+        )
+    }
+
+    fn create_jira_issues_by_id_endpoint(
+        &self,
+        issue_ids: &[String],
+        custom_field_ids: &BTreeMap<String, String>,
     ) -> String {
-        let date_in_query_format = "%Y-%m-%d";
+        let jql = JqlQuery::new().and(JqlClause::in_list(
+            "id",
+            issue_ids.iter().map(String::as_str),
+        ));
         format!(
-            "/search?jql=worklogDate%20%3E=%20{}%20and%20worklogDate%20%3C=%20{}%20and%20worklogAuthor%20in%20({})",
-            start_date.format(date_in_query_format),
-            end_date.format(date_in_query_format),
+            "/search?jql={}&fields={}&maxResults={}",
+            jql.url_encoded(),
+            self.requested_fields(custom_field_ids),
+            ISSUES_PAGE_SIZE
+        )
+    }
+
+    fn requested_fields(&self, custom_field_ids: &BTreeMap<String, String>) -> String {
+        std::iter::once("summary")
+            .chain(self.issue_fields.fields.iter().map(String::as_str))
+            .chain(custom_field_ids.values().map(String::as_str))
+            .unique()
+            .join(",")
+    }
+
+    fn create_jira_jql_parse_endpoint(&self) -> String {
+        "/jql/parse?validation=strict".to_string()
+    }
+
+    /// Checks the extra JQL with Jira's parser once. Servers without `/jql/parse`
+    /// (Server/DC) are skipped and report syntax errors on the search itself.
+    fn validate_issue_filter(&self) -> anyhow::Result<()> {
+        let extra_jql = match &self.issue_filter.extra_jql {
+            Some(extra_jql) if !self.issue_filter_validated.get() => extra_jql,
+            _ => return Ok(()),
+        };
+        if self
+            .capabilities
+            .as_ref()
+            .is_some_and(|capabilities| !capabilities.jql_parse)
+        {
+            return Ok(());
+        }
+
+        let parse_request_url = format!(
+            "{}{}",
+            self.jira_api_root_url.as_str(),
+            self.create_jira_jql_parse_endpoint()
+        );
+        let parse_response = self.jira_client.request_post(
+            parse_request_url,
+            &serde_json::json!({ "queries": [extra_jql] }),
+        )?;
+        if parse_response.status() == reqwest::StatusCode::NOT_FOUND {
+            self.issue_filter_validated.set(true);
+            return Ok(());
+        }
+        let parse_body = parse_response
+            .error_for_status()?
+            .json::<serde_json::Value>()?;
+        let errors = parse_body["queries"][0]["errors"]
+            .as_array()
+            .map(|errors| errors.iter().filter_map(|error| error.as_str()).join("; "))
+            .unwrap_or_default();
+        if !errors.is_empty() {
+            return Err(anyhow!("Invalid JQL filter '{}': {}", extra_jql, errors));
+        }
+
+        self.issue_filter_validated.set(true);
+        Ok(())
+    }
+
+    fn create_jira_myself_endpoint(&self) -> String {
+        "/myself".to_string()
+    }
+
+    #[cfg(test)]
+    fn create_jira_user_search_endpoint(&self, parameter: &str, user_name: &str) -> String {
+        super::user_identity::user_search_endpoint(parameter, user_name)
+    }
+
+    /// Resolves the user name, email, display name, key or account id to the canonical Jira
+    /// identity.
+    /// `currentUser()` or an empty name resolves to the authenticated user.
+    fn resolve_user(&self, user_name: &str) -> anyhow::Result<UserIdentity> {
+        if let Some(user) = self.resolved_users.borrow().get(user_name) {
+            return Ok(user.clone());
+        }
+
+        let user = if user_name.is_empty() || user_name == CURRENT_USER {
+            self.fetch_myself()?
+        } else {
+            self.search_user(user_name)?
+        };
+        self.resolved_users
+            .borrow_mut()
+            .insert(user_name.to_string(), user.clone());
+        Ok(user)
+    }
+
+    fn fetch_myself(&self) -> anyhow::Result<UserIdentity> {
+        let myself_request_url = format!(
+            "{}{}",
+            self.jira_api_root_url.as_str(),
+            self.create_jira_myself_endpoint()
+        );
+        let myself_response = self
+            .jira_client
+            .request_get(myself_request_url)?
+            .error_for_status()?;
+        Ok(UserIdentity::from_json(
+            &myself_response.json::<serde_json::Value>()?,
+        ))
+    }
+
+    fn search_user(&self, user_name: &str) -> anyhow::Result<UserIdentity> {
+        search_user(
+            &self.jira_client,
+            self.jira_api_root_url.as_str(),
+            self.capabilities.as_ref(),
             user_name,
         )
     }
 
+    fn create_jira_fields_endpoint(&self) -> String {
+        "/field".to_string()
+    }
+
+    fn resolve_custom_field_ids(&self) -> anyhow::Result<BTreeMap<String, String>> {
+        let custom_field_names = self.issue_fields.custom_field_names();
+        if custom_field_names.is_empty() {
+            return Ok(BTreeMap::new());
+        }
+
+        let fields_request_url = format!(
+            "{}{}",
+            self.jira_api_root_url.as_str(),
+            self.create_jira_fields_endpoint()
+        );
+        let fields_response = self
+            .jira_client
+            .request_get(fields_request_url)?
+            .error_for_status()?;
+        let fields_body = fields_response.json::<serde_json::Value>()?;
+        let fields = fields_body
+            .as_array()
+            .ok_or(anyhow!("Can't parse fields as array"))?;
+
+        custom_field_names
+            .into_iter()
+            .map(|name| {
+                fields
+                    .iter()
+                    .find(|field| field["name"].as_str() == Some(name))
+                    .and_then(|field| field["id"].as_str())
+                    .map(|id| (name.to_string(), id.to_string()))
+                    .ok_or(anyhow!("Can't find Jira field named '{}'", name))
+            })
+            .collect()
+    }
+
     fn fetch_issues(
         &self,
-        user_name: &str,
+        user_names: &[&str],
         start_date: &chrono::NaiveDate,
         end_date: &chrono::NaiveDate,
+        custom_field_ids: &BTreeMap<String, String>,
+        start_at: usize,
     ) -> reqwest::Result<reqwest::blocking::Response> {
         let jira_issues_request_url = format!(
             "{}{}",
             self.jira_api_root_url.as_str(),
-            self.create_jira_issues_endpoint(user_name, &start_date, &end_date)
+            self.create_jira_issues_endpoint(
+                user_names,
+                start_date,
+                end_date,
+                custom_field_ids,
+                start_at
+            )
         );
         self.jira_client.request_get(jira_issues_request_url)
     }
 
+    fn fetch_all_issues(
+        &self,
+        user_names: &[&str],
+        start_date: &chrono::NaiveDate,
+        end_date: &chrono::NaiveDate,
+        custom_field_ids: &BTreeMap<String, String>,
+    ) -> anyhow::Result<Vec<serde_json::Value>> {
+        let mut issues = vec![];
+        loop {
+            let page = self
+                .fetch_issues(
+                    user_names,
+                    start_date,
+                    end_date,
+                    custom_field_ids,
+                    issues.len(),
+                )?
+                .error_for_status()?
+                .json::<serde_json::Value>()?;
+            let page_issues = page["issues"]
+                .as_array()
+                .ok_or(anyhow!("Can't parse 'issues' as array"))?;
+            issues.extend(page_issues.iter().cloned());
+
+            let is_last = page["isLast"].as_bool().unwrap_or_else(|| {
+                page["total"]
+                    .as_u64()
+                    .map_or(page_issues.len() < ISSUES_PAGE_SIZE, |total| {
+                        issues.len() as u64 >= total
+                    })
+            });
+            if page_issues.is_empty() || is_last {
+                return Ok(issues);
+            }
+        }
+    }
+
     fn fetch_issue_worklogs(
         &self,
         issue_url: &str,
@@ -100,22 +521,27 @@ where
     fn process_issue(
         &self,
         issue: &serde_json::Value,
-        user_name: &str,
+        issue_info: &IssueInfo,
+        users: &[UserIdentity],
         start_date: &chrono::NaiveDate,
         end_date: &chrono::NaiveDate,
     ) -> anyhow::Result<Vec<Worklog>> {
-        let worklogs_response = self
-            .fetch_issue_worklogs(issue["self"].as_str().unwrap())?
-            .error_for_status()?;
+        let issue_url = issue["self"]
+            .as_str()
+            .ok_or_else(|| anyhow!("Can't parse 'self' of issue as string"))?;
+        let worklogs_response = self.fetch_issue_worklogs(issue_url)?.error_for_status()?;
         let worklogs_response_body = worklogs_response.json::<serde_json::Value>()?;
 
         let worklogs = worklogs_response_body["worklogs"]
             .as_array()
             .ok_or(anyhow!("Can't parse 'worklogs' as array"))?
-            .into_iter()
-            .filter_map(|worklog| {
-                self.process_worklog(&issue, worklog, user_name, &start_date, &end_date)
+            .iter()
+            .map(|worklog| {
+                self.process_worklog(issue, issue_info, worklog, users, start_date, end_date)
             })
+            .collect::<anyhow::Result<Vec<_>>>()?
+            .into_iter()
+            .flatten()
             .collect();
 
         Ok(worklogs)
@@ -124,31 +550,82 @@ where
     fn process_worklog(
         &self,
         issue: &serde_json::Value,
+        issue_info: &IssueInfo,
         worklog: &serde_json::Value,
-        user_name: &str,
+        users: &[UserIdentity],
         start_date: &chrono::NaiveDate,
         end_date: &chrono::NaiveDate,
-    ) -> Option<Worklog> {
-        let author = worklog["author"]["name"].as_str().unwrap();
-        let worklog_started = worklog["started"].as_str().unwrap();
-        let worklog_started = NaiveDate::parse_from_str(worklog_started, JIRA_TIME_FORMAT).unwrap();
-
-        if self.author_is(author, user_name)
-            && self.date_between(&worklog_started, &start_date, &end_date)
+    ) -> anyhow::Result<Option<Worklog>> {
+        let author = WorklogAuthor::from_json(&worklog["author"]);
+        let worklog_started = worklog["started"]
+            .as_str()
+            .ok_or_else(|| anyhow!("Can't parse 'started' of worklog as string"))?;
+        let worklog_started =
+            DateTime::parse_from_str(worklog_started, JIRA_TIME_FORMAT).map_err(|error| {
+                anyhow!("Can't parse worklog start '{}': {}", worklog_started, error)
+            })?;
+        let worklog_date = match &self.timezone {
+            Some(timezone) => worklog_started.with_timezone(timezone).naive_local().date(),
+            None => worklog_started.naive_local().date(),
+        };
+
+        if users.iter().any(|user| self.author_is(&author, user))
+            && self.date_between(&worklog_date, start_date, end_date)
         {
-            Some(Worklog::new(
-                worklog_started,
-                issue["key"].as_str().unwrap(),
-                issue["fields"]["summary"].as_str().unwrap(),
-                chrono::Duration::seconds(worklog["timeSpentSeconds"].as_u64().unwrap() as i64),
-            ))
+            self.create_worklog(issue, issue_info, worklog, worklog_started)
+                .map(Some)
         } else {
-            None
+            Ok(None)
         }
     }
 
-    fn author_is(&self, author: &str, user_name: &str) -> bool {
-        author == user_name
+    fn create_worklog(
+        &self,
+        issue: &serde_json::Value,
+        issue_info: &IssueInfo,
+        worklog: &serde_json::Value,
+        worklog_started: DateTime<FixedOffset>,
+    ) -> anyhow::Result<Worklog> {
+        let issue_key = issue["key"]
+            .as_str()
+            .ok_or_else(|| anyhow!("Can't parse 'key' of issue as string"))?;
+        let issue_summary = issue["fields"]["summary"]
+            .as_str()
+            .ok_or_else(|| anyhow!("Missing 'summary' field of issue '{}'", issue_key))?;
+        let time_spent = worklog["timeSpentSeconds"]
+            .as_i64()
+            .and_then(checked_seconds)
+            .ok_or_else(|| {
+                anyhow!(
+                    "Can't parse 'timeSpentSeconds' of worklog on '{}'",
+                    issue_key
+                )
+            })?;
+        let mut result = Worklog::new(worklog_started, issue_key, issue_summary, time_spent)
+            .with_author(WorklogAuthor::from_json(&worklog["author"]))
+            .with_issue(issue_info.clone());
+        result.id = worklog["id"].as_str().map(|id| id.to_string());
+        result.issue_id = worklog["issueId"]
+            .as_str()
+            .or_else(|| issue["id"].as_str())
+            .map(|issue_id| issue_id.to_string());
+        result.comment = match &worklog["comment"] {
+            serde_json::Value::String(comment) => Some(comment.clone()),
+            serde_json::Value::Object(_) => Some(adf::to_plain_text(&worklog["comment"])),
+            _ => None,
+        };
+        result.created = Self::parse_jira_time(&worklog["created"]);
+        result.updated = Self::parse_jira_time(&worklog["updated"]);
+        result.visibility = WorklogVisibility::from_json(&worklog["visibility"]);
+        Ok(result)
+    }
+
+    fn parse_jira_time(time: &serde_json::Value) -> Option<DateTime<FixedOffset>> {
+        DateTime::parse_from_str(time.as_str()?, JIRA_TIME_FORMAT).ok()
+    }
+
+    fn author_is(&self, author: &WorklogAuthor, user: &UserIdentity) -> bool {
+        user.matches(author)
     }
 
     fn date_between(
@@ -157,7 +634,7 @@ where
         start_date: &chrono::NaiveDate,
         end_date: &chrono::NaiveDate,
     ) -> bool {
-        date.ge(&start_date) && date.le(&end_date)
+        date.ge(start_date) && date.le(end_date)
     }
 }
 
@@ -165,14 +642,18 @@ where
 mod tests {
     use super::UserWorklogsFetcher;
     use crate::jira::{
-        user_worklogs::UserWorklogs, worklog::Worklog, Client, UserWorklogsFetcherTrait,
+        user_worklogs::UserWorklogs, worklog::Worklog, ApiCapabilities, Client, IssueFieldsConfig,
+        IssueFilterConfig, IssueInfo, IssueResolverTrait, ResolvedIssue, ServerInfo, UserIdentity,
+        UserWorklogsFetcherTrait, WorklogAuthor, WorklogVisibility,
     };
-    use chrono::{Duration, NaiveDate};
+    use chrono::{DateTime, Duration, NaiveDate};
     use lazy_static::lazy_static;
     use mockito;
+    use std::collections::BTreeMap;
 
     mod helpers {
-        use chrono::NaiveDate;
+        use crate::jira::{IssueInfo, Worklog, WorklogAuthor};
+        use chrono::{DateTime, FixedOffset, NaiveDate, TimeZone};
 
         pub fn create_issue_entry(id: &str, server_url: &str) -> String {
             format!(
@@ -191,6 +672,49 @@ mod tests {
             )
         }
 
+        pub fn create_issue_entry_with_parent(
+            id: &str,
+            server_url: &str,
+            parent_key: &str,
+        ) -> String {
+            format!(
+                r###"{{
+                    "id": "{id}",
+                    "self": "{jira_server_url}/issue/{id}",
+                    "key": "{key}",
+                    "fields": {{
+                        "summary": "{summary}",
+                        "parent": {{ "key": "{parent_key}" }}
+                    }}
+                }}"###,
+                id = id,
+                jira_server_url = server_url,
+                key = create_issue_key(id),
+                summary = create_issue_summary(id),
+                parent_key = parent_key,
+            )
+        }
+
+        pub fn create_expected_worklog(
+            started: DateTime<FixedOffset>,
+            issue_id: &str,
+            author_name: &str,
+            time_spent: chrono::Duration,
+        ) -> Worklog {
+            Worklog::new(
+                started,
+                &create_issue_key(issue_id),
+                &create_issue_summary(issue_id),
+                time_spent,
+            )
+            .with_issue_id(issue_id)
+            .with_issue(IssueInfo::default())
+            .with_author(WorklogAuthor {
+                name: Some(author_name.to_string()),
+                ..Default::default()
+            })
+        }
+
         pub fn create_issue_key(id: &str) -> String {
             format!("DUMMY-TASK-KEY-{}", id)
         }
@@ -199,10 +723,28 @@ mod tests {
             format!("ISSUE-SUMMARY-DUMMY-TASK-{}", id)
         }
 
+        pub fn create_started_time(date: &NaiveDate) -> DateTime<FixedOffset> {
+            FixedOffset::east(3 * 3600)
+                .from_local_datetime(&date.and_hms_milli(12, 34, 56, 123))
+                .unwrap()
+        }
+
         pub fn create_worklog_entry(
             author_name: &str,
             started_time: &NaiveDate,
             time_spent: chrono::Duration,
+        ) -> String {
+            create_worklog_entry_started_at(
+                author_name,
+                &create_started_time(started_time),
+                time_spent,
+            )
+        }
+
+        pub fn create_worklog_entry_started_at(
+            author_name: &str,
+            started_time: &DateTime<FixedOffset>,
+            time_spent: chrono::Duration,
         ) -> String {
             format!(
                 r###"{{
@@ -211,9 +753,7 @@ mod tests {
                     "timeSpentSeconds": {time_spent}
                 }}"###,
                 author_name = author_name,
-                started_time = started_time
-                    .and_hms_milli(12, 34, 56, 123)
-                    .format("%Y-%m-%dT%H:%M:%S%.3f+0300"),
+                started_time = started_time.format("%Y-%m-%dT%H:%M:%S%.3f%z"),
                 time_spent = time_spent.num_seconds()
             )
         }
@@ -230,27 +770,35 @@ mod tests {
                 .create()
         }
 
+        pub fn create_user_search_endpoint_mock(user_name: &str) -> mockito::Mock {
+            create_get_endpoint_mock(
+                format!("/user/search?query={}", user_name).as_str(),
+                &format!(r###"[{{"name": "{}"}}]"###, user_name),
+                reqwest::StatusCode::OK.as_u16().into(),
+            )
+        }
+
         pub fn create_jira_issue_worklogs_endpoint(id: &str) -> String {
             format!("/issue/{}/worklog", id)
         }
     } // mod helpers
 
-    const ISSUE_1_ID: &'static str = "111111";
-    const ISSUE_2_ID: &'static str = "222222";
+    const ISSUE_1_ID: &str = "111111";
+    const ISSUE_2_ID: &str = "222222";
 
-    const USER_NAME: &'static str = "dummy_user_name";
-    const ANOTHER_USER_NAME: &'static str = "other_user_name";
+    const USER_NAME: &str = "dummy_user_name";
+    const ANOTHER_USER_NAME: &str = "other_user_name";
 
-    const DUMMY_JIRA_USER: &'static str = "dummy_jira_user";
-    const DUMMY_JIRA_PASSWORD: &'static str = "dummy_jira_password";
+    const DUMMY_JIRA_USER: &str = "dummy_jira_user";
+    const DUMMY_JIRA_PASSWORD: &str = "dummy_jira_password";
 
     lazy_static! {
         static ref DUMMY_TIME_IN_SEC_1: Duration = Duration::seconds(123);
         static ref DUMMY_TIME_IN_SEC_2: Duration = Duration::seconds(360);
         static ref DUMMY_TIME_IN_SEC_3: Duration = Duration::seconds(720);
-        static ref START_DATE: NaiveDate = NaiveDate::from_ymd(2022, 09, 10);
-        static ref END_DATE: NaiveDate = NaiveDate::from_ymd(2022, 09, 17);
-        static ref DATE_IN_SEARCHED_TIME_PERIOD: NaiveDate = NaiveDate::from_ymd(2022, 09, 15);
+        static ref START_DATE: NaiveDate = NaiveDate::from_ymd(2022, 9, 10);
+        static ref END_DATE: NaiveDate = NaiveDate::from_ymd(2022, 9, 17);
+        static ref DATE_IN_SEARCHED_TIME_PERIOD: NaiveDate = NaiveDate::from_ymd(2022, 9, 15);
         static ref DATE_AFTER_END_DATE: NaiveDate = *END_DATE + Duration::days(1);
         static ref DATE_BEFORE_START_DATE: NaiveDate = *START_DATE - Duration::days(1);
     }
@@ -263,59 +811,75 @@ mod tests {
     #[test]
     fn is_author_returns_true_if_values_are_equal() {
         let uut = create_uut();
-        let author = USER_NAME;
-        let expected_author = USER_NAME;
-        assert_eq!(uut.author_is(author, expected_author), true);
+        let author = WorklogAuthor {
+            name: Some(USER_NAME.to_string()),
+            ..Default::default()
+        };
+        let expected_author = UserIdentity {
+            name: Some(USER_NAME.to_string()),
+            ..Default::default()
+        };
+        assert!(uut.author_is(&author, &expected_author));
     }
 
     #[test]
     fn is_author_returns_false_if_values_are_not_equal() {
         let uut = create_uut();
-        let author = USER_NAME;
-        let expected_author = ANOTHER_USER_NAME;
-        assert_eq!(uut.author_is(author, expected_author), false);
+        let author = WorklogAuthor {
+            name: Some(USER_NAME.to_string()),
+            ..Default::default()
+        };
+        let expected_author = UserIdentity {
+            name: Some(ANOTHER_USER_NAME.to_string()),
+            ..Default::default()
+        };
+        assert!(!uut.author_is(&author, &expected_author));
+    }
+
+    #[test]
+    fn is_author_matches_account_id_when_user_name_was_changed() {
+        let uut = create_uut();
+        let author = WorklogAuthor {
+            account_id: Some("557058:0001".to_string()),
+            name: Some(ANOTHER_USER_NAME.to_string()),
+            ..Default::default()
+        };
+        let expected_author = UserIdentity {
+            account_id: Some("557058:0001".to_string()),
+            name: Some(USER_NAME.to_string()),
+            ..Default::default()
+        };
+        assert!(uut.author_is(&author, &expected_author));
     }
 
     #[test]
     fn date_between_returns_true_if_date_is_equal_to_start_date() {
         let uut = create_uut();
-        assert_eq!(
-            uut.date_between(&*START_DATE, &*START_DATE, &*END_DATE),
-            true
-        );
+        assert!(uut.date_between(&START_DATE, &START_DATE, &END_DATE));
     }
 
     #[test]
     fn date_between_returns_true_if_date_between_start_and_end_date() {
         let uut = create_uut();
-        assert_eq!(
-            uut.date_between(&*DATE_IN_SEARCHED_TIME_PERIOD, &*START_DATE, &*END_DATE),
-            true
-        );
+        assert!(uut.date_between(&DATE_IN_SEARCHED_TIME_PERIOD, &START_DATE, &END_DATE));
     }
 
     #[test]
     fn date_between_returns_true_if_date_is_equal_to_end_date() {
         let uut = create_uut();
-        assert_eq!(uut.date_between(&*END_DATE, &*START_DATE, &*END_DATE), true);
+        assert!(uut.date_between(&END_DATE, &START_DATE, &END_DATE));
     }
 
     #[test]
     fn date_between_returns_false_if_date_is_before_start_date() {
         let uut = create_uut();
-        assert_eq!(
-            uut.date_between(&*DATE_BEFORE_START_DATE, &*START_DATE, &*END_DATE),
-            false
-        );
+        assert!(!uut.date_between(&DATE_BEFORE_START_DATE, &START_DATE, &END_DATE));
     }
 
     #[test]
     fn date_between_returns_false_if_date_is_after_end_date() {
         let uut = create_uut();
-        assert_eq!(
-            uut.date_between(&*DATE_AFTER_END_DATE, &*START_DATE, &*END_DATE),
-            false
-        );
+        assert!(!uut.date_between(&DATE_AFTER_END_DATE, &START_DATE, &END_DATE));
     }
 
     #[test]
@@ -324,26 +888,54 @@ mod tests {
 
         let expected_endpoint_regex = r"/search";
         assert_eq!(
-            uut.create_jira_issues_endpoint(USER_NAME, &*START_DATE, &*END_DATE)
-                .matches(expected_endpoint_regex)
-                .count(),
+            uut.create_jira_issues_endpoint(
+                &[USER_NAME],
+                &START_DATE,
+                &END_DATE,
+                &BTreeMap::new(),
+                0
+            )
+            .matches(expected_endpoint_regex)
+            .count(),
             1
         );
     }
 
+    #[test]
+    fn create_jira_issues_endpoint_quotes_and_encodes_user_name() {
+        let uut = create_uut();
+
+        assert!(uut
+            .create_jira_issues_endpoint(
+                &["jan \"kowalski\".żółw"],
+                &START_DATE,
+                &END_DATE,
+                &BTreeMap::new(), 0)
+            .starts_with(
+                "/search?jql=worklogDate+%3E%3D+%222022-09-10%22+AND+worklogDate+%3C%3D+%222022-09-17%22\
+                 +AND+worklogAuthor+IN+%28%22jan+%5C%22kowalski%5C%22.%C5%BC%C3%B3%C5%82w%22%29&fields="
+            ));
+    }
+
     #[test]
     fn fetch_issues_returns_ok_status() {
         let uut = create_uut();
         let dummy_issues_response_body = r###"{"key1": "value1", "key2": [123]}"###;
         let _issues_endpoint_mock = helpers::create_get_endpoint_mock(
-            uut.create_jira_issues_endpoint(USER_NAME, &START_DATE, &END_DATE)
-                .as_str(),
+            uut.create_jira_issues_endpoint(
+                &[USER_NAME],
+                &START_DATE,
+                &END_DATE,
+                &BTreeMap::new(),
+                0,
+            )
+            .as_str(),
             dummy_issues_response_body,
             reqwest::StatusCode::OK.as_u16().into(),
         );
 
         assert!(uut
-            .fetch_issues(USER_NAME, &*START_DATE, &*END_DATE)
+            .fetch_issues(&[USER_NAME], &START_DATE, &END_DATE, &BTreeMap::new(), 0)
             .is_ok());
     }
 
@@ -353,12 +945,20 @@ mod tests {
 
         let dummy_issues_response_body = r###"{"key1": "value1", "key2": [123]}"###;
         let _issues_endpoint_mock = helpers::create_get_endpoint_mock(
-            uut.create_jira_issues_endpoint(USER_NAME, &START_DATE, &END_DATE)
-                .as_str(),
+            uut.create_jira_issues_endpoint(
+                &[USER_NAME],
+                &START_DATE,
+                &END_DATE,
+                &BTreeMap::new(),
+                0,
+            )
+            .as_str(),
             dummy_issues_response_body,
             reqwest::StatusCode::UNAUTHORIZED.as_u16().into(),
         );
 
+        let _user_search_endpoint_mock = helpers::create_user_search_endpoint_mock(USER_NAME);
+
         let result = uut.fetch(USER_NAME, *START_DATE, *END_DATE);
         assert!(result.is_err());
 
@@ -373,12 +973,20 @@ mod tests {
 
         let dummy_issues_response_body = "invalid_json_body";
         let _issues_endpoint_mock = helpers::create_get_endpoint_mock(
-            uut.create_jira_issues_endpoint(USER_NAME, &START_DATE, &END_DATE)
-                .as_str(),
+            uut.create_jira_issues_endpoint(
+                &[USER_NAME],
+                &START_DATE,
+                &END_DATE,
+                &BTreeMap::new(),
+                0,
+            )
+            .as_str(),
             dummy_issues_response_body,
             reqwest::StatusCode::OK.as_u16().into(),
         );
 
+        let _user_search_endpoint_mock = helpers::create_user_search_endpoint_mock(USER_NAME);
+
         let result = uut.fetch(USER_NAME, *START_DATE, *END_DATE);
         assert!(result.is_err());
 
@@ -417,8 +1025,14 @@ mod tests {
         );
 
         let _issues_endpoint_mock = helpers::create_get_endpoint_mock(
-            uut.create_jira_issues_endpoint(USER_NAME, &START_DATE, &END_DATE)
-                .as_str(),
+            uut.create_jira_issues_endpoint(
+                &[USER_NAME],
+                &START_DATE,
+                &END_DATE,
+                &BTreeMap::new(),
+                0,
+            )
+            .as_str(),
             &issues_response_body,
             reqwest::StatusCode::OK.as_u16().into(),
         );
@@ -433,6 +1047,8 @@ mod tests {
             reqwest::StatusCode::OK.as_u16().into(),
         );
 
+        let _user_search_endpoint_mock = helpers::create_user_search_endpoint_mock(USER_NAME);
+
         let result = uut.fetch(USER_NAME, *START_DATE, *END_DATE);
         assert!(result.is_err());
 
@@ -462,8 +1078,14 @@ mod tests {
         );
         let dummy_worklogs_response_body = "invalid_json_body";
         let _issues_endpoint_mock = helpers::create_get_endpoint_mock(
-            uut.create_jira_issues_endpoint(USER_NAME, &START_DATE, &END_DATE)
-                .as_str(),
+            uut.create_jira_issues_endpoint(
+                &[USER_NAME],
+                &START_DATE,
+                &END_DATE,
+                &BTreeMap::new(),
+                0,
+            )
+            .as_str(),
             &issues_response_body,
             reqwest::StatusCode::OK.as_u16().into(),
         );
@@ -474,10 +1096,12 @@ mod tests {
         );
         let _issue_2_worklogs_endpoint_mock = helpers::create_get_endpoint_mock(
             helpers::create_jira_issue_worklogs_endpoint(ISSUE_2_ID).as_str(),
-            &dummy_worklogs_response_body,
+            dummy_worklogs_response_body,
             reqwest::StatusCode::OK.as_u16().into(),
         );
 
+        let _user_search_endpoint_mock = helpers::create_user_search_endpoint_mock(USER_NAME);
+
         let result = uut.fetch(USER_NAME, *START_DATE, *END_DATE);
         assert!(result.is_err());
 
@@ -485,6 +1109,139 @@ mod tests {
         assert!(error.is_decode());
     }
 
+    #[test]
+    fn fetch_returns_error_if_worklog_has_no_time_spent() {
+        let uut = create_uut();
+
+        let issues_response_body = format!(
+            r###"{{"issues": [{}]}}"###,
+            helpers::create_issue_entry(ISSUE_1_ID, &mockito::server_url())
+        );
+        let worklogs_response_body = format!(
+            r###"{{"worklogs": [{{
+                "author": {{ "name": "{}" }},
+                "started": "{}"
+            }}]}}"###,
+            USER_NAME,
+            helpers::create_started_time(&START_DATE).format("%Y-%m-%dT%H:%M:%S%.3f%z")
+        );
+        let _issues_endpoint_mock = helpers::create_get_endpoint_mock(
+            uut.create_jira_issues_endpoint(
+                &[USER_NAME],
+                &START_DATE,
+                &END_DATE,
+                &BTreeMap::new(),
+                0,
+            )
+            .as_str(),
+            &issues_response_body,
+            reqwest::StatusCode::OK.as_u16().into(),
+        );
+        let _worklogs_endpoint_mock = helpers::create_get_endpoint_mock(
+            helpers::create_jira_issue_worklogs_endpoint(ISSUE_1_ID).as_str(),
+            &worklogs_response_body,
+            reqwest::StatusCode::OK.as_u16().into(),
+        );
+        let _user_search_endpoint_mock = helpers::create_user_search_endpoint_mock(USER_NAME);
+
+        let result = uut.fetch(USER_NAME, *START_DATE, *END_DATE);
+
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("Can't parse 'timeSpentSeconds'"));
+    }
+
+    #[test]
+    fn fetch_returns_error_if_worklog_time_spent_is_out_of_range() {
+        let uut = create_uut();
+
+        let issues_response_body = format!(
+            r###"{{"issues": [{}]}}"###,
+            helpers::create_issue_entry(ISSUE_1_ID, &mockito::server_url())
+        );
+        let worklogs_response_body = format!(
+            r###"{{"worklogs": [{{
+                "author": {{ "name": "{}" }},
+                "started": "{}",
+                "timeSpentSeconds": 9223372036854775807
+            }}]}}"###,
+            USER_NAME,
+            helpers::create_started_time(&START_DATE).format("%Y-%m-%dT%H:%M:%S%.3f%z")
+        );
+        let _issues_endpoint_mock = helpers::create_get_endpoint_mock(
+            uut.create_jira_issues_endpoint(
+                &[USER_NAME],
+                &START_DATE,
+                &END_DATE,
+                &BTreeMap::new(),
+                0,
+            )
+            .as_str(),
+            &issues_response_body,
+            reqwest::StatusCode::OK.as_u16().into(),
+        );
+        let _worklogs_endpoint_mock = helpers::create_get_endpoint_mock(
+            helpers::create_jira_issue_worklogs_endpoint(ISSUE_1_ID).as_str(),
+            &worklogs_response_body,
+            reqwest::StatusCode::OK.as_u16().into(),
+        );
+        let _user_search_endpoint_mock = helpers::create_user_search_endpoint_mock(USER_NAME);
+
+        let result = uut.fetch(USER_NAME, *START_DATE, *END_DATE);
+
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("Can't parse 'timeSpentSeconds'"));
+    }
+
+    #[test]
+    fn fetch_returns_error_if_issue_has_no_summary() {
+        let uut = create_uut();
+
+        let issues_response_body = format!(
+            r###"{{"issues": [{{
+                "id": "{id}",
+                "self": "{url}/issue/{id}",
+                "key": "{key}",
+                "fields": {{}}
+            }}]}}"###,
+            id = ISSUE_1_ID,
+            url = mockito::server_url(),
+            key = helpers::create_issue_key(ISSUE_1_ID),
+        );
+        let worklogs_response_body = format!(
+            r###"{{"worklogs": [{}]}}"###,
+            helpers::create_worklog_entry(USER_NAME, &START_DATE, *DUMMY_TIME_IN_SEC_1)
+        );
+        let _issues_endpoint_mock = helpers::create_get_endpoint_mock(
+            uut.create_jira_issues_endpoint(
+                &[USER_NAME],
+                &START_DATE,
+                &END_DATE,
+                &BTreeMap::new(),
+                0,
+            )
+            .as_str(),
+            &issues_response_body,
+            reqwest::StatusCode::OK.as_u16().into(),
+        );
+        let _worklogs_endpoint_mock = helpers::create_get_endpoint_mock(
+            helpers::create_jira_issue_worklogs_endpoint(ISSUE_1_ID).as_str(),
+            &worklogs_response_body,
+            reqwest::StatusCode::OK.as_u16().into(),
+        );
+        let _user_search_endpoint_mock = helpers::create_user_search_endpoint_mock(USER_NAME);
+
+        let result = uut.fetch(USER_NAME, *START_DATE, *END_DATE);
+
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("Missing 'summary' field"));
+    }
+
     #[test]
     fn fetch_returns_correct_user_worklogs() {
         let issues_response_body = format!(
@@ -518,8 +1275,14 @@ mod tests {
         let uut = create_uut();
 
         let _issues_endpoint_mock = helpers::create_get_endpoint_mock(
-            uut.create_jira_issues_endpoint(USER_NAME, &START_DATE, &END_DATE)
-                .as_str(),
+            uut.create_jira_issues_endpoint(
+                &[USER_NAME],
+                &START_DATE,
+                &END_DATE,
+                &BTreeMap::new(),
+                0,
+            )
+            .as_str(),
             &issues_response_body,
             reqwest::StatusCode::OK.as_u16().into(),
         );
@@ -535,6 +1298,8 @@ mod tests {
             reqwest::StatusCode::OK.as_u16().into(),
         );
 
+        let _user_search_endpoint_mock = helpers::create_user_search_endpoint_mock(USER_NAME);
+
         let result = uut.fetch(USER_NAME, *START_DATE, *END_DATE);
         assert!(result.is_ok());
 
@@ -543,22 +1308,22 @@ mod tests {
             *START_DATE,
             *END_DATE,
             vec![
-                Worklog::new(
-                    *START_DATE,
-                    &helpers::create_issue_key(ISSUE_1_ID),
-                    &helpers::create_issue_summary(ISSUE_1_ID),
+                helpers::create_expected_worklog(
+                    helpers::create_started_time(&START_DATE),
+                    ISSUE_1_ID,
+                    USER_NAME,
                     *DUMMY_TIME_IN_SEC_1,
                 ),
-                Worklog::new(
-                    *END_DATE,
-                    &helpers::create_issue_key(ISSUE_1_ID),
-                    &helpers::create_issue_summary(ISSUE_1_ID),
+                helpers::create_expected_worklog(
+                    helpers::create_started_time(&END_DATE),
+                    ISSUE_1_ID,
+                    USER_NAME,
                     *DUMMY_TIME_IN_SEC_2,
                 ),
-                Worklog::new(
-                    *DATE_IN_SEARCHED_TIME_PERIOD,
-                    &helpers::create_issue_key(ISSUE_2_ID),
-                    &helpers::create_issue_summary(ISSUE_2_ID),
+                helpers::create_expected_worklog(
+                    helpers::create_started_time(&DATE_IN_SEARCHED_TIME_PERIOD),
+                    ISSUE_2_ID,
+                    USER_NAME,
                     *DUMMY_TIME_IN_SEC_3,
                 ),
             ],
@@ -566,4 +1331,898 @@ mod tests {
 
         assert_eq!(result.unwrap(), expected_result);
     }
+
+    #[test]
+    fn fetch_returns_worklogs_with_parent_key_when_issue_has_parent() {
+        let parent_key = "DUMMY-EPIC-1";
+        let issues_response_body = format!(
+            r###"{{"dummy_field": "values", "issues": [{}]}}"###,
+            helpers::create_issue_entry_with_parent(ISSUE_1_ID, &mockito::server_url(), parent_key),
+        );
+        let issue_1_worklogs_response_body = format!(
+            r###"{{"dummy_field": "values", "worklogs": [{}]}}"###,
+            helpers::create_worklog_entry(USER_NAME, &START_DATE, *DUMMY_TIME_IN_SEC_1),
+        );
+
+        let uut = create_uut();
+
+        let _issues_endpoint_mock = helpers::create_get_endpoint_mock(
+            uut.create_jira_issues_endpoint(
+                &[USER_NAME],
+                &START_DATE,
+                &END_DATE,
+                &BTreeMap::new(),
+                0,
+            )
+            .as_str(),
+            &issues_response_body,
+            reqwest::StatusCode::OK.as_u16().into(),
+        );
+        let _issue_1_worklogs_endpoint_mock = helpers::create_get_endpoint_mock(
+            helpers::create_jira_issue_worklogs_endpoint(ISSUE_1_ID).as_str(),
+            &issue_1_worklogs_response_body,
+            reqwest::StatusCode::OK.as_u16().into(),
+        );
+
+        let _user_search_endpoint_mock = helpers::create_user_search_endpoint_mock(USER_NAME);
+
+        let result = uut.fetch(USER_NAME, *START_DATE, *END_DATE).unwrap();
+
+        assert_eq!(
+            result.worklogs,
+            vec![helpers::create_expected_worklog(
+                helpers::create_started_time(&START_DATE),
+                ISSUE_1_ID,
+                USER_NAME,
+                *DUMMY_TIME_IN_SEC_1
+            )
+            .with_issue(IssueInfo {
+                parent_key: Some(parent_key.to_string()),
+                ..Default::default()
+            })]
+        );
+    }
+
+    #[test]
+    fn fetch_assigns_worklogs_to_days_in_configured_timezone() {
+        let issues_response_body = format!(
+            r###"{{"dummy_field": "values", "issues": [{}]}}"###,
+            helpers::create_issue_entry(ISSUE_1_ID, &mockito::server_url()),
+        );
+        let started_on_start_date_in_warsaw =
+            DateTime::parse_from_rfc3339("2022-09-09T22:30:00+00:00").unwrap();
+        let started_after_end_date_in_warsaw =
+            DateTime::parse_from_rfc3339("2022-09-17T22:30:00+00:00").unwrap();
+        let issue_1_worklogs_response_body = format!(
+            r###"{{"dummy_field": "values", "worklogs": [{}, {}]}}"###,
+            helpers::create_worklog_entry_started_at(
+                USER_NAME,
+                &started_on_start_date_in_warsaw,
+                *DUMMY_TIME_IN_SEC_1
+            ),
+            helpers::create_worklog_entry_started_at(
+                USER_NAME,
+                &started_after_end_date_in_warsaw,
+                *DUMMY_TIME_IN_SEC_2
+            ),
+        );
+
+        let uut = create_uut().with_timezone(chrono_tz::Europe::Warsaw);
+
+        let _issues_endpoint_mock = helpers::create_get_endpoint_mock(
+            uut.create_jira_issues_endpoint(
+                &[USER_NAME],
+                &START_DATE,
+                &END_DATE,
+                &BTreeMap::new(),
+                0,
+            )
+            .as_str(),
+            &issues_response_body,
+            reqwest::StatusCode::OK.as_u16().into(),
+        );
+        let _issue_1_worklogs_endpoint_mock = helpers::create_get_endpoint_mock(
+            helpers::create_jira_issue_worklogs_endpoint(ISSUE_1_ID).as_str(),
+            &issue_1_worklogs_response_body,
+            reqwest::StatusCode::OK.as_u16().into(),
+        );
+
+        let _user_search_endpoint_mock = helpers::create_user_search_endpoint_mock(USER_NAME);
+
+        let result = uut.fetch(USER_NAME, *START_DATE, *END_DATE).unwrap();
+
+        assert_eq!(
+            result.worklogs,
+            vec![helpers::create_expected_worklog(
+                started_on_start_date_in_warsaw,
+                ISSUE_1_ID,
+                USER_NAME,
+                *DUMMY_TIME_IN_SEC_1
+            )]
+        );
+    }
+
+    #[test]
+    fn fetch_returns_worklogs_with_details() {
+        let issues_response_body = format!(
+            r###"{{"dummy_field": "values", "issues": [{}]}}"###,
+            helpers::create_issue_entry(ISSUE_1_ID, &mockito::server_url()),
+        );
+        let issue_1_worklogs_response_body = format!(
+            r###"{{"dummy_field": "values", "worklogs": [{{
+                "id": "10001",
+                "issueId": "{issue_id}",
+                "author": {{
+                    "accountId": "5b10a2844c20165700ede21g",
+                    "name": "{user_name}",
+                    "displayName": "Dummy User",
+                    "emailAddress": "dummy@example.com"
+                }},
+                "comment": {{
+                    "type": "doc",
+                    "version": 1,
+                    "content": [{{
+                        "type": "paragraph",
+                        "content": [{{ "type": "text", "text": "Code review" }}]
+                    }}]
+                }},
+                "created": "2022-09-10T18:00:00.000+0300",
+                "updated": "2022-09-11T08:30:00.000+0300",
+                "started": "2022-09-10T12:34:56.123+0300",
+                "timeSpentSeconds": 3600,
+                "visibility": {{ "type": "group", "value": "developers" }}
+            }}]}}"###,
+            issue_id = ISSUE_1_ID,
+            user_name = USER_NAME,
+        );
+
+        let uut = create_uut();
+
+        let _issues_endpoint_mock = helpers::create_get_endpoint_mock(
+            uut.create_jira_issues_endpoint(
+                &[USER_NAME],
+                &START_DATE,
+                &END_DATE,
+                &BTreeMap::new(),
+                0,
+            )
+            .as_str(),
+            &issues_response_body,
+            reqwest::StatusCode::OK.as_u16().into(),
+        );
+        let _issue_1_worklogs_endpoint_mock = helpers::create_get_endpoint_mock(
+            helpers::create_jira_issue_worklogs_endpoint(ISSUE_1_ID).as_str(),
+            &issue_1_worklogs_response_body,
+            reqwest::StatusCode::OK.as_u16().into(),
+        );
+
+        let _user_search_endpoint_mock = helpers::create_user_search_endpoint_mock(USER_NAME);
+
+        let result = uut.fetch(USER_NAME, *START_DATE, *END_DATE).unwrap();
+
+        let expected_worklog = Worklog::new(
+            helpers::create_started_time(&START_DATE),
+            &helpers::create_issue_key(ISSUE_1_ID),
+            &helpers::create_issue_summary(ISSUE_1_ID),
+            Duration::hours(1),
+        )
+        .with_id("10001")
+        .with_issue_id(ISSUE_1_ID)
+        .with_issue(IssueInfo::default())
+        .with_comment("Code review")
+        .with_author(WorklogAuthor {
+            account_id: Some("5b10a2844c20165700ede21g".to_string()),
+            key: None,
+            name: Some(USER_NAME.to_string()),
+            display_name: Some("Dummy User".to_string()),
+            email: Some("dummy@example.com".to_string()),
+        })
+        .with_created(DateTime::parse_from_rfc3339("2022-09-10T18:00:00+03:00").unwrap())
+        .with_updated(DateTime::parse_from_rfc3339("2022-09-11T08:30:00+03:00").unwrap())
+        .with_visibility(WorklogVisibility {
+            kind: "group".to_string(),
+            value: "developers".to_string(),
+        });
+        assert_eq!(result.worklogs, vec![expected_worklog]);
+    }
+
+    #[test]
+    fn fetch_returns_worklogs_with_issue_metadata_and_resolved_custom_fields() {
+        let fields_response_body = r###"[
+            {"id": "summary", "name": "Summary", "custom": false},
+            {"id": "customfield_10100", "name": "Billing account", "custom": true}
+        ]"###;
+        let issues_response_body = format!(
+            r###"{{"issues": [{{
+                "id": "{id}",
+                "self": "{jira_server_url}/issue/{id}",
+                "key": "{key}",
+                "fields": {{
+                    "summary": "{summary}",
+                    "project": {{ "key": "DUMMY" }},
+                    "status": {{ "name": "Done", "statusCategory": {{ "key": "done" }} }},
+                    "labels": ["billable"],
+                    "customfield_10100": {{ "value": "ACME" }}
+                }}
+            }}]}}"###,
+            id = ISSUE_1_ID,
+            jira_server_url = mockito::server_url(),
+            key = helpers::create_issue_key(ISSUE_1_ID),
+            summary = helpers::create_issue_summary(ISSUE_1_ID),
+        );
+        let issue_1_worklogs_response_body = format!(
+            r###"{{"dummy_field": "values", "worklogs": [{}]}}"###,
+            helpers::create_worklog_entry(USER_NAME, &START_DATE, *DUMMY_TIME_IN_SEC_1),
+        );
+
+        let uut = create_uut().with_issue_fields(
+            IssueFieldsConfig::default().with_billing_account_field("Billing account"),
+        );
+        let custom_field_ids = BTreeMap::from([(
+            "Billing account".to_string(),
+            "customfield_10100".to_string(),
+        )]);
+
+        let _fields_endpoint_mock = helpers::create_get_endpoint_mock(
+            "/field",
+            fields_response_body,
+            reqwest::StatusCode::OK.as_u16().into(),
+        );
+        let _issues_endpoint_mock = helpers::create_get_endpoint_mock(
+            uut.create_jira_issues_endpoint(
+                &[USER_NAME],
+                &START_DATE,
+                &END_DATE,
+                &custom_field_ids,
+                0,
+            )
+            .as_str(),
+            &issues_response_body,
+            reqwest::StatusCode::OK.as_u16().into(),
+        );
+        let _issue_1_worklogs_endpoint_mock = helpers::create_get_endpoint_mock(
+            helpers::create_jira_issue_worklogs_endpoint(ISSUE_1_ID).as_str(),
+            &issue_1_worklogs_response_body,
+            reqwest::StatusCode::OK.as_u16().into(),
+        );
+
+        let _user_search_endpoint_mock = helpers::create_user_search_endpoint_mock(USER_NAME);
+
+        let result = uut.fetch(USER_NAME, *START_DATE, *END_DATE).unwrap();
+
+        assert_eq!(result.worklogs.len(), 1);
+        assert_eq!(
+            result.worklogs[0].issue,
+            Some(IssueInfo {
+                project_key: Some("DUMMY".to_string()),
+                status: Some("Done".to_string()),
+                status_category: Some("done".to_string()),
+                labels: vec!["billable".to_string()],
+                billing_account: Some("ACME".to_string()),
+                custom_fields: BTreeMap::from([(
+                    "Billing account".to_string(),
+                    serde_json::json!({ "value": "ACME" })
+                )]),
+                ..Default::default()
+            })
+        );
+    }
+
+    #[test]
+    fn fetch_returns_error_if_custom_field_cannot_be_resolved() {
+        let uut = create_uut()
+            .with_issue_fields(IssueFieldsConfig::default().with_custom_field("Unknown field"));
+
+        let _fields_endpoint_mock = helpers::create_get_endpoint_mock(
+            "/field",
+            r###"[{"id": "summary", "name": "Summary", "custom": false}]"###,
+            reqwest::StatusCode::OK.as_u16().into(),
+        );
+
+        let _user_search_endpoint_mock = helpers::create_user_search_endpoint_mock(USER_NAME);
+
+        let result = uut.fetch(USER_NAME, *START_DATE, *END_DATE);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn create_jira_issues_endpoint_requests_configured_and_custom_fields() {
+        let uut = create_uut();
+        let custom_field_ids =
+            BTreeMap::from([("Team".to_string(), "customfield_10300".to_string())]);
+
+        assert!(uut
+            .create_jira_issues_endpoint(&[USER_NAME], &START_DATE, &END_DATE, &custom_field_ids, 0)
+            .ends_with(
+                "&fields=summary,parent,project,issuetype,status,labels,components,assignee,\
+                 customfield_10300&startAt=0&maxResults=50"
+            ));
+    }
+
+    #[test]
+    fn fetch_resolves_email_to_account_id_and_matches_worklogs_by_account_id() {
+        let email = "john.doe@example.com";
+        let account_id = "557058:0001";
+        let users_response_body = format!(
+            r###"[{{"accountId": "{}", "displayName": "John Doe", "emailAddress": "{}"}}]"###,
+            account_id, email
+        );
+        let issues_response_body = format!(
+            r###"{{"issues": [{}]}}"###,
+            helpers::create_issue_entry(ISSUE_1_ID, &mockito::server_url()),
+        );
+        let worklogs_response_body = format!(
+            r###"{{"worklogs": [
+                {{
+                    "author": {{ "accountId": "{account_id}" }},
+                    "started": "2022-09-15T12:34:56.123+0300",
+                    "timeSpentSeconds": 60
+                }},
+                {{
+                    "author": {{ "accountId": "557058:0002" }},
+                    "started": "2022-09-15T12:34:56.123+0300",
+                    "timeSpentSeconds": 120
+                }}
+            ]}}"###,
+            account_id = account_id
+        );
+
+        let uut = create_uut();
+
+        let _user_search_endpoint_mock = helpers::create_get_endpoint_mock(
+            uut.create_jira_user_search_endpoint("query", email)
+                .as_str(),
+            &users_response_body,
+            reqwest::StatusCode::OK.as_u16().into(),
+        )
+        .expect(1);
+        let _issues_endpoint_mock = helpers::create_get_endpoint_mock(
+            uut.create_jira_issues_endpoint(
+                &[account_id],
+                &START_DATE,
+                &END_DATE,
+                &BTreeMap::new(),
+                0,
+            )
+            .as_str(),
+            &issues_response_body,
+            reqwest::StatusCode::OK.as_u16().into(),
+        );
+        let _issue_1_worklogs_endpoint_mock = helpers::create_get_endpoint_mock(
+            helpers::create_jira_issue_worklogs_endpoint(ISSUE_1_ID).as_str(),
+            &worklogs_response_body,
+            reqwest::StatusCode::OK.as_u16().into(),
+        );
+
+        let result = uut.fetch(email, *START_DATE, *END_DATE).unwrap();
+        assert_eq!(result.worklogs.len(), 1);
+        assert_eq!(
+            result.worklogs[0].author.as_ref().unwrap().account_id,
+            Some(account_id.to_string())
+        );
+
+        // The resolved identity is cached between fetches.
+        assert!(uut.fetch(email, *START_DATE, *END_DATE).is_ok());
+        _user_search_endpoint_mock.assert();
+    }
+
+    #[test]
+    fn fetch_resolves_current_user_via_myself_endpoint() {
+        let uut = create_uut();
+
+        let _myself_endpoint_mock = helpers::create_get_endpoint_mock(
+            "/myself",
+            &format!(r###"{{"key": "JIRAUSER1", "name": "{}"}}"###, USER_NAME),
+            reqwest::StatusCode::OK.as_u16().into(),
+        );
+
+        assert_eq!(
+            uut.resolve_user("currentUser()").unwrap(),
+            UserIdentity {
+                key: Some("JIRAUSER1".to_string()),
+                name: Some(USER_NAME.to_string()),
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn resolve_user_falls_back_to_username_search_and_fails_for_unknown_user() {
+        let uut = create_uut();
+
+        let _query_search_endpoint_mock = helpers::create_get_endpoint_mock(
+            uut.create_jira_user_search_endpoint("query", USER_NAME)
+                .as_str(),
+            r###"{"errorMessages": ["The username query parameter was not provided"]}"###,
+            reqwest::StatusCode::BAD_REQUEST.as_u16().into(),
+        );
+        let _username_search_endpoint_mock = helpers::create_get_endpoint_mock(
+            uut.create_jira_user_search_endpoint("username", USER_NAME)
+                .as_str(),
+            "[]",
+            reqwest::StatusCode::OK.as_u16().into(),
+        );
+
+        assert!(uut.resolve_user(USER_NAME).is_err());
+    }
+
+    #[test]
+    fn create_jira_issues_endpoint_combines_issue_filter_with_worklog_clauses() {
+        let uut = create_uut().with_issue_filter(
+            IssueFilterConfig::default()
+                .with_included_projects(&["ABC", "DEF"])
+                .with_excluded_projects(&["Internal"])
+                .with_extra_jql("labels = billable OR type = Bug"),
+        );
+
+        let endpoint = uut.create_jira_issues_endpoint(
+            &[USER_NAME],
+            &START_DATE,
+            &END_DATE,
+            &BTreeMap::new(),
+            0,
+        );
+        let jql: String =
+            form_urlencoded::parse(endpoint.trim_start_matches("/search?").as_bytes())
+                .find(|(key, _)| key == "jql")
+                .unwrap()
+                .1
+                .into_owned();
+        assert_eq!(
+            jql,
+            "worklogDate >= \"2022-09-10\" AND worklogDate <= \"2022-09-17\" \
+             AND worklogAuthor IN (\"dummy_user_name\") AND project IN (\"ABC\", \"DEF\") \
+             AND project NOT IN (\"Internal\") AND (labels = billable OR type = Bug)"
+        );
+    }
+
+    #[test]
+    fn fetch_returns_error_if_issue_filter_is_rejected_by_jql_parser() {
+        let uut = create_uut()
+            .with_issue_filter(IssueFilterConfig::default().with_extra_jql("project = = ABC"));
+
+        let _jql_parse_endpoint_mock = mockito::mock("POST", "/jql/parse?validation=strict")
+            .match_body(mockito::Matcher::Json(
+                serde_json::json!({ "queries": ["project = = ABC"] }),
+            ))
+            .with_status(reqwest::StatusCode::OK.as_u16().into())
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .with_body(
+                r###"{"queries": [{"query": "project = = ABC", "errors": ["Error in the JQL Query"]}]}"###,
+            )
+            .create();
+        let _user_search_endpoint_mock = helpers::create_user_search_endpoint_mock(USER_NAME);
+
+        let result = uut.fetch(USER_NAME, *START_DATE, *END_DATE);
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("Error in the JQL Query"));
+    }
+
+    #[test]
+    fn validate_issue_filter_skips_validation_when_jql_parser_is_not_available() {
+        let uut = create_uut()
+            .with_issue_filter(IssueFilterConfig::default().with_extra_jql("project = ABC"));
+
+        let _jql_parse_endpoint_mock = mockito::mock("POST", "/jql/parse?validation=strict")
+            .with_status(reqwest::StatusCode::NOT_FOUND.as_u16().into())
+            .expect(1)
+            .create();
+
+        assert!(uut.validate_issue_filter().is_ok());
+        assert!(uut.validate_issue_filter().is_ok());
+        _jql_parse_endpoint_mock.assert();
+    }
+
+    #[test]
+    fn fetch_team_searches_once_and_partitions_worklogs_by_user() {
+        let issues_response_body = format!(
+            r###"{{"issues": [{}]}}"###,
+            helpers::create_issue_entry(ISSUE_1_ID, &mockito::server_url()),
+        );
+        let issue_1_worklogs_response_body = format!(
+            r###"{{"worklogs": [{}, {}, {}]}}"###,
+            helpers::create_worklog_entry(USER_NAME, &START_DATE, *DUMMY_TIME_IN_SEC_1),
+            helpers::create_worklog_entry(ANOTHER_USER_NAME, &END_DATE, *DUMMY_TIME_IN_SEC_2),
+            helpers::create_worklog_entry("third_user_name", &END_DATE, *DUMMY_TIME_IN_SEC_3),
+        );
+
+        let uut = create_uut();
+
+        let _user_search_endpoint_mock = helpers::create_user_search_endpoint_mock(USER_NAME);
+        let _another_user_search_endpoint_mock =
+            helpers::create_user_search_endpoint_mock(ANOTHER_USER_NAME);
+        let _issues_endpoint_mock = helpers::create_get_endpoint_mock(
+            uut.create_jira_issues_endpoint(
+                &[USER_NAME, ANOTHER_USER_NAME],
+                &START_DATE,
+                &END_DATE,
+                &BTreeMap::new(),
+                0,
+            )
+            .as_str(),
+            &issues_response_body,
+            reqwest::StatusCode::OK.as_u16().into(),
+        )
+        .expect(1);
+        let _issue_1_worklogs_endpoint_mock = helpers::create_get_endpoint_mock(
+            helpers::create_jira_issue_worklogs_endpoint(ISSUE_1_ID).as_str(),
+            &issue_1_worklogs_response_body,
+            reqwest::StatusCode::OK.as_u16().into(),
+        )
+        .expect(1);
+
+        let result = uut
+            .fetch_team(
+                &[USER_NAME.to_string(), ANOTHER_USER_NAME.to_string()],
+                *START_DATE,
+                *END_DATE,
+            )
+            .unwrap();
+
+        assert_eq!(
+            result,
+            BTreeMap::from([
+                (
+                    USER_NAME.to_string(),
+                    UserWorklogs::new(
+                        USER_NAME,
+                        *START_DATE,
+                        *END_DATE,
+                        vec![helpers::create_expected_worklog(
+                            helpers::create_started_time(&START_DATE),
+                            ISSUE_1_ID,
+                            USER_NAME,
+                            *DUMMY_TIME_IN_SEC_1,
+                        )],
+                    )
+                ),
+                (
+                    ANOTHER_USER_NAME.to_string(),
+                    UserWorklogs::new(
+                        ANOTHER_USER_NAME,
+                        *START_DATE,
+                        *END_DATE,
+                        vec![helpers::create_expected_worklog(
+                            helpers::create_started_time(&END_DATE),
+                            ISSUE_1_ID,
+                            ANOTHER_USER_NAME,
+                            *DUMMY_TIME_IN_SEC_2,
+                        )],
+                    )
+                ),
+            ])
+        );
+        _issues_endpoint_mock.assert();
+        _issue_1_worklogs_endpoint_mock.assert();
+    }
+
+    #[test]
+    fn fetch_team_pages_through_search_results() {
+        let first_page_body = format!(
+            r###"{{"startAt": 0, "maxResults": 1, "total": 2, "issues": [{}]}}"###,
+            helpers::create_issue_entry(ISSUE_1_ID, &mockito::server_url()),
+        );
+        let second_page_body = format!(
+            r###"{{"startAt": 1, "maxResults": 1, "total": 2, "issues": [{}]}}"###,
+            helpers::create_issue_entry(ISSUE_2_ID, &mockito::server_url()),
+        );
+        let issue_1_worklogs_response_body = format!(
+            r###"{{"worklogs": [{}]}}"###,
+            helpers::create_worklog_entry(USER_NAME, &START_DATE, *DUMMY_TIME_IN_SEC_1),
+        );
+        let issue_2_worklogs_response_body = format!(
+            r###"{{"worklogs": [{}]}}"###,
+            helpers::create_worklog_entry(USER_NAME, &END_DATE, *DUMMY_TIME_IN_SEC_2),
+        );
+
+        let uut = create_uut();
+
+        let _user_search_endpoint_mock = helpers::create_user_search_endpoint_mock(USER_NAME);
+        let _first_page_mock = helpers::create_get_endpoint_mock(
+            uut.create_jira_issues_endpoint(
+                &[USER_NAME],
+                &START_DATE,
+                &END_DATE,
+                &BTreeMap::new(),
+                0,
+            )
+            .as_str(),
+            &first_page_body,
+            reqwest::StatusCode::OK.as_u16().into(),
+        )
+        .expect(1);
+        let _second_page_mock = helpers::create_get_endpoint_mock(
+            uut.create_jira_issues_endpoint(
+                &[USER_NAME],
+                &START_DATE,
+                &END_DATE,
+                &BTreeMap::new(),
+                1,
+            )
+            .as_str(),
+            &second_page_body,
+            reqwest::StatusCode::OK.as_u16().into(),
+        )
+        .expect(1);
+        let _issue_1_worklogs_endpoint_mock = helpers::create_get_endpoint_mock(
+            helpers::create_jira_issue_worklogs_endpoint(ISSUE_1_ID).as_str(),
+            &issue_1_worklogs_response_body,
+            reqwest::StatusCode::OK.as_u16().into(),
+        );
+        let _issue_2_worklogs_endpoint_mock = helpers::create_get_endpoint_mock(
+            helpers::create_jira_issue_worklogs_endpoint(ISSUE_2_ID).as_str(),
+            &issue_2_worklogs_response_body,
+            reqwest::StatusCode::OK.as_u16().into(),
+        );
+
+        let result = uut
+            .fetch_team(&[USER_NAME.to_string()], *START_DATE, *END_DATE)
+            .unwrap();
+
+        assert_eq!(
+            result[USER_NAME],
+            UserWorklogs::new(
+                USER_NAME,
+                *START_DATE,
+                *END_DATE,
+                vec![
+                    helpers::create_expected_worklog(
+                        helpers::create_started_time(&START_DATE),
+                        ISSUE_1_ID,
+                        USER_NAME,
+                        *DUMMY_TIME_IN_SEC_1,
+                    ),
+                    helpers::create_expected_worklog(
+                        helpers::create_started_time(&END_DATE),
+                        ISSUE_2_ID,
+                        USER_NAME,
+                        *DUMMY_TIME_IN_SEC_2,
+                    ),
+                ],
+            )
+        );
+        _first_page_mock.assert();
+        _second_page_mock.assert();
+    }
+
+    #[test]
+    fn fetch_team_pages_until_last_or_empty_page_when_total_is_missing() {
+        let first_page_body = format!(
+            r###"{{"isLast": false, "issues": [{}]}}"###,
+            helpers::create_issue_entry(ISSUE_1_ID, &mockito::server_url()),
+        );
+        let issue_1_worklogs_response_body = format!(
+            r###"{{"worklogs": [{}]}}"###,
+            helpers::create_worklog_entry(USER_NAME, &START_DATE, *DUMMY_TIME_IN_SEC_1),
+        );
+
+        let uut = create_uut();
+
+        let _user_search_endpoint_mock = helpers::create_user_search_endpoint_mock(USER_NAME);
+        let _first_page_mock = helpers::create_get_endpoint_mock(
+            uut.create_jira_issues_endpoint(
+                &[USER_NAME],
+                &START_DATE,
+                &END_DATE,
+                &BTreeMap::new(),
+                0,
+            )
+            .as_str(),
+            &first_page_body,
+            reqwest::StatusCode::OK.as_u16().into(),
+        )
+        .expect(1);
+        let _second_page_mock = helpers::create_get_endpoint_mock(
+            uut.create_jira_issues_endpoint(
+                &[USER_NAME],
+                &START_DATE,
+                &END_DATE,
+                &BTreeMap::new(),
+                1,
+            )
+            .as_str(),
+            r###"{"issues": []}"###,
+            reqwest::StatusCode::OK.as_u16().into(),
+        )
+        .expect(1);
+        let _issue_1_worklogs_endpoint_mock = helpers::create_get_endpoint_mock(
+            helpers::create_jira_issue_worklogs_endpoint(ISSUE_1_ID).as_str(),
+            &issue_1_worklogs_response_body,
+            reqwest::StatusCode::OK.as_u16().into(),
+        );
+
+        let result = uut
+            .fetch_team(&[USER_NAME.to_string()], *START_DATE, *END_DATE)
+            .unwrap();
+
+        assert_eq!(result[USER_NAME].worklogs.len(), 1);
+        _first_page_mock.assert();
+        _second_page_mock.assert();
+    }
+
+    #[test]
+    fn fetch_team_returns_empty_worklogs_for_users_without_any() {
+        let issues_response_body = format!(
+            r###"{{"isLast": true, "issues": [{}]}}"###,
+            helpers::create_issue_entry(ISSUE_1_ID, &mockito::server_url()),
+        );
+        let issue_1_worklogs_response_body = format!(
+            r###"{{"worklogs": [{}]}}"###,
+            helpers::create_worklog_entry(USER_NAME, &START_DATE, *DUMMY_TIME_IN_SEC_1),
+        );
+
+        let uut = create_uut();
+
+        let _user_search_endpoint_mock = helpers::create_user_search_endpoint_mock(USER_NAME);
+        let _another_user_search_endpoint_mock =
+            helpers::create_user_search_endpoint_mock(ANOTHER_USER_NAME);
+        let _issues_endpoint_mock = helpers::create_get_endpoint_mock(
+            uut.create_jira_issues_endpoint(
+                &[USER_NAME, ANOTHER_USER_NAME],
+                &START_DATE,
+                &END_DATE,
+                &BTreeMap::new(),
+                0,
+            )
+            .as_str(),
+            &issues_response_body,
+            reqwest::StatusCode::OK.as_u16().into(),
+        );
+        let _issue_1_worklogs_endpoint_mock = helpers::create_get_endpoint_mock(
+            helpers::create_jira_issue_worklogs_endpoint(ISSUE_1_ID).as_str(),
+            &issue_1_worklogs_response_body,
+            reqwest::StatusCode::OK.as_u16().into(),
+        );
+
+        let result = uut
+            .fetch_team(
+                &[USER_NAME.to_string(), ANOTHER_USER_NAME.to_string()],
+                *START_DATE,
+                *END_DATE,
+            )
+            .unwrap();
+
+        assert_eq!(result[USER_NAME].worklogs.len(), 1);
+        assert_eq!(
+            result[ANOTHER_USER_NAME],
+            UserWorklogs::new(ANOTHER_USER_NAME, *START_DATE, *END_DATE, vec![])
+        );
+    }
+
+    #[test]
+    fn detect_uses_rest_api_version_of_detected_jira() {
+        let _server_info_mock = helpers::create_get_endpoint_mock(
+            "/rest/api/2/serverInfo",
+            r###"{"version": "1001.0.0", "deploymentType": "Cloud"}"###,
+            reqwest::StatusCode::OK.as_u16().into(),
+        );
+
+        let uut = UserWorklogsFetcher::detect(
+            &mockito::server_url(),
+            Client::new(DUMMY_JIRA_USER, DUMMY_JIRA_PASSWORD),
+        )
+        .unwrap();
+
+        assert_eq!(
+            uut.jira_api_root_url,
+            format!("{}/rest/api/3", mockito::server_url())
+        );
+        assert!(uut
+            .capabilities()
+            .is_some_and(|capabilities| capabilities.account_ids));
+    }
+
+    #[test]
+    fn resolve_user_uses_known_users_without_searching() {
+        let user = UserIdentity {
+            account_id: Some("557058:0001".to_string()),
+            ..Default::default()
+        };
+        let uut = create_uut().with_known_users(std::slice::from_ref(&user));
+
+        let _user_search_endpoint_mock = mockito::mock("GET", mockito::Matcher::Any)
+            .with_status(500)
+            .expect(0)
+            .create();
+
+        assert_eq!(uut.resolve_user("557058:0001").unwrap(), user);
+        _user_search_endpoint_mock.assert();
+    }
+
+    #[test]
+    fn resolve_user_searches_by_username_on_detected_server() {
+        let server_info = ServerInfo::from_json(&serde_json::json!({
+            "version": "8.20.10",
+            "deploymentType": "Server"
+        }))
+        .unwrap();
+        let uut = create_uut().with_capabilities(ApiCapabilities::from_server_info(server_info));
+
+        let _query_search_endpoint_mock = mockito::mock(
+            "GET",
+            uut.create_jira_user_search_endpoint("query", USER_NAME)
+                .as_str(),
+        )
+        .expect(0)
+        .create();
+        let _username_search_endpoint_mock = helpers::create_get_endpoint_mock(
+            uut.create_jira_user_search_endpoint("username", USER_NAME)
+                .as_str(),
+            &format!(r###"[{{"key": "JIRAUSER1", "name": "{}"}}]"###, USER_NAME),
+            reqwest::StatusCode::OK.as_u16().into(),
+        );
+
+        assert_eq!(
+            uut.resolve_user(USER_NAME).unwrap().key,
+            Some("JIRAUSER1".to_string())
+        );
+        _query_search_endpoint_mock.assert();
+    }
+
+    #[test]
+    fn resolve_user_accepts_display_name_and_reports_ambiguous_ones() {
+        let uut = create_uut();
+
+        let _unique_search_endpoint_mock = helpers::create_get_endpoint_mock(
+            uut.create_jira_user_search_endpoint("query", "John Doe")
+                .as_str(),
+            r###"[
+                {"accountId": "557058:0001", "displayName": "John Doe"},
+                {"accountId": "557058:0002", "displayName": "John Doering"}
+            ]"###,
+            reqwest::StatusCode::OK.as_u16().into(),
+        );
+        let _ambiguous_search_endpoint_mock = helpers::create_get_endpoint_mock(
+            uut.create_jira_user_search_endpoint("query", "Jane Doe")
+                .as_str(),
+            r###"[
+                {"accountId": "557058:0003", "displayName": "Jane Doe"},
+                {"accountId": "557058:0004", "displayName": "Jane Doe"}
+            ]"###,
+            reqwest::StatusCode::OK.as_u16().into(),
+        );
+
+        assert_eq!(
+            uut.resolve_user("John Doe").unwrap().account_id,
+            Some("557058:0001".to_string())
+        );
+        assert!(uut.resolve_user("Jane Doe").is_err());
+    }
+
+    #[test]
+    fn resolve_issues_looks_up_keys_summaries_and_fields_by_id() {
+        let uut = create_uut();
+
+        let _issues_endpoint_mock = helpers::create_get_endpoint_mock(
+            uut.create_jira_issues_by_id_endpoint(
+                &["10001".to_string(), "10002".to_string()],
+                &BTreeMap::new(),
+            )
+            .as_str(),
+            r###"{"issues": [{
+                "id": "10001",
+                "key": "ABC-1",
+                "fields": { "summary": "Planning", "project": { "key": "ABC" } }
+            }]}"###,
+            reqwest::StatusCode::OK.as_u16().into(),
+        );
+
+        assert_eq!(
+            uut.resolve_issues(&["10001".to_string(), "10002".to_string()])
+                .unwrap(),
+            BTreeMap::from([(
+                "10001".to_string(),
+                ResolvedIssue {
+                    key: "ABC-1".to_string(),
+                    summary: "Planning".to_string(),
+                    info: IssueInfo {
+                        project_key: Some("ABC".to_string()),
+                        ..Default::default()
+                    },
+                }
+            )])
+        );
+    }
 }
@@ -1,6 +1,9 @@
-use super::user_worklogs::UserWorklogs;
+use super::{duration::TimeTrackingConfig, user_worklogs::UserWorklogs};
+use crate::sources::SourceFailure;
 use anyhow::Result;
 use chrono::NaiveDate;
+use chrono_tz::Tz;
+use std::collections::BTreeMap;
 
 #[cfg(test)]
 use mockall::{automock, predicate::*};
@@ -13,4 +16,37 @@ pub trait UserWorklogsFetcherTrait {
         start_date: NaiveDate,
         end_date: NaiveDate,
     ) -> Result<UserWorklogs>;
+
+    /// Timezone the fetched worklogs are assigned to days in, and the one summaries and
+    /// policies use for their dates. `None` keeps the offsets reported by the source.
+    fn timezone(&self) -> Option<Tz> {
+        None
+    }
+
+    /// Working day and week of the source, used to format durations in Jira notation.
+    fn time_tracking(&self) -> Option<TimeTrackingConfig> {
+        None
+    }
+
+    /// Sources that failed during the last `fetch` or `fetch_team` while the others
+    /// succeeded; only fetchers merging several sources report any.
+    fn source_failures(&self) -> Vec<SourceFailure> {
+        vec![]
+    }
+
+    /// Fetches worklogs of several users, keyed by the given user names.
+    fn fetch_team(
+        &self,
+        user_names: &[String],
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> Result<BTreeMap<String, UserWorklogs>> {
+        user_names
+            .iter()
+            .map(|user_name| {
+                self.fetch(user_name, start_date, end_date)
+                    .map(|user_worklogs| (user_name.clone(), user_worklogs))
+            })
+            .collect()
+    }
 }
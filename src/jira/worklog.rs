@@ -1,40 +1,281 @@
-use std::fmt::Display;
+use super::{
+    duration::format_neutral, issue_info::IssueInfo, worklog_author::WorklogAuthor,
+    worklog_visibility::WorklogVisibility,
+};
+use chrono::{DateTime, FixedOffset, NaiveDate};
+use chrono_tz::Tz;
+use std::{collections::BTreeMap, fmt::Display};
+
+/// Matches Jira issue keys such as `ABC-123` in free text.
+pub const DEFAULT_ISSUE_KEY_PATTERN: &str = r"\b[A-Z][A-Z0-9_]+-\d+\b";
+
+/// Format of worklog timestamps in Jira's REST API, e.g. `2022-09-15T09:00:00.000+0200`.
+pub(crate) const JIRA_TIME_FORMAT: &str = "%Y-%m-%dT%H:%M:%S%.3f%z";
+
+/// Metadata key with the id of the Jira worklog an entry of another system (e.g. Tempo) mirrors.
+pub const JIRA_WORKLOG_ID_METADATA_KEY: &str = "jira.worklogId";
 
 #[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Worklog {
-    pub data: chrono::NaiveDate,
+    pub id: Option<String>,
+    pub issue_id: Option<String>,
+    pub started: DateTime<FixedOffset>,
     pub issue_key: String,
     pub issue_summary: String,
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            rename = "time_spent_seconds",
+            with = "crate::serialization::duration_seconds"
+        )
+    )]
     pub time_spent: chrono::Duration,
+    pub issue: Option<IssueInfo>,
+    pub comment: Option<String>,
+    pub author: Option<WorklogAuthor>,
+    pub created: Option<DateTime<FixedOffset>>,
+    pub updated: Option<DateTime<FixedOffset>>,
+    pub visibility: Option<WorklogVisibility>,
+    /// Source specific attributes, e.g. Tempo account or work attributes.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub metadata: BTreeMap<String, String>,
 }
 
 impl Worklog {
     pub fn new(
-        data: chrono::NaiveDate,
+        started: DateTime<FixedOffset>,
         issue_key: &str,
         issue_summary: &str,
         time_spent: chrono::Duration,
     ) -> Self {
         Self {
-            data: data,
+            id: None,
+            issue_id: None,
+            started,
             issue_key: issue_key.to_string(),
             issue_summary: issue_summary.to_string(),
-            time_spent: time_spent,
+            time_spent,
+            issue: None,
+            comment: None,
+            author: None,
+            created: None,
+            updated: None,
+            visibility: None,
+            metadata: BTreeMap::new(),
         }
     }
 
+    pub fn with_id(mut self, id: &str) -> Self {
+        self.id = Some(id.to_string());
+        self
+    }
+
+    pub fn with_issue_id(mut self, issue_id: &str) -> Self {
+        self.issue_id = Some(issue_id.to_string());
+        self
+    }
+
+    pub fn with_comment(mut self, comment: &str) -> Self {
+        self.comment = Some(comment.to_string());
+        self
+    }
+
+    pub fn with_author(mut self, author: WorklogAuthor) -> Self {
+        self.author = Some(author);
+        self
+    }
+
+    pub fn with_created(mut self, created: DateTime<FixedOffset>) -> Self {
+        self.created = Some(created);
+        self
+    }
+
+    pub fn with_updated(mut self, updated: DateTime<FixedOffset>) -> Self {
+        self.updated = Some(updated);
+        self
+    }
+
+    pub fn with_visibility(mut self, visibility: WorklogVisibility) -> Self {
+        self.visibility = Some(visibility);
+        self
+    }
+
+    pub fn with_metadata(mut self, key: &str, value: &str) -> Self {
+        self.metadata.insert(key.to_string(), value.to_string());
+        self
+    }
+
+    pub fn with_issue(mut self, issue: IssueInfo) -> Self {
+        self.issue = Some(issue);
+        self
+    }
+
+    pub fn with_parent_key(mut self, parent_key: &str) -> Self {
+        self.issue.get_or_insert_with(IssueInfo::default).parent_key = Some(parent_key.to_string());
+        self
+    }
+
+    pub fn date(&self) -> NaiveDate {
+        self.started.naive_local().date()
+    }
+
+    pub fn date_in(&self, timezone: &Tz) -> NaiveDate {
+        self.started.with_timezone(timezone).naive_local().date()
+    }
+
+    pub fn project_key(&self) -> &str {
+        match self
+            .issue
+            .as_ref()
+            .and_then(|issue| issue.project_key.as_deref())
+        {
+            Some(project_key) => project_key,
+            None => self
+                .issue_key
+                .rsplit_once('-')
+                .map_or(self.issue_key.as_str(), |(project_key, _)| project_key),
+        }
+    }
+
+    pub fn parent_key(&self) -> Option<&str> {
+        self.issue
+            .as_ref()
+            .and_then(|issue| issue.parent_key.as_deref())
+    }
+
     // fn time_in_hours(&self) -> u64
 }
 
 impl Display for Worklog {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let seconds = self.time_spent.num_seconds() % 60;
-        let minutes = self.time_spent.num_minutes() % 60;
-        let hours = self.time_spent.num_hours();
+        if let Some(id) = &self.id {
+            write!(f, "Worklog id: {}, ", id)?;
+        }
+        write!(f, "Issue key: {}, ", self.issue_key)?;
+        if let Some(issue_id) = &self.issue_id {
+            write!(f, "Issue id: {}, ", issue_id)?;
+        }
         write!(
             f,
-            "Issue key: {}, Summary: {}, Logger time: {}h{}m{}s",
-            self.issue_key, self.issue_summary, hours, minutes, seconds
+            "Summary: {}, Started: {}, Logger time: {}",
+            self.issue_summary,
+            self.started.to_rfc3339(),
+            format_neutral(self.time_spent)
+        )?;
+        if let Some(display_name) = self
+            .author
+            .as_ref()
+            .and_then(|author| author.display_name.as_ref())
+        {
+            write!(f, ", Author: {}", display_name)?;
+        }
+        if let Some(created) = &self.created {
+            write!(f, ", Created: {}", created.to_rfc3339())?;
+        }
+        if let Some(updated) = &self.updated {
+            write!(f, ", Updated: {}", updated.to_rfc3339())?;
+        }
+        if let Some(comment) = &self.comment {
+            write!(f, ", Comment: {}", comment)?;
+        }
+        if let Some(visibility) = &self.visibility {
+            write!(f, ", Visible to {}: {}", visibility.kind, visibility.value)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn create_worklog(started: &str) -> Worklog {
+        Worklog::new(
+            DateTime::parse_from_rfc3339(started).unwrap(),
+            "PROJ-123",
+            "summary",
+            Duration::hours(1),
         )
     }
+
+    #[test]
+    fn project_key_returns_issue_key_prefix() {
+        let mut worklog = create_worklog("2022-09-15T12:00:00+00:00");
+        assert_eq!(worklog.project_key(), "PROJ");
+
+        worklog.issue_key = "NOPROJECT".to_string();
+        assert_eq!(worklog.project_key(), "NOPROJECT");
+    }
+
+    #[test]
+    fn project_key_prefers_project_from_issue_info() {
+        let worklog = create_worklog("2022-09-15T12:00:00+00:00").with_issue(IssueInfo {
+            project_key: Some("OTHER".to_string()),
+            ..Default::default()
+        });
+        assert_eq!(worklog.project_key(), "OTHER");
+    }
+
+    #[test]
+    fn date_returns_date_in_offset_of_started_time() {
+        let worklog = create_worklog("2022-09-15T00:30:00+02:00");
+        assert_eq!(worklog.date(), NaiveDate::from_ymd(2022, 9, 15));
+    }
+
+    #[test]
+    fn date_in_returns_date_in_given_timezone() {
+        let worklog = create_worklog("2022-09-15T00:30:00+02:00");
+        assert_eq!(
+            worklog.date_in(&chrono_tz::UTC),
+            NaiveDate::from_ymd(2022, 9, 14)
+        );
+        assert_eq!(
+            worklog.date_in(&chrono_tz::Europe::Warsaw),
+            NaiveDate::from_ymd(2022, 9, 15)
+        );
+    }
+
+    #[test]
+    fn date_in_respects_daylight_saving_time_transitions() {
+        // Warsaw switches from CEST (+02:00) to CET (+01:00) on 2022-10-30.
+        let after_transition = create_worklog("2022-10-30T22:30:00+00:00");
+        assert_eq!(
+            after_transition.date_in(&chrono_tz::Europe::Warsaw),
+            NaiveDate::from_ymd(2022, 10, 30)
+        );
+
+        let before_transition = create_worklog("2022-10-29T22:30:00+00:00");
+        assert_eq!(
+            before_transition.date_in(&chrono_tz::Europe::Warsaw),
+            NaiveDate::from_ymd(2022, 10, 30)
+        );
+    }
+
+    #[test]
+    fn display_shows_worklog_details() {
+        let worklog = create_worklog("2022-09-15T09:00:00+02:00")
+            .with_id("10001")
+            .with_issue_id("10100")
+            .with_created(DateTime::parse_from_rfc3339("2022-09-15T10:00:00+02:00").unwrap())
+            .with_updated(DateTime::parse_from_rfc3339("2022-09-16T08:30:00+02:00").unwrap())
+            .with_comment("Code review")
+            .with_author(WorklogAuthor {
+                display_name: Some("John Doe".to_string()),
+                ..Default::default()
+            })
+            .with_visibility(WorklogVisibility {
+                kind: "group".to_string(),
+                value: "developers".to_string(),
+            });
+
+        assert_eq!(
+            worklog.to_string(),
+            "Worklog id: 10001, Issue key: PROJ-123, Issue id: 10100, Summary: summary, \
+             Started: 2022-09-15T09:00:00+02:00, Logger time: 1h, Author: John Doe, \
+             Created: 2022-09-15T10:00:00+02:00, Updated: 2022-09-16T08:30:00+02:00, \
+             Comment: Code review, Visible to group: developers"
+        );
+    }
 }
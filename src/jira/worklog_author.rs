@@ -0,0 +1,103 @@
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WorklogAuthor {
+    pub account_id: Option<String>,
+    pub key: Option<String>,
+    pub name: Option<String>,
+    pub display_name: Option<String>,
+    pub email: Option<String>,
+}
+
+impl WorklogAuthor {
+    pub fn from_json(author: &serde_json::Value) -> Self {
+        let field = |name: &str| author[name].as_str().map(|value| value.to_string());
+        Self {
+            account_id: field("accountId"),
+            key: field("key"),
+            name: field("name"),
+            display_name: field("displayName"),
+            email: field("emailAddress"),
+        }
+    }
+
+    /// Checks whether `input` (account id, key, user name, email or display name) refers to
+    /// this user; email and display name ignore case.
+    pub fn is_referred_by(&self, input: &str) -> bool {
+        [&self.account_id, &self.key, &self.name]
+            .into_iter()
+            .flatten()
+            .any(|value| value == input)
+            || [&self.email, &self.display_name]
+                .into_iter()
+                .flatten()
+                .any(|value| value.eq_ignore_ascii_case(input))
+    }
+
+    /// Compares the most stable identifier both sides have: account id, then key, then name.
+    pub fn matches(&self, author: &WorklogAuthor) -> bool {
+        if let (Some(expected), Some(actual)) = (&self.account_id, &author.account_id) {
+            return expected == actual;
+        }
+        if let (Some(expected), Some(actual)) = (&self.key, &author.key) {
+            return expected == actual;
+        }
+        match (&self.name, &author.name) {
+            (Some(expected), Some(actual)) => expected == actual,
+            _ => false,
+        }
+    }
+
+    /// Value accepted by `worklogAuthor` in JQL: account id on Cloud, user name on Server/DC.
+    pub fn jql_value(&self) -> Option<&str> {
+        self.account_id
+            .as_deref()
+            .or(self.name.as_deref())
+            .or(self.key.as_deref())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_prefers_account_id_over_key_and_name() {
+        let uut = WorklogAuthor {
+            account_id: Some("557058:abc".to_string()),
+            name: Some("john".to_string()),
+            ..Default::default()
+        };
+
+        assert!(uut.matches(&WorklogAuthor {
+            account_id: Some("557058:abc".to_string()),
+            name: Some("renamed".to_string()),
+            ..Default::default()
+        }));
+        assert!(!uut.matches(&WorklogAuthor {
+            account_id: Some("557058:xyz".to_string()),
+            name: Some("john".to_string()),
+            ..Default::default()
+        }));
+        assert!(uut.matches(&WorklogAuthor {
+            name: Some("john".to_string()),
+            ..Default::default()
+        }));
+        assert!(!uut.matches(&WorklogAuthor::default()));
+    }
+
+    #[test]
+    fn is_referred_by_accepts_identifiers_email_and_display_name() {
+        let uut = WorklogAuthor::from_json(&serde_json::json!({
+            "key": "JIRAUSER10100",
+            "name": "john",
+            "displayName": "John Doe",
+            "emailAddress": "John.Doe@example.com"
+        }));
+
+        assert!(uut.is_referred_by("JIRAUSER10100"));
+        assert!(uut.is_referred_by("john"));
+        assert!(uut.is_referred_by("john.doe@example.com"));
+        assert!(uut.is_referred_by("john doe"));
+        assert!(!uut.is_referred_by("Jane Doe"));
+    }
+}
@@ -0,0 +1,212 @@
+use super::{
+    api_capabilities::ApiCapabilities,
+    client_trait::ClientTrait,
+    worklog::{Worklog, JIRA_TIME_FORMAT},
+};
+use anyhow::anyhow;
+use reqwest::IntoUrl;
+
+/// Creates worklogs through Jira's `POST /issue/{key}/worklog`.
+pub struct WorklogUploader<UrlType, ClientType>
+where
+    UrlType: IntoUrl,
+    ClientType: ClientTrait,
+{
+    jira_api_root_url: UrlType,
+    jira_client: ClientType,
+    adf_comments: bool,
+}
+
+impl<ClientType> WorklogUploader<String, ClientType>
+where
+    ClientType: ClientTrait,
+{
+    /// Detects the Jira flavor at `jira_base_url` and uploads through the matching REST API.
+    pub fn detect(jira_base_url: &str, jira_client: ClientType) -> anyhow::Result<Self> {
+        let capabilities = ApiCapabilities::detect(&jira_client, jira_base_url)?;
+        Ok(
+            Self::new(capabilities.api_root_url(jira_base_url), jira_client)
+                .with_capabilities(&capabilities),
+        )
+    }
+}
+
+impl<UrlType, ClientType> WorklogUploader<UrlType, ClientType>
+where
+    UrlType: IntoUrl,
+    ClientType: ClientTrait,
+{
+    pub fn new(jira_api_root_url: UrlType, jira_client: ClientType) -> Self {
+        Self {
+            jira_api_root_url,
+            jira_client,
+            adf_comments: false,
+        }
+    }
+
+    /// Sends comments as ADF documents when the API expects them (REST v3).
+    pub fn with_capabilities(mut self, capabilities: &ApiCapabilities) -> Self {
+        self.adf_comments = capabilities.adf_comments;
+        self
+    }
+
+    fn create_jira_issue_worklog_endpoint(&self, issue_key: &str) -> String {
+        format!(
+            "/issue/{}/worklog",
+            form_urlencoded::byte_serialize(issue_key.as_bytes()).collect::<String>()
+        )
+    }
+
+    fn create_request_body(&self, worklog: &Worklog) -> serde_json::Value {
+        let mut body = serde_json::json!({
+            "started": worklog.started.format(JIRA_TIME_FORMAT).to_string(),
+            "timeSpentSeconds": worklog.time_spent.num_seconds(),
+        });
+        // Jira Cloud rejects ADF text nodes without text, so blank comments are left out.
+        if let Some(comment) = worklog
+            .comment
+            .as_ref()
+            .filter(|comment| !comment.trim().is_empty())
+        {
+            body["comment"] = if self.adf_comments {
+                serde_json::json!({
+                    "type": "doc",
+                    "version": 1,
+                    "content": [{
+                        "type": "paragraph",
+                        "content": [{ "type": "text", "text": comment }]
+                    }]
+                })
+            } else {
+                serde_json::Value::String(comment.clone())
+            };
+        }
+        body
+    }
+
+    /// Uploads the worklog and returns id of the created Jira worklog.
+    pub fn upload(&self, worklog: &Worklog) -> anyhow::Result<String> {
+        let response = self
+            .jira_client
+            .request_post(
+                format!(
+                    "{}{}",
+                    self.jira_api_root_url.as_str(),
+                    self.create_jira_issue_worklog_endpoint(&worklog.issue_key)
+                ),
+                &self.create_request_body(worklog),
+            )?
+            .error_for_status()?
+            .json::<serde_json::Value>()?;
+        response["id"]
+            .as_str()
+            .map(|id| id.to_string())
+            .ok_or(anyhow!("Can't parse id of created worklog"))
+    }
+
+    /// Uploads every worklog, continuing after failures.
+    pub fn upload_all(&self, worklogs: &[Worklog]) -> Vec<anyhow::Result<String>> {
+        worklogs
+            .iter()
+            .map(|worklog| self.upload(worklog))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jira::{Client, ServerInfo};
+    use chrono::{DateTime, Duration};
+
+    #[test]
+    fn upload_posts_worklog_and_returns_created_id() {
+        let uut = WorklogUploader::new(mockito::server_url(), Client::new("user", "password"));
+        let worklog = Worklog::new(
+            DateTime::parse_from_rfc3339("2022-09-15T09:00:00+02:00").unwrap(),
+            "ABC-1",
+            "",
+            Duration::minutes(90),
+        )
+        .with_comment("Imported");
+
+        let _create_worklog_mock = mockito::mock("POST", "/issue/ABC-1/worklog")
+            .match_body(mockito::Matcher::Json(serde_json::json!({
+                "started": "2022-09-15T09:00:00.000+0200",
+                "timeSpentSeconds": 5400,
+                "comment": "Imported"
+            })))
+            .with_status(201)
+            .with_header("content-type", "application/json")
+            .with_body(r###"{"id": "10100"}"###)
+            .create();
+        let _missing_issue_mock = mockito::mock("POST", "/issue/ABC-2/worklog")
+            .with_status(404)
+            .create();
+
+        let mut missing_issue_worklog = worklog.clone();
+        missing_issue_worklog.issue_key = "ABC-2".to_string();
+        let results = uut.upload_all(&[worklog, missing_issue_worklog]);
+        assert_eq!(results[0].as_ref().unwrap(), "10100");
+        assert!(results[1].is_err());
+    }
+
+    #[test]
+    fn detect_uploads_through_v2_api_with_plain_comments_on_server() {
+        let _server_info_mock = mockito::mock("GET", "/rest/api/2/serverInfo")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r###"{"version": "8.20.10", "deploymentType": "Server"}"###)
+            .create();
+        let _create_worklog_mock = mockito::mock("POST", "/rest/api/2/issue/ABC-1/worklog")
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                "comment": "Imported"
+            })))
+            .with_status(201)
+            .with_header("content-type", "application/json")
+            .with_body(r###"{"id": "10101"}"###)
+            .create();
+
+        let uut = WorklogUploader::detect(&mockito::server_url(), Client::new("user", "password"))
+            .unwrap();
+        let worklog = Worklog::new(
+            DateTime::parse_from_rfc3339("2022-09-15T09:00:00+02:00").unwrap(),
+            "ABC-1",
+            "",
+            Duration::minutes(30),
+        )
+        .with_comment("Imported");
+
+        assert_eq!(uut.upload(&worklog).unwrap(), "10101");
+    }
+
+    #[test]
+    fn create_request_body_omits_blank_comments() {
+        let capabilities = ApiCapabilities::from_server_info(
+            ServerInfo::from_json(&serde_json::json!({
+                "version": "1001.0.0-SNAPSHOT",
+                "deploymentType": "Cloud"
+            }))
+            .unwrap(),
+        );
+        let worklog = Worklog::new(
+            DateTime::parse_from_rfc3339("2022-09-15T09:00:00+02:00").unwrap(),
+            "ABC-1",
+            "",
+            Duration::minutes(30),
+        );
+
+        for uut in [
+            WorklogUploader::new(mockito::server_url(), Client::new("user", "password")),
+            WorklogUploader::new(mockito::server_url(), Client::new("user", "password"))
+                .with_capabilities(&capabilities),
+        ] {
+            for comment in ["", "  "] {
+                let body = uut.create_request_body(&worklog.clone().with_comment(comment));
+                assert!(body.get("comment").is_none());
+            }
+            let body = uut.create_request_body(&worklog.clone().with_comment("Imported"));
+            assert!(body.get("comment").is_some());
+        }
+    }
+}
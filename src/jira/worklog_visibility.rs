@@ -0,0 +1,15 @@
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WorklogVisibility {
+    pub kind: String,
+    pub value: String,
+}
+
+impl WorklogVisibility {
+    pub fn from_json(visibility: &serde_json::Value) -> Option<Self> {
+        Some(Self {
+            kind: visibility["type"].as_str()?.to_string(),
+            value: visibility["value"].as_str()?.to_string(),
+        })
+    }
+}
@@ -1,25 +1,138 @@
 pub mod audio;
+pub mod drafts;
+pub mod gitlab;
 pub mod gui;
+pub mod import;
 pub mod jira;
+pub mod output;
+pub mod report;
+pub mod rules;
+#[cfg(feature = "serde")]
+pub mod serialization;
+pub mod sources;
+pub mod tempo;
+#[cfg(test)]
+mod test_helpers;
 pub mod time_tracker;
 
 pub mod application {
     use super::audio::PlayerTrait;
     use super::gui::WindowTrait;
-    use super::time_tracker::SummaryTrait;
+    use super::jira::TimeTrackingConfig;
+    use super::output::OutputFormat;
+    #[cfg(feature = "serde")]
+    use super::output::{ErrorReport, EvaluationReport};
+    use super::report::BreakdownReport;
+    use super::rules::{Policy, Violation};
+    use super::time_tracker::{BreakdownGrouping, SummaryTrait};
     use crate::time_tracker::UserWorklogsSummary;
 
     pub fn run(
+        summary: impl SummaryTrait,
+        window: impl WindowTrait,
+        player: impl PlayerTrait,
+        policy: &Policy,
+        user_name: &str,
+        start_date: chrono::NaiveDate,
+        end_date: chrono::NaiveDate,
+    ) -> anyhow::Result<()> {
+        run_with_output(
+            summary,
+            window,
+            player,
+            policy,
+            user_name,
+            start_date,
+            end_date,
+            OutputFormat::Text,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn run_with_output(
         summary: impl SummaryTrait,
         mut window: impl WindowTrait,
         player: impl PlayerTrait,
+        policy: &Policy,
         user_name: &str,
         start_date: chrono::NaiveDate,
         end_date: chrono::NaiveDate,
+        output_format: OutputFormat,
     ) -> anyhow::Result<()> {
         match summary.get_user_worklogs_summary(user_name, start_date, end_date) {
-            Err(error) => process_error(error),
-            Ok(result) => process_ok(result, &mut window, &player)?,
+            Err(error) => match output_format {
+                OutputFormat::Text => process_error(error),
+                #[cfg(feature = "serde")]
+                OutputFormat::Json => {
+                    println!(
+                        "{}",
+                        ErrorReport::new(user_name, start_date, end_date, &error).to_json()?
+                    );
+                    return Err(error);
+                }
+                #[cfg(feature = "serde")]
+                OutputFormat::NdJson => {
+                    print!(
+                        "{}",
+                        ErrorReport::new(user_name, start_date, end_date, &error).to_ndjson()?
+                    );
+                    return Err(error);
+                }
+                #[cfg(not(feature = "serde"))]
+                OutputFormat::Json | OutputFormat::NdJson => return Err(error),
+            },
+            Ok(result) => {
+                let violations = policy.evaluate(&result, summary.timezone());
+                let time_tracking = summary.time_tracking();
+                let source_failures = summary.source_failures();
+                for failure in &source_failures {
+                    eprintln!(
+                        "WARNING: source '{}' failed for '{}': {}",
+                        failure.source, failure.user_name, failure.error
+                    );
+                }
+                match output_format {
+                    OutputFormat::Text => print_text(&result, &violations, time_tracking.as_ref()),
+                    #[cfg(feature = "serde")]
+                    OutputFormat::Json => println!(
+                        "{}",
+                        create_report(
+                            user_name,
+                            start_date,
+                            end_date,
+                            &result,
+                            &violations,
+                            policy,
+                            time_tracking.as_ref()
+                        )
+                        .with_source_failures(source_failures)
+                        .to_json()?
+                    ),
+                    #[cfg(feature = "serde")]
+                    OutputFormat::NdJson => print!(
+                        "{}",
+                        create_report(
+                            user_name,
+                            start_date,
+                            end_date,
+                            &result,
+                            &violations,
+                            policy,
+                            time_tracking.as_ref()
+                        )
+                        .with_source_failures(source_failures)
+                        .to_ndjson()?
+                    ),
+                    #[cfg(not(feature = "serde"))]
+                    OutputFormat::Json | OutputFormat::NdJson => {
+                        return Err(anyhow::anyhow!(
+                            "{:?} output requires the 'serde' feature",
+                            output_format
+                        ))
+                    }
+                }
+                process_ok(&violations, policy, &mut window, &player)?
+            }
         };
         Ok(())
     }
@@ -28,18 +141,55 @@ pub mod application {
         eprintln!("ERROR: {}", error);
     }
 
+    fn print_text(
+        result: &UserWorklogsSummary,
+        violations: &[Violation],
+        time_tracking: Option<&TimeTrackingConfig>,
+    ) {
+        result.iter().for_each(|(date, worklog_summary)| {
+            println!("{}: {}", date, worklog_summary.spent_time)
+        });
+        violations
+            .iter()
+            .for_each(|violation| println!("{}", violation.describe(time_tracking)));
+        if !result.is_empty() {
+            let breakdown = result.breakdown(BreakdownGrouping::Issue);
+            let report = BreakdownReport::new(&breakdown);
+            match time_tracking {
+                Some(time_tracking) => println!("{}", report.with_time_tracking(*time_tracking)),
+                None => println!("{}", report),
+            }
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    fn create_report(
+        user_name: &str,
+        start_date: chrono::NaiveDate,
+        end_date: chrono::NaiveDate,
+        result: &UserWorklogsSummary,
+        violations: &[Violation],
+        policy: &Policy,
+        time_tracking: Option<&TimeTrackingConfig>,
+    ) -> EvaluationReport {
+        EvaluationReport::new(
+            user_name,
+            start_date,
+            end_date,
+            result,
+            violations,
+            policy,
+            time_tracking,
+        )
+    }
+
     fn process_ok(
-        result: UserWorklogsSummary,
+        violations: &[Violation],
+        policy: &Policy,
         window: &mut impl WindowTrait,
         player: &impl PlayerTrait,
     ) -> anyhow::Result<()> {
-        let any_worklog_spent_timme_other_than_expected =
-            result.iter().any(|(_worklog_date, worklog_summary)| {
-                println!("{}", worklog_summary.spent_time);
-                worklog_summary.spent_time != chrono::Duration::hours(8)
-            });
-        println!("{:?}", any_worklog_spent_timme_other_than_expected);
-        if any_worklog_spent_timme_other_than_expected {
+        if policy.requires_alert(violations) {
             window.load_image("assets/image.jpg")?;
             window.show_image()?;
             player.play()?;
@@ -54,20 +204,21 @@ pub mod application {
         use crate::audio;
         use crate::gui;
         use crate::jira;
+        use crate::rules;
+        use crate::test_helpers::started_at;
         use crate::time_tracker;
         use anyhow::anyhow;
-        use chrono::Duration;
-        use chrono::NaiveDate;
+        use chrono::{Duration, NaiveDate};
         use lazy_static::lazy_static;
         use mockall::predicate::eq;
 
-        const USER_NAME: &'static str = "user_name";
+        const USER_NAME: &str = "user_name";
 
         lazy_static! {
-            static ref START_DATE: NaiveDate = NaiveDate::from_ymd(2022, 09, 10);
-            static ref END_DATE: NaiveDate = NaiveDate::from_ymd(2022, 09, 10);
-            static ref DATE_1: NaiveDate = NaiveDate::from_ymd(2022, 09, 15);
-            static ref DATE_2: NaiveDate = NaiveDate::from_ymd(2022, 09, 16);
+            static ref START_DATE: NaiveDate = NaiveDate::from_ymd(2022, 9, 10);
+            static ref END_DATE: NaiveDate = NaiveDate::from_ymd(2022, 9, 10);
+            static ref DATE_1: NaiveDate = NaiveDate::from_ymd(2022, 9, 15);
+            static ref DATE_2: NaiveDate = NaiveDate::from_ymd(2022, 9, 16);
             static ref DURATION_1H: Duration = Duration::hours(1);
             static ref DURATION_3H: Duration = Duration::hours(3);
             static ref DURATION_4H: Duration = Duration::hours(4);
@@ -85,10 +236,15 @@ pub mod application {
                 .with(eq(USER_NAME), eq(*START_DATE), eq(&*END_DATE))
                 .return_once(move |_, _, _| Ok(UserWorklogsSummary::new()));
 
+            summary.expect_timezone().return_const(None);
+            summary.expect_time_tracking().return_const(None);
+            summary.expect_source_failures().return_const(vec![]);
+
             run(
                 summary,
                 windows,
                 sin_player,
+                &Policy::default(),
                 USER_NAME,
                 *START_DATE,
                 *END_DATE,
@@ -103,16 +259,36 @@ pub mod application {
             let mut windows = gui::testing::MockWindowTrait::default();
             let mut summary = time_tracker::testing::MockSummaryTrait::default();
 
-            let date_1_worklog1 =
-                jira::Worklog::new(*DATE_1, "date_1-key1", "date_1-summary1", *DURATION_3H);
-            let date_1_worklog2 =
-                jira::Worklog::new(*DATE_1, "date_1-key1", "date_1-summary1", *DURATION_5H);
-            let date_2_worklog1 =
-                jira::Worklog::new(*DATE_2, "date_2-key1", "date_2-summary1", *DURATION_1H);
-            let date_2_worklog2 =
-                jira::Worklog::new(*DATE_2, "date_2-key2", "date_2-summary2", *DURATION_3H);
-            let date_2_worklog3 =
-                jira::Worklog::new(*DATE_2, "date_2-key3", "date_2-summary3", *DURATION_4H);
+            let date_1_worklog1 = jira::Worklog::new(
+                started_at(*DATE_1),
+                "date_1-key1",
+                "date_1-summary1",
+                *DURATION_3H,
+            );
+            let date_1_worklog2 = jira::Worklog::new(
+                started_at(*DATE_1),
+                "date_1-key1",
+                "date_1-summary1",
+                *DURATION_5H,
+            );
+            let date_2_worklog1 = jira::Worklog::new(
+                started_at(*DATE_2),
+                "date_2-key1",
+                "date_2-summary1",
+                *DURATION_1H,
+            );
+            let date_2_worklog2 = jira::Worklog::new(
+                started_at(*DATE_2),
+                "date_2-key2",
+                "date_2-summary2",
+                *DURATION_3H,
+            );
+            let date_2_worklog3 = jira::Worklog::new(
+                started_at(*DATE_2),
+                "date_2-key3",
+                "date_2-summary3",
+                *DURATION_4H,
+            );
 
             let mut user_worklog_summary = UserWorklogsSummary::new();
             user_worklog_summary.insert(
@@ -133,6 +309,9 @@ pub mod application {
                 .expect_get_user_worklogs_summary()
                 .with(eq(USER_NAME), eq(*START_DATE), eq(*END_DATE))
                 .return_once(move |_, _, _| Ok(user_worklog_summary));
+            summary.expect_timezone().return_const(None);
+            summary.expect_time_tracking().return_const(None);
+            summary.expect_source_failures().return_const(vec![]);
 
             windows.expect_load_image().times(0);
             windows.expect_show_image().times(0);
@@ -143,6 +322,7 @@ pub mod application {
                 summary,
                 windows,
                 sin_player,
+                &Policy::default(),
                 USER_NAME,
                 *START_DATE,
                 *END_DATE,
@@ -157,14 +337,30 @@ pub mod application {
             let mut windows = gui::testing::MockWindowTrait::default();
             let mut summary = time_tracker::testing::MockSummaryTrait::default();
 
-            let date_1_worklog1 =
-                jira::Worklog::new(*DATE_1, "date_1-key1", "date_1-summary1", *DURATION_3H);
-            let date_1_worklog2 =
-                jira::Worklog::new(*DATE_1, "date_1-key1", "date_1-summary1", *DURATION_5H);
-            let date_2_worklog2 =
-                jira::Worklog::new(*DATE_2, "date_2-key2", "date_2-summary2", *DURATION_3H);
-            let date_2_worklog3 =
-                jira::Worklog::new(*DATE_2, "date_2-key3", "date_2-summary3", *DURATION_4H);
+            let date_1_worklog1 = jira::Worklog::new(
+                started_at(*DATE_1),
+                "date_1-key1",
+                "date_1-summary1",
+                *DURATION_3H,
+            );
+            let date_1_worklog2 = jira::Worklog::new(
+                started_at(*DATE_1),
+                "date_1-key1",
+                "date_1-summary1",
+                *DURATION_5H,
+            );
+            let date_2_worklog2 = jira::Worklog::new(
+                started_at(*DATE_2),
+                "date_2-key2",
+                "date_2-summary2",
+                *DURATION_3H,
+            );
+            let date_2_worklog3 = jira::Worklog::new(
+                started_at(*DATE_2),
+                "date_2-key3",
+                "date_2-summary3",
+                *DURATION_4H,
+            );
 
             let mut user_worklog_summary = UserWorklogsSummary::new();
             user_worklog_summary.insert(
@@ -187,6 +383,10 @@ pub mod application {
                 .with(eq(USER_NAME), eq(*START_DATE), eq(*END_DATE))
                 .return_once(move |_, _, _| Ok(user_worklog_summary));
 
+            summary.expect_timezone().return_const(None);
+            summary.expect_time_tracking().return_const(None);
+            summary.expect_source_failures().return_const(vec![]);
+
             windows.expect_load_image().return_once(|_| Ok(()));
             windows.expect_show_image().return_once(|| Ok(()));
             sin_player.expect_play().return_once(|| Ok(()));
@@ -196,6 +396,64 @@ pub mod application {
                 summary,
                 windows,
                 sin_player,
+                &Policy::default(),
+                USER_NAME,
+                *START_DATE,
+                *END_DATE,
+            )
+            .unwrap();
+        }
+
+        #[test]
+        fn run_function_does_nothing_when_violations_are_below_alert_severity() {
+            let mut sin_player = audio::testing::MockPlayerTrait::default();
+            let mut windows = gui::testing::MockWindowTrait::default();
+            let mut summary = time_tracker::testing::MockSummaryTrait::default();
+
+            let date_1_worklog1 = jira::Worklog::new(
+                started_at(*DATE_1),
+                "date_1-key1",
+                "date_1-summary1",
+                *DURATION_3H,
+            );
+
+            let mut user_worklog_summary = UserWorklogsSummary::new();
+            user_worklog_summary.insert(
+                *DATE_1,
+                time_tracker::WorklogSummary {
+                    spent_time: *DURATION_3H,
+                    worklogs: vec![date_1_worklog1],
+                },
+            );
+
+            summary
+                .expect_get_user_worklogs_summary()
+                .with(eq(USER_NAME), eq(*START_DATE), eq(*END_DATE))
+                .return_once(move |_, _, _| Ok(user_worklog_summary));
+
+            summary.expect_timezone().return_const(None);
+            summary.expect_time_tracking().return_const(None);
+            summary.expect_source_failures().return_const(vec![]);
+
+            windows.expect_load_image().times(0);
+            windows.expect_show_image().times(0);
+            sin_player.expect_play().times(0);
+            windows.expect_hide_image().times(0);
+
+            let policy = Policy::new(rules::PolicyConfig {
+                alert_severity: rules::Severity::Error,
+                expected_daily_time: Some(
+                    rules::ExpectedDailyTimeRule::new(Duration::hours(8))
+                        .with_severity(rules::Severity::Warning),
+                ),
+                ..Default::default()
+            });
+
+            run(
+                summary,
+                windows,
+                sin_player,
+                &policy,
                 USER_NAME,
                 *START_DATE,
                 *END_DATE,
@@ -214,15 +472,77 @@ pub mod application {
                 .with(eq(USER_NAME), eq(*START_DATE), eq(*END_DATE))
                 .return_once(move |_, _, _| Err(anyhow!("Error in get_user_worklogs_summary")));
 
+            summary.expect_timezone().return_const(None);
+            summary.expect_time_tracking().return_const(None);
+            summary.expect_source_failures().return_const(vec![]);
+
             run(
                 summary,
                 windows,
                 sin_player,
+                &Policy::default(),
                 USER_NAME,
                 *START_DATE,
                 *END_DATE,
             )
             .unwrap();
         }
+
+        #[test]
+        fn run_with_output_returns_error_when_summary_is_err_in_json_modes() {
+            for output_format in [OutputFormat::Json, OutputFormat::NdJson] {
+                let sin_player = audio::testing::MockPlayerTrait::default();
+                let windows = gui::testing::MockWindowTrait::default();
+                let mut summary = time_tracker::testing::MockSummaryTrait::default();
+
+                summary
+                    .expect_get_user_worklogs_summary()
+                    .return_once(move |_, _, _| Err(anyhow!("Error in get_user_worklogs_summary")));
+
+                let result = run_with_output(
+                    summary,
+                    windows,
+                    sin_player,
+                    &Policy::default(),
+                    USER_NAME,
+                    *START_DATE,
+                    *END_DATE,
+                    output_format,
+                );
+
+                assert_eq!(
+                    result.unwrap_err().to_string(),
+                    "Error in get_user_worklogs_summary"
+                );
+            }
+        }
+
+        #[cfg(not(feature = "serde"))]
+        #[test]
+        fn run_with_output_returns_error_for_json_without_serde_feature() {
+            let sin_player = audio::testing::MockPlayerTrait::default();
+            let windows = gui::testing::MockWindowTrait::default();
+            let mut summary = time_tracker::testing::MockSummaryTrait::default();
+
+            summary
+                .expect_get_user_worklogs_summary()
+                .return_once(move |_, _, _| Ok(UserWorklogsSummary::new()));
+            summary.expect_timezone().return_const(None);
+            summary.expect_time_tracking().return_const(None);
+            summary.expect_source_failures().return_const(vec![]);
+
+            let result = run_with_output(
+                summary,
+                windows,
+                sin_player,
+                &Policy::default(),
+                USER_NAME,
+                *START_DATE,
+                *END_DATE,
+                OutputFormat::Json,
+            );
+
+            assert!(result.is_err());
+        }
     }
 }
@@ -0,0 +1,412 @@
+use crate::jira::{TimeTrackingConfig, Worklog};
+use crate::rules::{Policy, Violation, ViolationKind};
+use crate::sources::SourceFailure;
+use crate::time_tracker::{BreakdownGrouping, UserWorklogsSummary};
+use chrono::NaiveDate;
+use serde::Serialize;
+use std::collections::BTreeSet;
+
+/// Bumped on every incompatible change of the JSON and NDJSON output.
+pub const OUTPUT_SCHEMA_VERSION: u32 = 1;
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize)]
+pub struct ViolationReport {
+    pub rule: String,
+    pub severity: String,
+    pub date: NaiveDate,
+    pub message: String,
+    /// `kind` tag plus the violation's own fields, durations as `*_seconds`.
+    #[serde(flatten)]
+    pub kind: ViolationKind,
+}
+
+impl ViolationReport {
+    fn new(violation: &Violation, time_tracking: Option<&TimeTrackingConfig>) -> Self {
+        Self {
+            rule: violation.rule.clone(),
+            severity: violation.severity.to_string(),
+            date: violation.date,
+            message: violation.kind.describe(time_tracking),
+            kind: violation.kind.clone(),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize)]
+pub struct BreakdownEntryReport {
+    pub key: String,
+    pub label: String,
+    pub spent_seconds: i64,
+    pub percentage: f64,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize)]
+pub struct DayReport {
+    pub date: NaiveDate,
+    pub logged_seconds: i64,
+    /// `None` when the policy has no expected daily time rule.
+    pub expected_seconds: Option<i64>,
+    pub violations: Vec<ViolationReport>,
+    pub worklogs: Vec<Worklog>,
+}
+
+/// Machine readable result of evaluating a policy against a user's summary.
+#[derive(Debug, PartialEq, Clone, Serialize)]
+pub struct EvaluationReport {
+    pub schema_version: u32,
+    pub user: String,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub logged_seconds: i64,
+    pub expected_seconds: Option<i64>,
+    pub alert: bool,
+    pub days: Vec<DayReport>,
+    /// Time by issue over the whole range, largest first.
+    pub breakdown: Vec<BreakdownEntryReport>,
+    /// Sources skipped because they failed; the report covers only the others.
+    pub source_failures: Vec<SourceFailure>,
+}
+
+/// Machine readable failure to fetch the summary, printed instead of an `EvaluationReport`.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize)]
+pub struct ErrorReport {
+    pub schema_version: u32,
+    pub user: String,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    /// The error with its whole context chain.
+    pub error: String,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+enum Event<'a> {
+    Evaluation {
+        user: &'a str,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    },
+    SourceFailure(&'a SourceFailure),
+    Day {
+        date: NaiveDate,
+        logged_seconds: i64,
+        expected_seconds: Option<i64>,
+    },
+    Worklog {
+        date: NaiveDate,
+        worklog: &'a Worklog,
+    },
+    Violation(&'a ViolationReport),
+    Summary {
+        logged_seconds: i64,
+        expected_seconds: Option<i64>,
+        violations: usize,
+        alert: bool,
+    },
+    Error {
+        user: &'a str,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+        error: &'a str,
+    },
+}
+
+#[derive(Serialize)]
+struct EventLine<'a> {
+    schema_version: u32,
+    #[serde(flatten)]
+    event: Event<'a>,
+}
+
+impl EvaluationReport {
+    /// Days are those present in the summary plus any day a violation was reported for.
+    /// Violation messages use Jira notation when `time_tracking` is known.
+    pub fn new(
+        user: &str,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+        summary: &UserWorklogsSummary,
+        violations: &[Violation],
+        policy: &Policy,
+        time_tracking: Option<&TimeTrackingConfig>,
+    ) -> Self {
+        let dates: BTreeSet<NaiveDate> = summary
+            .keys()
+            .copied()
+            .chain(violations.iter().map(|violation| violation.date))
+            .collect();
+        let days: Vec<DayReport> = dates
+            .into_iter()
+            .map(|date| {
+                let worklog_summary = summary.get(&date);
+                DayReport {
+                    date,
+                    logged_seconds: worklog_summary
+                        .map(|worklog_summary| worklog_summary.spent_time.num_seconds())
+                        .unwrap_or_default(),
+                    expected_seconds: policy
+                        .schedule()
+                        .map(|schedule| schedule.expected_time(&date).num_seconds()),
+                    violations: violations
+                        .iter()
+                        .filter(|violation| violation.date == date)
+                        .map(|violation| ViolationReport::new(violation, time_tracking))
+                        .collect(),
+                    worklogs: worklog_summary
+                        .map(|worklog_summary| worklog_summary.worklogs.clone())
+                        .unwrap_or_default(),
+                }
+            })
+            .collect();
+
+        Self {
+            schema_version: OUTPUT_SCHEMA_VERSION,
+            user: user.to_string(),
+            start_date,
+            end_date,
+            logged_seconds: days.iter().map(|day| day.logged_seconds).sum(),
+            expected_seconds: policy
+                .schedule()
+                .map(|_| days.iter().filter_map(|day| day.expected_seconds).sum()),
+            alert: policy.requires_alert(violations),
+            days,
+            breakdown: summary
+                .breakdown(BreakdownGrouping::Issue)
+                .entries
+                .into_iter()
+                .map(|entry| BreakdownEntryReport {
+                    key: entry.key,
+                    label: entry.label,
+                    spent_seconds: entry.spent_time.num_seconds(),
+                    percentage: entry.percentage,
+                })
+                .collect(),
+            source_failures: vec![],
+        }
+    }
+
+    pub fn with_source_failures(mut self, source_failures: Vec<SourceFailure>) -> Self {
+        self.source_failures = source_failures;
+        self
+    }
+
+    pub fn to_json(&self) -> anyhow::Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Header event and `source_failure` events, then per day a `day` event followed by its
+    /// `worklog` and `violation` events, and a closing `summary` event.
+    pub fn to_ndjson(&self) -> anyhow::Result<String> {
+        let mut events = vec![Event::Evaluation {
+            user: &self.user,
+            start_date: self.start_date,
+            end_date: self.end_date,
+        }];
+        events.extend(self.source_failures.iter().map(Event::SourceFailure));
+        for day in &self.days {
+            events.push(Event::Day {
+                date: day.date,
+                logged_seconds: day.logged_seconds,
+                expected_seconds: day.expected_seconds,
+            });
+            events.extend(day.worklogs.iter().map(|worklog| Event::Worklog {
+                date: day.date,
+                worklog,
+            }));
+            events.extend(day.violations.iter().map(Event::Violation));
+        }
+        events.push(Event::Summary {
+            logged_seconds: self.logged_seconds,
+            expected_seconds: self.expected_seconds,
+            violations: self.days.iter().map(|day| day.violations.len()).sum(),
+            alert: self.alert,
+        });
+
+        let mut output = String::new();
+        for event in events {
+            output += &serde_json::to_string(&EventLine {
+                schema_version: self.schema_version,
+                event,
+            })?;
+            output.push('\n');
+        }
+        Ok(output)
+    }
+}
+
+impl ErrorReport {
+    pub fn new(
+        user: &str,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+        error: &anyhow::Error,
+    ) -> Self {
+        Self {
+            schema_version: OUTPUT_SCHEMA_VERSION,
+            user: user.to_string(),
+            start_date,
+            end_date,
+            error: format!("{:#}", error),
+        }
+    }
+
+    pub fn to_json(&self) -> anyhow::Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// A single `error` event.
+    pub fn to_ndjson(&self) -> anyhow::Result<String> {
+        let mut output = serde_json::to_string(&EventLine {
+            schema_version: self.schema_version,
+            event: Event::Error {
+                user: &self.user,
+                start_date: self.start_date,
+                end_date: self.end_date,
+                error: &self.error,
+            },
+        })?;
+        output.push('\n');
+        Ok(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::{PolicyConfig, Severity, ViolationKind};
+    use crate::time_tracker::WorklogSummary;
+    use chrono::{DateTime, Duration};
+    use serde_json::{json, Value};
+
+    fn create_uut() -> EvaluationReport {
+        let date = NaiveDate::from_ymd(2022, 9, 15);
+        let worklog = Worklog::new(
+            DateTime::parse_from_rfc3339("2022-09-15T09:00:00+02:00").unwrap(),
+            "PROJ-1",
+            "summary",
+            Duration::hours(6),
+        );
+        let summary: UserWorklogsSummary = [(
+            date,
+            WorklogSummary {
+                spent_time: Duration::hours(6),
+                worklogs: vec![worklog],
+            },
+        )]
+        .into_iter()
+        .collect();
+        let violations = [Violation::new(
+            "expected-daily-time",
+            Severity::Error,
+            date,
+            ViolationKind::UnexpectedDailyTime {
+                expected: Duration::hours(8),
+                logged: Duration::hours(6),
+            },
+        )];
+
+        EvaluationReport::new(
+            "john",
+            date,
+            date,
+            &summary,
+            &violations,
+            &Policy::new(PolicyConfig::default()),
+            Some(&TimeTrackingConfig::new(7.0, 5.0).unwrap()),
+        )
+        .with_source_failures(vec![SourceFailure {
+            source: "tempo".to_string(),
+            user_name: "john".to_string(),
+            error: "unauthorized".to_string(),
+        }])
+    }
+
+    #[test]
+    fn to_json_contains_days_worklogs_and_violations() {
+        let json: Value = serde_json::from_str(&create_uut().to_json().unwrap()).unwrap();
+
+        assert_eq!(json["schema_version"], json!(OUTPUT_SCHEMA_VERSION));
+        assert_eq!(json["alert"], json!(true));
+        assert_eq!(json["expected_seconds"], json!(8 * 3600));
+        let day = &json["days"][0];
+        assert_eq!(day["date"], json!("2022-09-15"));
+        assert_eq!(day["logged_seconds"], json!(6 * 3600));
+        assert_eq!(day["worklogs"][0]["issue_key"], json!("PROJ-1"));
+        assert_eq!(day["violations"][0]["severity"], json!("ERROR"));
+        assert_eq!(
+            day["violations"][0]["message"],
+            json!("logged 6h, expected 1d 1h")
+        );
+        assert_eq!(day["violations"][0]["kind"], json!("unexpected_daily_time"));
+        assert_eq!(day["violations"][0]["expected_seconds"], json!(8 * 3600));
+        assert_eq!(day["violations"][0]["logged_seconds"], json!(6 * 3600));
+        assert_eq!(
+            json["breakdown"],
+            json!([{"key": "PROJ-1", "label": "summary", "spent_seconds": 6 * 3600, "percentage": 100.0}])
+        );
+        assert_eq!(
+            json["source_failures"],
+            json!([{"source": "tempo", "user_name": "john", "error": "unauthorized"}])
+        );
+    }
+
+    #[test]
+    fn to_ndjson_emits_one_versioned_event_per_line() {
+        let output = create_uut().to_ndjson().unwrap();
+        let events: Vec<Value> = output
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+
+        assert_eq!(
+            events
+                .iter()
+                .map(|event| event["event"].as_str().unwrap())
+                .collect::<Vec<_>>(),
+            vec![
+                "evaluation",
+                "source_failure",
+                "day",
+                "worklog",
+                "violation",
+                "summary"
+            ]
+        );
+        assert_eq!(events[1]["source"], json!("tempo"));
+        assert!(events
+            .iter()
+            .all(|event| event["schema_version"] == json!(OUTPUT_SCHEMA_VERSION)));
+        assert_eq!(events[3]["worklog"]["time_spent_seconds"], json!(6 * 3600));
+        assert_eq!(events[5]["violations"], json!(1));
+    }
+
+    #[test]
+    fn error_report_is_versioned_in_json_and_ndjson() {
+        let date = NaiveDate::from_ymd(2022, 9, 15);
+        let uut = ErrorReport::new(
+            "john",
+            date,
+            date,
+            &anyhow::anyhow!("unauthorized").context("Source 'tempo' failed"),
+        );
+
+        let json: Value = serde_json::from_str(&uut.to_json().unwrap()).unwrap();
+        assert_eq!(
+            json,
+            json!({
+                "schema_version": OUTPUT_SCHEMA_VERSION,
+                "user": "john",
+                "start_date": "2022-09-15",
+                "end_date": "2022-09-15",
+                "error": "Source 'tempo' failed: unauthorized"
+            })
+        );
+
+        let output = uut.to_ndjson().unwrap();
+        assert_eq!(output.lines().count(), 1);
+        let event: Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(event["event"], json!("error"));
+        assert_eq!(event["schema_version"], json!(OUTPUT_SCHEMA_VERSION));
+        assert_eq!(event["error"], json!("Source 'tempo' failed: unauthorized"));
+    }
+}
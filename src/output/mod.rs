@@ -0,0 +1,10 @@
+#[cfg(feature = "serde")]
+mod evaluation_report;
+mod output_format;
+
+#[cfg(feature = "serde")]
+pub use evaluation_report::{
+    BreakdownEntryReport, DayReport, ErrorReport, EvaluationReport, ViolationReport,
+    OUTPUT_SCHEMA_VERSION,
+};
+pub use output_format::OutputFormat;
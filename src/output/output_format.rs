@@ -0,0 +1,10 @@
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum OutputFormat {
+    /// Human readable lines.
+    #[default]
+    Text,
+    /// Single pretty printed JSON document. Requires the `serde` feature.
+    Json,
+    /// One JSON event per line, for `jq` and other line based tools. Requires the `serde` feature.
+    NdJson,
+}
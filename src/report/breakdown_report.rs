@@ -0,0 +1,118 @@
+use crate::jira::{format_duration, TimeTrackingConfig};
+use crate::time_tracker::{BreakdownGrouping, TimeBreakdown};
+use std::fmt::Display;
+
+pub struct BreakdownReport<'a> {
+    breakdown: &'a TimeBreakdown,
+    top: Option<usize>,
+    time_tracking: Option<TimeTrackingConfig>,
+}
+
+impl<'a> BreakdownReport<'a> {
+    pub fn new(breakdown: &'a TimeBreakdown) -> Self {
+        Self {
+            breakdown,
+            top: None,
+            time_tracking: None,
+        }
+    }
+
+    pub fn with_top(mut self, top: usize) -> Self {
+        self.top = Some(top);
+        self
+    }
+
+    /// Shows times in Jira notation instead of hours and minutes.
+    pub fn with_time_tracking(mut self, time_tracking: TimeTrackingConfig) -> Self {
+        self.time_tracking = Some(time_tracking);
+        self
+    }
+
+    fn title(&self) -> &'static str {
+        match self.breakdown.grouping {
+            BreakdownGrouping::Issue => "Time by issue",
+            BreakdownGrouping::Project => "Time by project",
+            BreakdownGrouping::Parent => "Time by parent",
+        }
+    }
+}
+
+impl<'a> Display for BreakdownReport<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let entries = match self.top {
+            Some(top) => self.breakdown.top(top),
+            None => &self.breakdown.entries,
+        };
+        writeln!(f, "{}:", self.title())?;
+        for entry in entries {
+            let time = format_duration(entry.spent_time, self.time_tracking.as_ref());
+            if entry.label == entry.key {
+                writeln!(f, "{:>9} {:>6.1}%  {}", time, entry.percentage, entry.key)?;
+            } else {
+                writeln!(
+                    f,
+                    "{:>9} {:>6.1}%  {} {}",
+                    time, entry.percentage, entry.key, entry.label
+                )?;
+            }
+        }
+        let total = format_duration(self.breakdown.total, self.time_tracking.as_ref());
+        write!(f, "{:>9} {:>6.1}%  Total", total, 100.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jira::Worklog;
+    use crate::test_helpers::started_at;
+    use chrono::{Duration, NaiveDate};
+
+    #[test]
+    fn report_lists_top_entries_and_total() {
+        let date = NaiveDate::from_ymd(2022, 9, 15);
+        let worklogs = [
+            Worklog::new(started_at(date), "PROJ-1", "Story 1", Duration::hours(6)),
+            Worklog::new(started_at(date), "OPS-7", "Support", Duration::minutes(90)),
+            Worklog::new(started_at(date), "PROJ-2", "Story 2", Duration::minutes(30)),
+        ];
+        let breakdown = TimeBreakdown::new(BreakdownGrouping::Issue, worklogs.iter());
+
+        let uut = BreakdownReport::new(&breakdown).with_top(2);
+
+        assert_eq!(
+            uut.to_string(),
+            [
+                "Time by issue:",
+                "       6h   75.0%  PROJ-1 Story 1",
+                "   1h 30m   18.8%  OPS-7 Support",
+                "       8h  100.0%  Total",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn report_formats_times_with_time_tracking_config() {
+        let date = NaiveDate::from_ymd(2022, 9, 15);
+        let worklogs = [
+            Worklog::new(started_at(date), "PROJ-1", "PROJ-1", Duration::hours(9)),
+            Worklog::new(started_at(date), "OPS-7", "OPS-7", Duration::minutes(90)),
+        ];
+        let breakdown = TimeBreakdown::new(BreakdownGrouping::Issue, worklogs.iter());
+
+        let uut = BreakdownReport::new(&breakdown)
+            .with_time_tracking(TimeTrackingConfig::new(7.5, 5.0).unwrap());
+
+        assert_eq!(
+            uut.to_string(),
+            [
+                "Time by issue:",
+                "1d 1h 30m   85.7%  PROJ-1",
+                "   1h 30m   14.3%  OPS-7",
+                "    1d 3h  100.0%  Total",
+            ]
+            .join("\n")
+        );
+    }
+}
@@ -0,0 +1,476 @@
+use crate::jira::{format_duration, TimeTrackingConfig};
+use crate::time_tracker::{UserWorklogsSummary, WorkSchedule};
+use chrono::{Duration, NaiveDate};
+use std::collections::BTreeMap;
+
+const ANSI_RESET: &str = "\x1b[0m";
+const TEAM_LABEL: &str = "Team";
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ComplianceStatus {
+    Met,
+    Under,
+    Over,
+    DayOff,
+}
+
+impl ComplianceStatus {
+    fn of(logged: Duration, expected: Duration) -> Self {
+        if expected.is_zero() && logged.is_zero() {
+            ComplianceStatus::DayOff
+        } else if logged < expected {
+            ComplianceStatus::Under
+        } else if logged > expected {
+            ComplianceStatus::Over
+        } else {
+            ComplianceStatus::Met
+        }
+    }
+
+    /// Worst of the statuses: any shortfall is `Under`, else any overtime is `Over`, so
+    /// overtime on one day never hides a shortfall on another.
+    fn combine(statuses: impl Iterator<Item = Self>) -> Self {
+        statuses.fold(ComplianceStatus::DayOff, |combined, status| {
+            match (combined, status) {
+                (ComplianceStatus::Under, _) | (_, ComplianceStatus::Under) => {
+                    ComplianceStatus::Under
+                }
+                (ComplianceStatus::Over, _) | (_, ComplianceStatus::Over) => ComplianceStatus::Over,
+                (ComplianceStatus::Met, _) | (_, ComplianceStatus::Met) => ComplianceStatus::Met,
+                _ => ComplianceStatus::DayOff,
+            }
+        })
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            ComplianceStatus::Met => "met",
+            ComplianceStatus::Under => "under",
+            ComplianceStatus::Over => "over",
+            ComplianceStatus::DayOff => "day-off",
+        }
+    }
+
+    fn ansi_color(&self) -> &'static str {
+        match self {
+            ComplianceStatus::Met => "\x1b[32m",
+            ComplianceStatus::Under => "\x1b[31m",
+            ComplianceStatus::Over => "\x1b[33m",
+            ComplianceStatus::DayOff => "\x1b[90m",
+        }
+    }
+
+    fn html_color(&self) -> &'static str {
+        match self {
+            ComplianceStatus::Met => "#c8e6c9",
+            ComplianceStatus::Under => "#ffcdd2",
+            ComplianceStatus::Over => "#fff9c4",
+            ComplianceStatus::DayOff => "#eeeeee",
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct ComplianceCell {
+    pub logged: Duration,
+    pub expected: Duration,
+    pub status: ComplianceStatus,
+}
+
+impl ComplianceCell {
+    fn new(logged: Duration, expected: Duration) -> Self {
+        Self {
+            logged,
+            expected,
+            status: ComplianceStatus::of(logged, expected),
+        }
+    }
+
+    /// Sums the cells; the status is combined from theirs rather than from the sums.
+    fn total<'a>(cells: impl Iterator<Item = &'a ComplianceCell> + Clone) -> Self {
+        let (logged, expected) = cells.clone().fold(
+            (Duration::zero(), Duration::zero()),
+            |(logged, expected), cell| (logged + cell.logged, expected + cell.expected),
+        );
+        Self {
+            logged,
+            expected,
+            status: ComplianceStatus::combine(cells.map(|cell| cell.status)),
+        }
+    }
+
+    fn text(&self, time_tracking: Option<&TimeTrackingConfig>) -> String {
+        format!(
+            "{}/{}",
+            format_duration(self.logged, time_tracking),
+            format_duration(self.expected, time_tracking)
+        )
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ComplianceRow {
+    pub user: String,
+    pub cells: Vec<ComplianceCell>,
+    pub total: ComplianceCell,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ComplianceMatrix {
+    pub dates: Vec<NaiveDate>,
+    pub rows: Vec<ComplianceRow>,
+    pub day_totals: Vec<ComplianceCell>,
+    pub total: ComplianceCell,
+    pub time_tracking: Option<TimeTrackingConfig>,
+}
+
+impl ComplianceMatrix {
+    /// Builds users × days matrix. Dates are the union of days present in the summaries.
+    pub fn new(summaries: &BTreeMap<String, UserWorklogsSummary>, schedule: &WorkSchedule) -> Self {
+        let mut dates: Vec<NaiveDate> = summaries
+            .values()
+            .flat_map(|summary| summary.keys().copied())
+            .collect();
+        dates.sort();
+        dates.dedup();
+
+        let rows: Vec<ComplianceRow> = summaries
+            .iter()
+            .map(|(user, summary)| {
+                let cells: Vec<ComplianceCell> = dates
+                    .iter()
+                    .map(|date| {
+                        ComplianceCell::new(
+                            summary
+                                .get(date)
+                                .map(|worklog_summary| worklog_summary.spent_time)
+                                .unwrap_or_else(Duration::zero),
+                            schedule.expected_time(date),
+                        )
+                    })
+                    .collect();
+                ComplianceRow {
+                    user: user.clone(),
+                    total: ComplianceCell::total(cells.iter()),
+                    cells,
+                }
+            })
+            .collect();
+
+        let day_totals: Vec<ComplianceCell> = (0..dates.len())
+            .map(|index| ComplianceCell::total(rows.iter().map(|row| &row.cells[index])))
+            .collect();
+        let total = ComplianceCell::total(rows.iter().flat_map(|row| row.cells.iter()));
+
+        Self {
+            dates,
+            rows,
+            day_totals,
+            total,
+            time_tracking: None,
+        }
+    }
+
+    /// Shows terminal and HTML cells in Jira notation instead of decimal hours.
+    pub fn with_time_tracking(mut self, time_tracking: TimeTrackingConfig) -> Self {
+        self.time_tracking = Some(time_tracking);
+        self
+    }
+
+    fn header(&self) -> Vec<String> {
+        std::iter::once("User".to_string())
+            .chain(
+                self.dates
+                    .iter()
+                    .map(|date| date.format("%m-%d").to_string()),
+            )
+            .chain(std::iter::once("Total".to_string()))
+            .collect()
+    }
+
+    /// User rows followed by the team totals row, which has no user.
+    fn lines(&self) -> Vec<(Option<&str>, Vec<ComplianceCell>)> {
+        self.rows
+            .iter()
+            .map(|row| {
+                let mut cells = row.cells.clone();
+                cells.push(row.total);
+                (Some(row.user.as_str()), cells)
+            })
+            .chain(std::iter::once({
+                let mut cells = self.day_totals.clone();
+                cells.push(self.total);
+                (None, cells)
+            }))
+            .collect()
+    }
+
+    /// Renders aligned table with `logged/expected` hours, optionally colored with ANSI codes.
+    pub fn to_terminal(&self, colored: bool) -> String {
+        let header = self.header();
+        let lines = self.lines();
+        let user_width = lines
+            .iter()
+            .map(|(user, _)| user.unwrap_or(TEAM_LABEL).chars().count())
+            .chain(std::iter::once(header[0].len()))
+            .max()
+            .unwrap_or_default();
+        let cell_width = lines
+            .iter()
+            .flat_map(|(_, cells)| {
+                cells
+                    .iter()
+                    .map(|cell| cell.text(self.time_tracking.as_ref()).len())
+            })
+            .chain(header.iter().skip(1).map(String::len))
+            .max()
+            .unwrap_or_default();
+
+        let mut output = format!("{:<width$}", header[0], width = user_width);
+        for title in header.iter().skip(1) {
+            output += &format!(" {:>width$}", title, width = cell_width);
+        }
+        for (user, cells) in lines {
+            output += &format!(
+                "\n{:<width$}",
+                user.unwrap_or(TEAM_LABEL),
+                width = user_width
+            );
+            for cell in cells {
+                let text = format!(
+                    "{:>width$}",
+                    cell.text(self.time_tracking.as_ref()),
+                    width = cell_width
+                );
+                if colored {
+                    output += &format!(" {}{}{}", cell.status.ansi_color(), text, ANSI_RESET);
+                } else {
+                    output += &format!(" {}", text);
+                }
+            }
+        }
+        output
+    }
+
+    pub fn to_html(&self) -> String {
+        let mut output = String::from("<table class=\"compliance-matrix\">\n<tr>");
+        for title in self.header() {
+            output += &format!("<th>{}</th>", escape_html(&title));
+        }
+        output += "</tr>\n";
+        for (user, cells) in self.lines() {
+            output += &match user {
+                Some(user) => format!("<tr><th>{}</th>", escape_html(user)),
+                None => format!("<tr class=\"team-total\"><th>{}</th>", TEAM_LABEL),
+            };
+            for cell in cells {
+                output += &format!(
+                    "<td class=\"{}\" style=\"background-color: {}\">{}</td>",
+                    cell.status.name(),
+                    cell.status.html_color(),
+                    cell.text(self.time_tracking.as_ref())
+                );
+            }
+            output += "</tr>\n";
+        }
+        output + "</table>"
+    }
+
+    /// One record per user and date (plus totals) with hours as decimal numbers. Team totals
+    /// have scope `team` and an empty user, so they never collide with a user's records.
+    pub fn to_csv(&self) -> anyhow::Result<String> {
+        let mut writer = csv::Writer::from_writer(vec![]);
+        writer.write_record([
+            "scope",
+            "user",
+            "date",
+            "logged_hours",
+            "expected_hours",
+            "status",
+        ])?;
+        for (user, cells) in self.lines() {
+            let dates = self
+                .dates
+                .iter()
+                .map(|date| date.to_string())
+                .chain(std::iter::once("total".to_string()));
+            for (date, cell) in dates.zip(cells) {
+                writer.write_record([
+                    if user.is_some() { "user" } else { "team" },
+                    user.unwrap_or_default(),
+                    date.as_str(),
+                    &to_hours(cell.logged).to_string(),
+                    &to_hours(cell.expected).to_string(),
+                    cell.status.name(),
+                ])?;
+            }
+        }
+        Ok(String::from_utf8(writer.into_inner()?)?)
+    }
+}
+
+fn to_hours(duration: Duration) -> f64 {
+    (duration.num_seconds() as f64 / 36.0).round() / 100.0
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::time_tracker::WorklogSummary;
+    use chrono::Weekday;
+
+    fn summary(entries: &[(NaiveDate, i64)]) -> UserWorklogsSummary {
+        entries
+            .iter()
+            .map(|(date, hours)| {
+                (
+                    *date,
+                    WorklogSummary {
+                        spent_time: Duration::hours(*hours),
+                        worklogs: vec![],
+                    },
+                )
+            })
+            .collect()
+    }
+
+    fn create_uut() -> ComplianceMatrix {
+        let friday = NaiveDate::from_ymd(2022, 9, 16);
+        let saturday = NaiveDate::from_ymd(2022, 9, 17);
+        let summaries = BTreeMap::from([
+            ("alice".to_string(), summary(&[(friday, 8), (saturday, 0)])),
+            ("bob<b>".to_string(), summary(&[(friday, 6), (saturday, 2)])),
+        ]);
+        let schedule = WorkSchedule::new(Duration::hours(8)).with_working_days(&[
+            Weekday::Mon,
+            Weekday::Tue,
+            Weekday::Wed,
+            Weekday::Thu,
+            Weekday::Fri,
+        ]);
+        ComplianceMatrix::new(&summaries, &schedule)
+    }
+
+    #[test]
+    fn new_computes_statuses_and_totals() {
+        let uut = create_uut();
+
+        assert_eq!(uut.rows[0].cells[0].status, ComplianceStatus::Met);
+        assert_eq!(uut.rows[0].cells[1].status, ComplianceStatus::DayOff);
+        assert_eq!(uut.rows[1].cells[0].status, ComplianceStatus::Under);
+        assert_eq!(uut.rows[1].cells[1].status, ComplianceStatus::Over);
+        assert_eq!(
+            uut.rows[0].total,
+            ComplianceCell::new(Duration::hours(8), Duration::hours(8))
+        );
+        assert_eq!(
+            uut.rows[1].total,
+            ComplianceCell {
+                logged: Duration::hours(8),
+                expected: Duration::hours(8),
+                status: ComplianceStatus::Under,
+            }
+        );
+        assert_eq!(
+            uut.day_totals
+                .iter()
+                .map(|cell| (cell.logged, cell.expected, cell.status))
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    Duration::hours(14),
+                    Duration::hours(16),
+                    ComplianceStatus::Under
+                ),
+                (Duration::hours(2), Duration::zero(), ComplianceStatus::Over),
+            ]
+        );
+        assert_eq!(
+            uut.total,
+            ComplianceCell {
+                logged: Duration::hours(16),
+                expected: Duration::hours(16),
+                status: ComplianceStatus::Under,
+            }
+        );
+    }
+
+    #[test]
+    fn team_totals_do_not_collide_with_user_named_team() {
+        let friday = NaiveDate::from_ymd(2022, 9, 16);
+        let summaries = BTreeMap::from([("Team".to_string(), summary(&[(friday, 8)]))]);
+        let uut = ComplianceMatrix::new(&summaries, &WorkSchedule::new(Duration::hours(8)));
+
+        let csv = uut.to_csv().unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(
+            lines[1..],
+            [
+                "user,Team,2022-09-16,8,8,met",
+                "user,Team,total,8,8,met",
+                "team,,2022-09-16,8,8,met",
+                "team,,total,8,8,met",
+            ]
+        );
+        assert!(uut
+            .to_html()
+            .contains("<tr class=\"team-total\"><th>Team</th>"));
+    }
+
+    #[test]
+    fn to_terminal_renders_aligned_table() {
+        assert_eq!(
+            create_uut().to_terminal(false),
+            [
+                "User     09-16   09-17   Total",
+                "alice    8h/8h   0m/0m   8h/8h",
+                "bob<b>   6h/8h   2h/0m   8h/8h",
+                "Team   14h/16h   2h/0m 16h/16h",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn to_terminal_uses_time_tracking_config() {
+        assert_eq!(
+            create_uut()
+                .with_time_tracking(TimeTrackingConfig::default())
+                .to_terminal(false),
+            [
+                "User      09-16    09-17    Total",
+                "alice     1d/1d    0m/0m    1d/1d",
+                "bob<b>    6h/1d    2h/0m    1d/1d",
+                "Team   1d 6h/2d    2h/0m    2d/2d",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn to_html_and_to_csv_render_statuses() {
+        let uut = create_uut();
+
+        let html = uut.to_html();
+        assert!(html.contains("<th>bob&lt;b&gt;</th><td class=\"under\""));
+        assert!(html.ends_with("</table>"));
+
+        let csv = uut.to_csv().unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(
+            lines[0],
+            "scope,user,date,logged_hours,expected_hours,status"
+        );
+        assert_eq!(lines[1], "user,alice,2022-09-16,8,8,met");
+        assert_eq!(lines[5], "user,bob<b>,2022-09-17,2,0,over");
+        assert_eq!(lines[6], "user,bob<b>,total,8,8,under");
+        assert_eq!(lines[9], "team,,total,16,16,under");
+    }
+}
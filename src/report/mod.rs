@@ -0,0 +1,5 @@
+mod breakdown_report;
+mod compliance_matrix;
+
+pub use breakdown_report::BreakdownReport;
+pub use compliance_matrix::{ComplianceCell, ComplianceMatrix, ComplianceRow, ComplianceStatus};
@@ -0,0 +1,133 @@
+use super::{
+    rule_trait::RuleTrait,
+    severity::Severity,
+    violation::{Violation, ViolationKind},
+};
+use crate::time_tracker::UserWorklogsSummary;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ClosedIssueRule {
+    pub closed_statuses: Vec<String>,
+    pub severity: Severity,
+}
+
+impl ClosedIssueRule {
+    const NAME: &'static str = "closed-issue";
+
+    pub fn new() -> Self {
+        Self {
+            closed_statuses: vec![],
+            severity: Severity::Warning,
+        }
+    }
+
+    pub fn with_closed_statuses(mut self, closed_statuses: &[&str]) -> Self {
+        self.closed_statuses = closed_statuses
+            .iter()
+            .map(|status| status.to_string())
+            .collect();
+        self
+    }
+
+    pub fn with_severity(mut self, severity: Severity) -> Self {
+        self.severity = severity;
+        self
+    }
+}
+
+impl Default for ClosedIssueRule {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RuleTrait for ClosedIssueRule {
+    fn name(&self) -> &str {
+        Self::NAME
+    }
+
+    fn evaluate(&self, summary: &UserWorklogsSummary) -> Vec<Violation> {
+        summary
+            .iter()
+            .flat_map(|(date, worklog_summary)| {
+                worklog_summary.worklogs.iter().filter_map(|worklog| {
+                    let issue = worklog.issue.as_ref()?;
+                    let status = issue.status.clone().unwrap_or_default();
+                    (issue.is_done() || self.closed_statuses.contains(&status)).then(|| {
+                        Violation::new(
+                            self.name(),
+                            self.severity,
+                            *date,
+                            ViolationKind::ClosedIssueWorklog {
+                                issue_key: worklog.issue_key.clone(),
+                                status,
+                            },
+                        )
+                    })
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jira::{IssueInfo, Worklog};
+    use crate::test_helpers::started_at;
+    use crate::time_tracker::WorklogSummary;
+    use chrono::{Duration, NaiveDate};
+
+    fn create_worklog(date: NaiveDate, issue_key: &str, status: &str, category: &str) -> Worklog {
+        Worklog::new(started_at(date), issue_key, "summary", Duration::hours(2)).with_issue(
+            IssueInfo {
+                status: Some(status.to_string()),
+                status_category: Some(category.to_string()),
+                ..Default::default()
+            },
+        )
+    }
+
+    #[test]
+    fn evaluate_returns_violation_for_worklogs_on_done_or_closed_issues() {
+        let date = NaiveDate::from_ymd(2022, 9, 15);
+        let mut summary = UserWorklogsSummary::new();
+        summary.insert(
+            date,
+            WorklogSummary {
+                spent_time: Duration::hours(8),
+                worklogs: vec![
+                    create_worklog(date, "key1", "In Progress", "indeterminate"),
+                    create_worklog(date, "key2", "Done", "done"),
+                    create_worklog(date, "key3", "Rejected", "indeterminate"),
+                    Worklog::new(started_at(date), "key4", "summary", Duration::hours(2)),
+                ],
+            },
+        );
+
+        let uut = ClosedIssueRule::new().with_closed_statuses(&["Rejected"]);
+        assert_eq!(
+            uut.evaluate(&summary),
+            vec![
+                Violation::new(
+                    "closed-issue",
+                    Severity::Warning,
+                    date,
+                    ViolationKind::ClosedIssueWorklog {
+                        issue_key: "key2".to_string(),
+                        status: "Done".to_string(),
+                    },
+                ),
+                Violation::new(
+                    "closed-issue",
+                    Severity::Warning,
+                    date,
+                    ViolationKind::ClosedIssueWorklog {
+                        issue_key: "key3".to_string(),
+                        status: "Rejected".to_string(),
+                    },
+                ),
+            ]
+        );
+    }
+}
@@ -0,0 +1,136 @@
+use super::{
+    rule_trait::RuleTrait,
+    severity::Severity,
+    violation::{Violation, ViolationKind},
+};
+use crate::time_tracker::{UserWorklogsSummary, WorkSchedule};
+use chrono::{Duration, Weekday};
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ExpectedDailyTimeRule {
+    pub schedule: WorkSchedule,
+    pub severity: Severity,
+}
+
+impl ExpectedDailyTimeRule {
+    const NAME: &'static str = "expected-daily-time";
+
+    pub fn new(expected: Duration) -> Self {
+        Self {
+            schedule: WorkSchedule::new(expected),
+            severity: Severity::Error,
+        }
+    }
+
+    pub fn with_working_days(mut self, working_days: &[Weekday]) -> Self {
+        self.schedule = self.schedule.with_working_days(working_days);
+        self
+    }
+
+    pub fn with_severity(mut self, severity: Severity) -> Self {
+        self.severity = severity;
+        self
+    }
+}
+
+impl RuleTrait for ExpectedDailyTimeRule {
+    fn name(&self) -> &str {
+        Self::NAME
+    }
+
+    fn evaluate(&self, summary: &UserWorklogsSummary) -> Vec<Violation> {
+        summary
+            .iter()
+            .filter(|(date, _)| self.schedule.is_working_day(date))
+            .filter(|(_, worklog_summary)| worklog_summary.spent_time != self.schedule.daily_time)
+            .map(|(date, worklog_summary)| {
+                Violation::new(
+                    self.name(),
+                    self.severity,
+                    *date,
+                    ViolationKind::UnexpectedDailyTime {
+                        expected: self.schedule.daily_time,
+                        logged: worklog_summary.spent_time,
+                    },
+                )
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::time_tracker::WorklogSummary;
+    use chrono::NaiveDate;
+    use lazy_static::lazy_static;
+
+    lazy_static! {
+        static ref THURSDAY: NaiveDate = NaiveDate::from_ymd(2022, 9, 15);
+        static ref FRIDAY: NaiveDate = NaiveDate::from_ymd(2022, 9, 16);
+        static ref SATURDAY: NaiveDate = NaiveDate::from_ymd(2022, 9, 17);
+    }
+
+    fn create_summary(days: &[(NaiveDate, Duration)]) -> UserWorklogsSummary {
+        let mut summary = UserWorklogsSummary::new();
+        days.iter().for_each(|(date, spent_time)| {
+            summary.insert(
+                *date,
+                WorklogSummary {
+                    spent_time: *spent_time,
+                    worklogs: vec![],
+                },
+            );
+        });
+        summary
+    }
+
+    #[test]
+    fn evaluate_returns_no_violations_when_every_day_has_expected_time() {
+        let uut = ExpectedDailyTimeRule::new(Duration::hours(8));
+        let summary = create_summary(&[
+            (*THURSDAY, Duration::hours(8)),
+            (*FRIDAY, Duration::hours(8)),
+        ]);
+
+        assert!(uut.evaluate(&summary).is_empty());
+    }
+
+    #[test]
+    fn evaluate_returns_violation_for_day_with_unexpected_time() {
+        let uut = ExpectedDailyTimeRule::new(Duration::hours(8));
+        let summary = create_summary(&[
+            (*THURSDAY, Duration::hours(8)),
+            (*FRIDAY, Duration::hours(7)),
+        ]);
+
+        assert_eq!(
+            uut.evaluate(&summary),
+            vec![Violation::new(
+                "expected-daily-time",
+                Severity::Error,
+                *FRIDAY,
+                ViolationKind::UnexpectedDailyTime {
+                    expected: Duration::hours(8),
+                    logged: Duration::hours(7),
+                },
+            )]
+        );
+    }
+
+    #[test]
+    fn evaluate_skips_days_which_are_not_working_days() {
+        let uut = ExpectedDailyTimeRule::new(Duration::hours(8)).with_working_days(&[
+            Weekday::Mon,
+            Weekday::Tue,
+            Weekday::Thu,
+            Weekday::Fri,
+        ]);
+        let summary = create_summary(&[
+            (*THURSDAY, Duration::hours(8)),
+            (*SATURDAY, Duration::hours(0)),
+        ]);
+
+        assert!(uut.evaluate(&summary).is_empty());
+    }
+}
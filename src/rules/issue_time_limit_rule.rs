@@ -0,0 +1,122 @@
+use super::{
+    rule_trait::RuleTrait,
+    severity::Severity,
+    violation::{Violation, ViolationKind},
+};
+use crate::time_tracker::UserWorklogsSummary;
+use chrono::Duration;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct IssueTimeLimitRule {
+    pub name: String,
+    pub issue_keys: Vec<String>,
+    pub daily_limit: Duration,
+    pub severity: Severity,
+}
+
+impl IssueTimeLimitRule {
+    pub fn new(name: &str, issue_keys: &[&str], daily_limit: Duration) -> Self {
+        Self {
+            name: name.to_string(),
+            issue_keys: issue_keys.iter().map(|key| key.to_string()).collect(),
+            daily_limit,
+            severity: Severity::Warning,
+        }
+    }
+
+    pub fn with_severity(mut self, severity: Severity) -> Self {
+        self.severity = severity;
+        self
+    }
+}
+
+impl RuleTrait for IssueTimeLimitRule {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn evaluate(&self, summary: &UserWorklogsSummary) -> Vec<Violation> {
+        summary
+            .iter()
+            .filter_map(|(date, worklog_summary)| {
+                let logged = worklog_summary
+                    .worklogs
+                    .iter()
+                    .filter(|worklog| self.issue_keys.contains(&worklog.issue_key))
+                    .fold(Duration::seconds(0), |acc, worklog| {
+                        acc + worklog.time_spent
+                    });
+                (logged > self.daily_limit).then(|| {
+                    Violation::new(
+                        self.name(),
+                        self.severity,
+                        *date,
+                        ViolationKind::TimeLimitExceeded {
+                            limit: self.daily_limit,
+                            logged,
+                        },
+                    )
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jira::Worklog;
+    use crate::test_helpers::started_at;
+    use crate::time_tracker::WorklogSummary;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn evaluate_returns_violation_when_time_on_limited_issues_exceeds_limit() {
+        let date = NaiveDate::from_ymd(2022, 9, 15);
+        let mut summary = UserWorklogsSummary::new();
+        summary.insert(
+            date,
+            WorklogSummary {
+                spent_time: Duration::hours(8),
+                worklogs: vec![
+                    Worklog::new(started_at(date), "MEET-1", "Planning", Duration::hours(3)),
+                    Worklog::new(started_at(date), "MEET-2", "Retro", Duration::hours(2)),
+                    Worklog::new(started_at(date), "DEV-1", "Coding", Duration::hours(3)),
+                ],
+            },
+        );
+
+        let uut = IssueTimeLimitRule::new("meetings", &["MEET-1", "MEET-2"], Duration::hours(4));
+        assert_eq!(
+            uut.evaluate(&summary),
+            vec![Violation::new(
+                "meetings",
+                Severity::Warning,
+                date,
+                ViolationKind::TimeLimitExceeded {
+                    limit: Duration::hours(4),
+                    logged: Duration::hours(5),
+                },
+            )]
+        );
+    }
+
+    #[test]
+    fn evaluate_returns_no_violations_when_time_on_limited_issues_is_within_limit() {
+        let date = NaiveDate::from_ymd(2022, 9, 15);
+        let mut summary = UserWorklogsSummary::new();
+        summary.insert(
+            date,
+            WorklogSummary {
+                spent_time: Duration::hours(8),
+                worklogs: vec![
+                    Worklog::new(started_at(date), "MEET-1", "Planning", Duration::hours(4)),
+                    Worklog::new(started_at(date), "DEV-1", "Coding", Duration::hours(4)),
+                ],
+            },
+        );
+
+        let uut = IssueTimeLimitRule::new("meetings", &["MEET-1"], Duration::hours(4));
+        assert!(uut.evaluate(&summary).is_empty());
+    }
+}
@@ -0,0 +1,28 @@
+mod closed_issue_rule;
+mod expected_daily_time_rule;
+mod issue_time_limit_rule;
+mod period_expected_time_rule;
+mod policy;
+mod policy_config;
+mod rule_trait;
+mod severity;
+mod violation;
+mod weekend_worklogs_rule;
+mod worklog_comment_rule;
+
+pub use closed_issue_rule::ClosedIssueRule;
+pub use expected_daily_time_rule::ExpectedDailyTimeRule;
+pub use issue_time_limit_rule::IssueTimeLimitRule;
+pub use period_expected_time_rule::PeriodExpectedTimeRule;
+pub use policy::Policy;
+pub use policy_config::PolicyConfig;
+pub use rule_trait::RuleTrait;
+pub use severity::Severity;
+pub use violation::{Violation, ViolationKind};
+pub use weekend_worklogs_rule::WeekendWorklogsRule;
+pub use worklog_comment_rule::WorklogCommentRule;
+
+#[cfg(test)]
+pub mod testing {
+    pub use super::rule_trait::MockRuleTrait;
+}
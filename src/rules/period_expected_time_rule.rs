@@ -0,0 +1,122 @@
+use super::{
+    rule_trait::RuleTrait,
+    severity::Severity,
+    violation::{Violation, ViolationKind},
+};
+use crate::time_tracker::{PeriodGranularity, UserWorklogsSummary, WorkSchedule};
+use chrono::Duration;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct PeriodExpectedTimeRule {
+    pub granularity: PeriodGranularity,
+    pub schedule: WorkSchedule,
+    pub opening_balance: Duration,
+    pub severity: Severity,
+}
+
+impl PeriodExpectedTimeRule {
+    const NAME: &'static str = "period-expected-time";
+
+    pub fn new(granularity: PeriodGranularity, schedule: WorkSchedule) -> Self {
+        Self {
+            granularity,
+            schedule,
+            opening_balance: Duration::seconds(0),
+            severity: Severity::Error,
+        }
+    }
+
+    pub fn with_opening_balance(mut self, opening_balance: Duration) -> Self {
+        self.opening_balance = opening_balance;
+        self
+    }
+
+    pub fn with_severity(mut self, severity: Severity) -> Self {
+        self.severity = severity;
+        self
+    }
+}
+
+impl RuleTrait for PeriodExpectedTimeRule {
+    fn name(&self) -> &str {
+        Self::NAME
+    }
+
+    // Overtime is carried over to the next periods, so only a negative running balance
+    // is reported.
+    fn evaluate(&self, summary: &UserWorklogsSummary) -> Vec<Violation> {
+        summary
+            .period_summaries(self.granularity, &self.schedule, self.opening_balance)
+            .into_iter()
+            .filter(|period_summary| period_summary.balance < Duration::seconds(0))
+            .map(|period_summary| {
+                Violation::new(
+                    self.name(),
+                    self.severity,
+                    period_summary.end_date,
+                    ViolationKind::UnexpectedPeriodTime {
+                        period: period_summary.period,
+                        expected: period_summary.expected,
+                        logged: period_summary.logged,
+                        balance: period_summary.balance,
+                    },
+                )
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::time_tracker::{Period, WorklogSummary};
+    use chrono::NaiveDate;
+
+    #[test]
+    fn evaluate_reports_only_periods_which_end_with_negative_balance() {
+        let monday = NaiveDate::from_ymd(2022, 9, 12);
+        let spent_times = [9, 7, 8, 8, 8, 0, 0, 6, 8];
+        let mut summary = UserWorklogsSummary::new();
+        monday
+            .iter_days()
+            .zip(spent_times)
+            .for_each(|(date, hours)| {
+                summary.insert(
+                    date,
+                    WorklogSummary {
+                        spent_time: Duration::hours(hours),
+                        worklogs: vec![],
+                    },
+                );
+            });
+
+        let uut = PeriodExpectedTimeRule::new(
+            PeriodGranularity::Week,
+            WorkSchedule::new(Duration::hours(8)).with_working_days(&[
+                chrono::Weekday::Mon,
+                chrono::Weekday::Tue,
+                chrono::Weekday::Wed,
+                chrono::Weekday::Thu,
+                chrono::Weekday::Fri,
+            ]),
+        );
+
+        assert_eq!(
+            uut.evaluate(&summary),
+            vec![Violation::new(
+                "period-expected-time",
+                Severity::Error,
+                NaiveDate::from_ymd(2022, 9, 20),
+                ViolationKind::UnexpectedPeriodTime {
+                    period: Period::Week {
+                        year: 2022,
+                        week: 38
+                    },
+                    expected: Duration::hours(16),
+                    logged: Duration::hours(14),
+                    balance: Duration::hours(-2),
+                },
+            )]
+        );
+    }
+}
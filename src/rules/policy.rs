@@ -0,0 +1,359 @@
+use super::{
+    policy_config::PolicyConfig, rule_trait::RuleTrait, severity::Severity, violation::Violation,
+};
+use crate::time_tracker::{ClockTrait, SystemClock, UserWorklogsSummary, WorkSchedule};
+use chrono::{Local, NaiveDate, NaiveDateTime, NaiveTime};
+use chrono_tz::Tz;
+
+pub struct Policy {
+    alert_severity: Severity,
+    today_cutoff: Option<NaiveTime>,
+    exclude_future: bool,
+    clock: Box<dyn ClockTrait>,
+    schedule: Option<WorkSchedule>,
+    rules: Vec<Box<dyn RuleTrait>>,
+}
+
+impl Policy {
+    pub fn new(config: PolicyConfig) -> Self {
+        let mut rules: Vec<Box<dyn RuleTrait>> = vec![];
+        let schedule = config
+            .expected_daily_time
+            .as_ref()
+            .map(|rule| rule.schedule.clone());
+        if let Some(rule) = config.expected_daily_time {
+            rules.push(Box::new(rule));
+        }
+        if let Some(rule) = config.period_expected_time {
+            rules.push(Box::new(rule));
+        }
+        if let Some(rule) = config.weekend_worklogs {
+            rules.push(Box::new(rule));
+        }
+        if let Some(rule) = config.worklog_comment {
+            rules.push(Box::new(rule));
+        }
+        if let Some(rule) = config.closed_issue {
+            rules.push(Box::new(rule));
+        }
+        config
+            .issue_time_limits
+            .into_iter()
+            .for_each(|rule| rules.push(Box::new(rule)));
+
+        Self {
+            alert_severity: config.alert_severity,
+            today_cutoff: config.today_cutoff,
+            exclude_future: config.exclude_future,
+            clock: Box::new(SystemClock),
+            schedule,
+            rules,
+        }
+    }
+
+    pub fn with_clock(mut self, clock: Box<dyn ClockTrait>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    pub fn with_rule(mut self, rule: Box<dyn RuleTrait>) -> Self {
+        self.rules.push(rule);
+        self
+    }
+
+    /// Schedule of the expected daily time rule, if configured.
+    pub fn schedule(&self) -> Option<&WorkSchedule> {
+        self.schedule.as_ref()
+    }
+
+    /// `timezone` is the one the summary days are in (`SummaryTrait::timezone`), so that
+    /// today is determined the same way. With `None` days follow each worklog's own offset,
+    /// so today is taken in the offset of the day's worklogs, else of the latest worklog,
+    /// and only in the local timezone when the summary has no worklogs at all.
+    pub fn evaluate(&self, summary: &UserWorklogsSummary, timezone: Option<Tz>) -> Vec<Violation> {
+        let now = self.clock.now();
+        let latest_offset = summary
+            .values()
+            .flat_map(|worklog_summary| &worklog_summary.worklogs)
+            .max_by_key(|worklog| worklog.started)
+            .map(|worklog| *worklog.started.offset());
+
+        let evaluated_summary = summary
+            .iter()
+            .filter(|(date, worklog_summary)| {
+                let day_offset = worklog_summary
+                    .worklogs
+                    .first()
+                    .map(|worklog| *worklog.started.offset())
+                    .or(latest_offset);
+                let local_now = match (&timezone, day_offset) {
+                    (Some(timezone), _) => now.with_timezone(timezone).naive_local(),
+                    (None, Some(offset)) => now.with_timezone(&offset).naive_local(),
+                    (None, None) => now.with_timezone(&Local).naive_local(),
+                };
+                self.is_evaluated(date, &local_now)
+            })
+            .map(|(date, worklog_summary)| (*date, worklog_summary.clone()))
+            .collect();
+
+        let mut violations: Vec<_> = self
+            .rules
+            .iter()
+            .flat_map(|rule| rule.evaluate(&evaluated_summary))
+            .collect();
+        violations.sort_by_key(|violation| violation.date);
+        violations
+    }
+
+    fn is_evaluated(&self, date: &NaiveDate, now: &NaiveDateTime) -> bool {
+        let today = now.date();
+        if self.exclude_future && *date > today {
+            return false;
+        }
+        let today_is_incomplete = self
+            .today_cutoff
+            .is_some_and(|today_cutoff| now.time() < today_cutoff);
+        !(*date == today && today_is_incomplete)
+    }
+
+    pub fn requires_alert(&self, violations: &[Violation]) -> bool {
+        violations
+            .iter()
+            .any(|violation| violation.severity >= self.alert_severity)
+    }
+}
+
+impl Default for Policy {
+    fn default() -> Self {
+        Self::new(PolicyConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::{testing::MockRuleTrait, ExpectedDailyTimeRule, ViolationKind};
+    use crate::time_tracker::{testing::MockClockTrait, WorklogSummary};
+    use chrono::{Duration, NaiveDate, TimeZone, Utc};
+    use itertools::Itertools;
+
+    fn create_violation(date: NaiveDate, severity: Severity) -> Violation {
+        Violation::new(
+            "dummy-rule",
+            severity,
+            date,
+            ViolationKind::TimeLimitExceeded {
+                limit: Duration::hours(1),
+                logged: Duration::hours(2),
+            },
+        )
+    }
+
+    fn create_empty_config() -> PolicyConfig {
+        PolicyConfig {
+            expected_daily_time: None,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn evaluate_returns_violations_of_all_rules_ordered_by_date() {
+        let earlier_date = NaiveDate::from_ymd(2022, 9, 15);
+        let later_date = NaiveDate::from_ymd(2022, 9, 16);
+
+        let mut rule_1 = MockRuleTrait::new();
+        let rule_1_violation = create_violation(later_date, Severity::Error);
+        let expected_rule_1_violation = rule_1_violation.clone();
+        rule_1
+            .expect_evaluate()
+            .return_once(move |_| vec![rule_1_violation]);
+        let mut rule_2 = MockRuleTrait::new();
+        let rule_2_violation = create_violation(earlier_date, Severity::Info);
+        let expected_rule_2_violation = rule_2_violation.clone();
+        rule_2
+            .expect_evaluate()
+            .return_once(move |_| vec![rule_2_violation]);
+
+        let uut = Policy::new(create_empty_config())
+            .with_rule(Box::new(rule_1))
+            .with_rule(Box::new(rule_2));
+
+        assert_eq!(
+            uut.evaluate(&UserWorklogsSummary::new(), None),
+            vec![expected_rule_2_violation, expected_rule_1_violation]
+        );
+    }
+
+    #[test]
+    fn requires_alert_returns_true_only_for_violations_with_at_least_alert_severity() {
+        let date = NaiveDate::from_ymd(2022, 9, 15);
+        let uut = Policy::new(PolicyConfig {
+            alert_severity: Severity::Warning,
+            ..create_empty_config()
+        });
+
+        assert!(!uut.requires_alert(&[]));
+        assert!(!uut.requires_alert(&[create_violation(date, Severity::Info)]));
+        assert!(uut.requires_alert(&[
+            create_violation(date, Severity::Info),
+            create_violation(date, Severity::Warning)
+        ]));
+        assert!(uut.requires_alert(&[create_violation(date, Severity::Error)]));
+    }
+
+    fn create_clock(now: chrono::NaiveDateTime) -> Box<MockClockTrait> {
+        let now = Local.from_local_datetime(&now).unwrap().with_timezone(&Utc);
+        let mut clock = MockClockTrait::new();
+        clock.expect_now().return_const(now);
+        Box::new(clock)
+    }
+
+    fn create_empty_summary(start_date: NaiveDate, days: usize) -> UserWorklogsSummary {
+        start_date
+            .iter_days()
+            .take(days)
+            .map(|date| {
+                (
+                    date,
+                    WorklogSummary {
+                        spent_time: Duration::seconds(0),
+                        worklogs: vec![],
+                    },
+                )
+            })
+            .collect()
+    }
+
+    fn create_expected_time_config() -> PolicyConfig {
+        PolicyConfig {
+            expected_daily_time: Some(ExpectedDailyTimeRule::new(Duration::hours(8))),
+            ..Default::default()
+        }
+    }
+
+    fn violation_dates(violations: &[Violation]) -> Vec<NaiveDate> {
+        violations
+            .iter()
+            .map(|violation| violation.date)
+            .collect_vec()
+    }
+
+    #[test]
+    fn evaluate_skips_future_dates_when_excluded() {
+        let yesterday = NaiveDate::from_ymd(2022, 9, 14);
+        let today = NaiveDate::from_ymd(2022, 9, 15);
+        let uut = Policy::new(PolicyConfig {
+            exclude_future: true,
+            ..create_expected_time_config()
+        })
+        .with_clock(create_clock(today.and_hms(10, 0, 0)));
+
+        let violations = uut.evaluate(&create_empty_summary(yesterday, 4), None);
+
+        assert_eq!(violation_dates(&violations), vec![yesterday, today]);
+    }
+
+    #[test]
+    fn evaluate_checks_future_dates_when_not_excluded() {
+        let today = NaiveDate::from_ymd(2022, 9, 15);
+        let uut = Policy::new(PolicyConfig {
+            exclude_future: false,
+            ..create_expected_time_config()
+        })
+        .with_clock(create_clock(today.and_hms(10, 0, 0)));
+
+        let violations = uut.evaluate(&create_empty_summary(today, 3), None);
+
+        assert_eq!(violations.len(), 3);
+    }
+
+    #[test]
+    fn evaluate_skips_today_before_cutoff_time() {
+        let yesterday = NaiveDate::from_ymd(2022, 9, 14);
+        let today = NaiveDate::from_ymd(2022, 9, 15);
+        let uut = Policy::new(PolicyConfig {
+            today_cutoff: Some(NaiveTime::from_hms(17, 0, 0)),
+            ..create_expected_time_config()
+        })
+        .with_clock(create_clock(today.and_hms(10, 0, 0)));
+
+        let violations = uut.evaluate(&create_empty_summary(yesterday, 2), None);
+
+        assert_eq!(violation_dates(&violations), vec![yesterday]);
+    }
+
+    #[test]
+    fn evaluate_checks_today_after_cutoff_time() {
+        let yesterday = NaiveDate::from_ymd(2022, 9, 14);
+        let today = NaiveDate::from_ymd(2022, 9, 15);
+        let uut = Policy::new(PolicyConfig {
+            today_cutoff: Some(NaiveTime::from_hms(17, 0, 0)),
+            ..create_expected_time_config()
+        })
+        .with_clock(create_clock(today.and_hms(17, 30, 0)));
+
+        let violations = uut.evaluate(&create_empty_summary(yesterday, 2), None);
+
+        assert_eq!(violation_dates(&violations), vec![yesterday, today]);
+    }
+
+    #[test]
+    fn evaluate_determines_today_in_given_timezone() {
+        let today_in_tokyo = NaiveDate::from_ymd(2022, 9, 15);
+        let now = Utc.ymd(2022, 9, 15).and_hms(10, 0, 0);
+        let mut clock = MockClockTrait::new();
+        clock.expect_now().return_const(now);
+        let uut = Policy::new(PolicyConfig {
+            today_cutoff: Some(NaiveTime::from_hms(17, 0, 0)),
+            ..create_expected_time_config()
+        })
+        .with_clock(Box::new(clock));
+
+        let violations = uut.evaluate(
+            &create_empty_summary(today_in_tokyo, 2),
+            Some(chrono_tz::Asia::Tokyo),
+        );
+
+        assert_eq!(violation_dates(&violations), vec![today_in_tokyo]);
+    }
+
+    #[test]
+    fn evaluate_determines_today_in_offset_of_worklogs_without_timezone() {
+        let yesterday = NaiveDate::from_ymd(2022, 9, 14);
+        let today_in_tokyo = NaiveDate::from_ymd(2022, 9, 15);
+        let mut clock = MockClockTrait::new();
+        clock
+            .expect_now()
+            .return_const(Utc.ymd(2022, 9, 14).and_hms(20, 0, 0));
+        let uut = Policy::new(PolicyConfig {
+            today_cutoff: Some(NaiveTime::from_hms(17, 0, 0)),
+            exclude_future: false,
+            ..create_expected_time_config()
+        })
+        .with_clock(Box::new(clock));
+        let summary: UserWorklogsSummary = [yesterday, today_in_tokyo]
+            .into_iter()
+            .map(|date| {
+                let started = chrono::FixedOffset::east(9 * 3600)
+                    .from_local_datetime(&date.and_hms(9, 0, 0))
+                    .unwrap();
+                (
+                    date,
+                    WorklogSummary {
+                        spent_time: Duration::hours(1),
+                        worklogs: vec![crate::jira::Worklog::new(
+                            started,
+                            "PROJ-1",
+                            "summary",
+                            Duration::hours(1),
+                        )],
+                    },
+                )
+            })
+            .collect();
+
+        let violations = uut.evaluate(&summary, None);
+
+        assert_eq!(violation_dates(&violations), vec![yesterday]);
+    }
+}
@@ -0,0 +1,36 @@
+use super::{
+    closed_issue_rule::ClosedIssueRule, expected_daily_time_rule::ExpectedDailyTimeRule,
+    issue_time_limit_rule::IssueTimeLimitRule, period_expected_time_rule::PeriodExpectedTimeRule,
+    severity::Severity, weekend_worklogs_rule::WeekendWorklogsRule,
+    worklog_comment_rule::WorklogCommentRule,
+};
+use chrono::{Duration, NaiveTime};
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct PolicyConfig {
+    pub alert_severity: Severity,
+    pub today_cutoff: Option<NaiveTime>,
+    pub exclude_future: bool,
+    pub expected_daily_time: Option<ExpectedDailyTimeRule>,
+    pub period_expected_time: Option<PeriodExpectedTimeRule>,
+    pub weekend_worklogs: Option<WeekendWorklogsRule>,
+    pub worklog_comment: Option<WorklogCommentRule>,
+    pub closed_issue: Option<ClosedIssueRule>,
+    pub issue_time_limits: Vec<IssueTimeLimitRule>,
+}
+
+impl Default for PolicyConfig {
+    fn default() -> Self {
+        Self {
+            alert_severity: Severity::Warning,
+            today_cutoff: None,
+            exclude_future: true,
+            expected_daily_time: Some(ExpectedDailyTimeRule::new(Duration::hours(8))),
+            period_expected_time: None,
+            weekend_worklogs: None,
+            worklog_comment: None,
+            closed_issue: None,
+            issue_time_limits: vec![],
+        }
+    }
+}
@@ -0,0 +1,11 @@
+use super::violation::Violation;
+use crate::time_tracker::UserWorklogsSummary;
+
+#[cfg(test)]
+use mockall::{automock, predicate::*};
+
+#[cfg_attr(test, automock)]
+pub trait RuleTrait {
+    fn name(&self) -> &str;
+    fn evaluate(&self, summary: &UserWorklogsSummary) -> Vec<Violation>;
+}
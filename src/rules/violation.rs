@@ -0,0 +1,199 @@
+use super::severity::Severity;
+use crate::jira::{format_duration, TimeTrackingConfig};
+use crate::time_tracker::Period;
+use chrono::{Duration, NaiveDate};
+use std::fmt::Display;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize),
+    serde(tag = "kind", rename_all = "snake_case")
+)]
+pub enum ViolationKind {
+    UnexpectedDailyTime {
+        #[cfg_attr(
+            feature = "serde",
+            serde(
+                rename = "expected_seconds",
+                with = "crate::serialization::duration_seconds"
+            )
+        )]
+        expected: Duration,
+        #[cfg_attr(
+            feature = "serde",
+            serde(
+                rename = "logged_seconds",
+                with = "crate::serialization::duration_seconds"
+            )
+        )]
+        logged: Duration,
+    },
+    WeekendWorklog {
+        issue_key: String,
+        #[cfg_attr(
+            feature = "serde",
+            serde(
+                rename = "time_spent_seconds",
+                with = "crate::serialization::duration_seconds"
+            )
+        )]
+        time_spent: Duration,
+    },
+    TimeLimitExceeded {
+        #[cfg_attr(
+            feature = "serde",
+            serde(
+                rename = "limit_seconds",
+                with = "crate::serialization::duration_seconds"
+            )
+        )]
+        limit: Duration,
+        #[cfg_attr(
+            feature = "serde",
+            serde(
+                rename = "logged_seconds",
+                with = "crate::serialization::duration_seconds"
+            )
+        )]
+        logged: Duration,
+    },
+    UnexpectedPeriodTime {
+        #[cfg_attr(
+            feature = "serde",
+            serde(with = "crate::serialization::display_string")
+        )]
+        period: Period,
+        #[cfg_attr(
+            feature = "serde",
+            serde(
+                rename = "expected_seconds",
+                with = "crate::serialization::duration_seconds"
+            )
+        )]
+        expected: Duration,
+        #[cfg_attr(
+            feature = "serde",
+            serde(
+                rename = "logged_seconds",
+                with = "crate::serialization::duration_seconds"
+            )
+        )]
+        logged: Duration,
+        #[cfg_attr(
+            feature = "serde",
+            serde(
+                rename = "balance_seconds",
+                with = "crate::serialization::duration_seconds"
+            )
+        )]
+        balance: Duration,
+    },
+    MissingComment {
+        issue_key: String,
+        #[cfg_attr(
+            feature = "serde",
+            serde(
+                rename = "time_spent_seconds",
+                with = "crate::serialization::duration_seconds"
+            )
+        )]
+        time_spent: Duration,
+    },
+    ClosedIssueWorklog {
+        issue_key: String,
+        status: String,
+    },
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Violation {
+    pub rule: String,
+    pub severity: Severity,
+    pub date: NaiveDate,
+    pub kind: ViolationKind,
+}
+
+impl Violation {
+    pub fn new(rule: &str, severity: Severity, date: NaiveDate, kind: ViolationKind) -> Self {
+        Self {
+            rule: rule.to_string(),
+            severity,
+            date,
+            kind,
+        }
+    }
+}
+
+impl ViolationKind {
+    pub fn describe(&self, time_tracking: Option<&TimeTrackingConfig>) -> String {
+        let format_duration = |duration: &Duration| format_duration(*duration, time_tracking);
+        match self {
+            ViolationKind::UnexpectedDailyTime { expected, logged } => format!(
+                "logged {}, expected {}",
+                format_duration(logged),
+                format_duration(expected)
+            ),
+            ViolationKind::WeekendWorklog {
+                issue_key,
+                time_spent,
+            } => format!(
+                "{} logged on {} during weekend",
+                format_duration(time_spent),
+                issue_key
+            ),
+            ViolationKind::TimeLimitExceeded { limit, logged } => format!(
+                "logged {}, limit is {}",
+                format_duration(logged),
+                format_duration(limit)
+            ),
+            ViolationKind::UnexpectedPeriodTime {
+                period,
+                expected,
+                logged,
+                balance,
+            } => format!(
+                "{} logged {}, expected {}, balance {}",
+                period,
+                format_duration(logged),
+                format_duration(expected),
+                format_duration(balance)
+            ),
+            ViolationKind::MissingComment {
+                issue_key,
+                time_spent,
+            } => format!(
+                "{} logged on {} without comment",
+                format_duration(time_spent),
+                issue_key
+            ),
+            ViolationKind::ClosedIssueWorklog { issue_key, status } => {
+                format!("time logged on {} in status {}", issue_key, status)
+            }
+        }
+    }
+}
+
+impl Display for ViolationKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.describe(None))
+    }
+}
+
+impl Violation {
+    pub fn describe(&self, time_tracking: Option<&TimeTrackingConfig>) -> String {
+        format!(
+            "[{}] {} {}: {}",
+            self.severity,
+            self.date,
+            self.rule,
+            self.kind.describe(time_tracking)
+        )
+    }
+}
+
+impl Display for Violation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.describe(None))
+    }
+}
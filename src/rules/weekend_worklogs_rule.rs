@@ -0,0 +1,115 @@
+use super::{
+    rule_trait::RuleTrait,
+    severity::Severity,
+    violation::{Violation, ViolationKind},
+};
+use crate::time_tracker::UserWorklogsSummary;
+use chrono::{Datelike, Weekday};
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct WeekendWorklogsRule {
+    pub weekend_days: Vec<Weekday>,
+    pub severity: Severity,
+}
+
+impl WeekendWorklogsRule {
+    const NAME: &'static str = "weekend-worklogs";
+
+    pub fn new() -> Self {
+        Self {
+            weekend_days: vec![Weekday::Sat, Weekday::Sun],
+            severity: Severity::Warning,
+        }
+    }
+
+    pub fn with_weekend_days(mut self, weekend_days: &[Weekday]) -> Self {
+        self.weekend_days = weekend_days.to_vec();
+        self
+    }
+
+    pub fn with_severity(mut self, severity: Severity) -> Self {
+        self.severity = severity;
+        self
+    }
+}
+
+impl Default for WeekendWorklogsRule {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RuleTrait for WeekendWorklogsRule {
+    fn name(&self) -> &str {
+        Self::NAME
+    }
+
+    fn evaluate(&self, summary: &UserWorklogsSummary) -> Vec<Violation> {
+        summary
+            .iter()
+            .filter(|(date, _)| self.weekend_days.contains(&date.weekday()))
+            .flat_map(|(date, worklog_summary)| {
+                worklog_summary.worklogs.iter().map(|worklog| {
+                    Violation::new(
+                        self.name(),
+                        self.severity,
+                        *date,
+                        ViolationKind::WeekendWorklog {
+                            issue_key: worklog.issue_key.clone(),
+                            time_spent: worklog.time_spent,
+                        },
+                    )
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jira::Worklog;
+    use crate::test_helpers::started_at;
+    use crate::time_tracker::WorklogSummary;
+    use chrono::{Duration, NaiveDate};
+
+    #[test]
+    fn evaluate_returns_violation_for_every_worklog_logged_during_weekend() {
+        let friday = NaiveDate::from_ymd(2022, 9, 16);
+        let sunday = NaiveDate::from_ymd(2022, 9, 18);
+        let friday_worklog =
+            Worklog::new(started_at(friday), "key1", "summary1", Duration::hours(8));
+        let sunday_worklog =
+            Worklog::new(started_at(sunday), "key2", "summary2", Duration::hours(2));
+
+        let mut summary = UserWorklogsSummary::new();
+        summary.insert(
+            friday,
+            WorklogSummary {
+                spent_time: Duration::hours(8),
+                worklogs: vec![friday_worklog],
+            },
+        );
+        summary.insert(
+            sunday,
+            WorklogSummary {
+                spent_time: Duration::hours(2),
+                worklogs: vec![sunday_worklog],
+            },
+        );
+
+        let uut = WeekendWorklogsRule::new();
+        assert_eq!(
+            uut.evaluate(&summary),
+            vec![Violation::new(
+                "weekend-worklogs",
+                Severity::Warning,
+                sunday,
+                ViolationKind::WeekendWorklog {
+                    issue_key: "key2".to_string(),
+                    time_spent: Duration::hours(2),
+                },
+            )]
+        );
+    }
+}
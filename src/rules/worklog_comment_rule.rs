@@ -0,0 +1,119 @@
+use super::{
+    rule_trait::RuleTrait,
+    severity::Severity,
+    violation::{Violation, ViolationKind},
+};
+use crate::time_tracker::UserWorklogsSummary;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct WorklogCommentRule {
+    pub severity: Severity,
+}
+
+impl WorklogCommentRule {
+    const NAME: &'static str = "worklog-comment";
+
+    pub fn new() -> Self {
+        Self {
+            severity: Severity::Warning,
+        }
+    }
+
+    pub fn with_severity(mut self, severity: Severity) -> Self {
+        self.severity = severity;
+        self
+    }
+}
+
+impl Default for WorklogCommentRule {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RuleTrait for WorklogCommentRule {
+    fn name(&self) -> &str {
+        Self::NAME
+    }
+
+    fn evaluate(&self, summary: &UserWorklogsSummary) -> Vec<Violation> {
+        summary
+            .iter()
+            .flat_map(|(date, worklog_summary)| {
+                worklog_summary
+                    .worklogs
+                    .iter()
+                    .filter(|worklog| {
+                        worklog
+                            .comment
+                            .as_ref()
+                            .is_none_or(|comment| comment.trim().is_empty())
+                    })
+                    .map(|worklog| {
+                        Violation::new(
+                            self.name(),
+                            self.severity,
+                            *date,
+                            ViolationKind::MissingComment {
+                                issue_key: worklog.issue_key.clone(),
+                                time_spent: worklog.time_spent,
+                            },
+                        )
+                    })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jira::Worklog;
+    use crate::test_helpers::started_at;
+    use crate::time_tracker::WorklogSummary;
+    use chrono::{Duration, NaiveDate};
+
+    #[test]
+    fn evaluate_returns_violation_for_worklogs_without_comment() {
+        let date = NaiveDate::from_ymd(2022, 9, 15);
+        let mut summary = UserWorklogsSummary::new();
+        summary.insert(
+            date,
+            WorklogSummary {
+                spent_time: Duration::hours(8),
+                worklogs: vec![
+                    Worklog::new(started_at(date), "key1", "summary1", Duration::hours(4))
+                        .with_comment("Implemented feature"),
+                    Worklog::new(started_at(date), "key2", "summary2", Duration::hours(3))
+                        .with_comment("  "),
+                    Worklog::new(started_at(date), "key3", "summary3", Duration::hours(1)),
+                ],
+            },
+        );
+
+        let uut = WorklogCommentRule::new();
+        assert_eq!(
+            uut.evaluate(&summary),
+            vec![
+                Violation::new(
+                    "worklog-comment",
+                    Severity::Warning,
+                    date,
+                    ViolationKind::MissingComment {
+                        issue_key: "key2".to_string(),
+                        time_spent: Duration::hours(3),
+                    },
+                ),
+                Violation::new(
+                    "worklog-comment",
+                    Severity::Warning,
+                    date,
+                    ViolationKind::MissingComment {
+                        issue_key: "key3".to_string(),
+                        time_spent: Duration::hours(1),
+                    },
+                ),
+            ]
+        );
+    }
+}
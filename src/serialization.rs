@@ -0,0 +1,203 @@
+//! Versioned serde representations of the domain types, described by
+//! `schema/worklogs.schema.json`. Durations are whole seconds, timestamps RFC 3339
+//! (ISO 8601) and dates `YYYY-MM-DD`.
+
+use anyhow::anyhow;
+use serde::{Deserialize, Serialize};
+
+/// Bumped on every incompatible change of the serialized representation.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Envelope tagging serialized data with the schema version it was written with.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct Versioned<T> {
+    pub schema_version: u32,
+    pub data: T,
+}
+
+impl<T> Versioned<T> {
+    pub fn new(data: T) -> Self {
+        Self {
+            schema_version: SCHEMA_VERSION,
+            data,
+        }
+    }
+
+    pub fn into_data(self) -> anyhow::Result<T> {
+        if self.schema_version != SCHEMA_VERSION {
+            return Err(anyhow!(
+                "Unsupported schema version {}, expected {}",
+                self.schema_version,
+                SCHEMA_VERSION
+            ));
+        }
+        Ok(self.data)
+    }
+}
+
+pub(crate) mod duration_seconds {
+    use crate::jira::checked_seconds;
+    use chrono::Duration;
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_i64(duration.num_seconds())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+        let seconds = i64::deserialize(deserializer)?;
+        checked_seconds(seconds)
+            .ok_or_else(|| D::Error::custom(format!("duration of {} s is out of range", seconds)))
+    }
+}
+
+/// Serializes values through their `Display`, e.g. `Period` as `2022-W37`.
+pub(crate) mod display_string {
+    use serde::Serializer;
+    use std::fmt::Display;
+
+    pub fn serialize<T: Display, S: Serializer>(
+        value: &T,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jira::{IssueInfo, UserWorklogs, Worklog, WorklogAuthor, WorklogVisibility};
+    use crate::rules::ViolationKind;
+    use crate::time_tracker::{Period, UserWorklogsSummary, WorklogSummary};
+    use chrono::{DateTime, Duration, NaiveDate};
+    use serde_json::json;
+
+    const SCHEMA: &str = include_str!("../schema/worklogs.schema.json");
+
+    fn create_worklog() -> Worklog {
+        let mut worklog = Worklog::new(
+            DateTime::parse_from_rfc3339("2022-09-15T09:00:00+02:00").unwrap(),
+            "PROJ-123",
+            "summary",
+            Duration::minutes(90),
+        )
+        .with_comment("Code review")
+        .with_author(WorklogAuthor {
+            account_id: Some("5b10a2844c20165700ede21g".to_string()),
+            display_name: Some("John Doe".to_string()),
+            ..Default::default()
+        })
+        .with_visibility(WorklogVisibility {
+            kind: "group".to_string(),
+            value: "developers".to_string(),
+        })
+        .with_metadata("source", "jira");
+        worklog.id = Some("10001".to_string());
+        worklog.issue = Some(IssueInfo {
+            project_key: Some("PROJ".to_string()),
+            labels: vec!["backend".to_string()],
+            ..Default::default()
+        });
+        worklog
+    }
+
+    #[test]
+    fn worklog_uses_seconds_and_iso_timestamps() {
+        let json = serde_json::to_value(create_worklog()).unwrap();
+
+        assert_eq!(json["time_spent_seconds"], json!(5400));
+        assert_eq!(json["started"], json!("2022-09-15T09:00:00+02:00"));
+        assert_eq!(json["visibility"]["kind"], json!("group"));
+        assert_eq!(json["metadata"], json!({"source": "jira"}));
+    }
+
+    #[test]
+    fn versioned_types_round_trip() {
+        let date = NaiveDate::from_ymd(2022, 9, 15);
+        let user_worklogs = UserWorklogs::new("john", date, date, vec![create_worklog()]);
+        let summary: UserWorklogsSummary = [(
+            date,
+            WorklogSummary {
+                spent_time: Duration::minutes(90),
+                worklogs: vec![create_worklog()],
+            },
+        )]
+        .into_iter()
+        .collect();
+
+        let text = serde_json::to_string(&Versioned::new(user_worklogs.clone())).unwrap();
+        let parsed: Versioned<UserWorklogs> = serde_json::from_str(&text).unwrap();
+        assert_eq!(parsed.into_data().unwrap(), user_worklogs);
+
+        let json = serde_json::to_value(Versioned::new(summary.clone())).unwrap();
+        assert_eq!(json["schema_version"], json!(SCHEMA_VERSION));
+        assert_eq!(
+            json["data"]["2022-09-15"]["spent_time_seconds"],
+            json!(5400)
+        );
+        let parsed: Versioned<UserWorklogsSummary> = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed.into_data().unwrap(), summary);
+    }
+
+    #[test]
+    fn violation_kind_is_tagged_with_seconds_and_period_string() {
+        let kind = ViolationKind::UnexpectedPeriodTime {
+            period: Period::Week {
+                year: 2022,
+                week: 37,
+            },
+            expected: Duration::hours(40),
+            logged: Duration::hours(38),
+            balance: Duration::hours(-2),
+        };
+
+        assert_eq!(
+            serde_json::to_value(kind).unwrap(),
+            json!({
+                "kind": "unexpected_period_time",
+                "period": "2022-W37",
+                "expected_seconds": 40 * 3600,
+                "logged_seconds": 38 * 3600,
+                "balance_seconds": -2 * 3600
+            })
+        );
+    }
+
+    #[test]
+    fn deserialize_rejects_out_of_range_durations() {
+        let mut json = serde_json::to_value(create_worklog()).unwrap();
+        json["time_spent_seconds"] = json!(i64::MAX);
+
+        let error = serde_json::from_value::<Worklog>(json).unwrap_err();
+
+        assert!(error.to_string().contains("out of range"));
+    }
+
+    #[test]
+    fn into_data_rejects_other_schema_versions() {
+        let parsed: Versioned<UserWorklogsSummary> =
+            serde_json::from_value(json!({"schema_version": 0, "data": {}})).unwrap();
+
+        assert!(parsed.into_data().is_err());
+    }
+
+    #[test]
+    fn schema_documents_current_version_and_fields() {
+        let schema: serde_json::Value = serde_json::from_str(SCHEMA).unwrap();
+
+        assert_eq!(
+            schema["properties"]["schema_version"]["const"],
+            json!(SCHEMA_VERSION)
+        );
+        let worklog_json = serde_json::to_value(create_worklog()).unwrap();
+        let documented = &schema["$defs"]["Worklog"]["properties"];
+        for field in worklog_json.as_object().unwrap().keys() {
+            assert!(
+                documented.get(field).is_some(),
+                "{} is not documented",
+                field
+            );
+        }
+    }
+}
@@ -0,0 +1,432 @@
+use super::source_failure::SourceFailure;
+use crate::jira::{
+    TimeTrackingConfig, UserWorklogs, UserWorklogsFetcherTrait, Worklog,
+    JIRA_WORKLOG_ID_METADATA_KEY,
+};
+use anyhow::anyhow;
+use chrono::NaiveDate;
+use chrono_tz::Tz;
+use std::{cell::RefCell, collections::BTreeMap};
+
+pub const SOURCE_METADATA_KEY: &str = "source";
+
+pub struct WorklogSource {
+    pub name: String,
+    /// Identity of the user in this source, if it differs from the requested one.
+    pub user_name: Option<String>,
+    pub fetcher: Box<dyn UserWorklogsFetcherTrait>,
+}
+
+/// Combines worklogs of several sources (Jira instances, Tempo, GitLab) into one list.
+pub struct CompositeUserWorklogsFetcher {
+    sources: Vec<WorklogSource>,
+    timezone: Option<Tz>,
+    allow_partial_failures: bool,
+    failures: RefCell<Vec<SourceFailure>>,
+}
+
+impl UserWorklogsFetcherTrait for CompositeUserWorklogsFetcher {
+    fn fetch(
+        &self,
+        user_name: &str,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> anyhow::Result<UserWorklogs> {
+        let mut failures = vec![];
+        let result = self.fetch_merged(user_name, start_date, end_date, &mut failures);
+        self.failures.replace(failures);
+        result
+    }
+
+    /// Like `fetch` for each user, reporting the failures of all of them.
+    fn fetch_team(
+        &self,
+        user_names: &[String],
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> anyhow::Result<BTreeMap<String, UserWorklogs>> {
+        let mut failures = vec![];
+        let result = user_names
+            .iter()
+            .map(|user_name| {
+                self.fetch_merged(user_name, start_date, end_date, &mut failures)
+                    .map(|user_worklogs| (user_name.clone(), user_worklogs))
+            })
+            .collect();
+        self.failures.replace(failures);
+        result
+    }
+
+    fn timezone(&self) -> Option<Tz> {
+        self.timezone
+    }
+
+    /// Time tracking configuration of the first source that knows one.
+    fn time_tracking(&self) -> Option<TimeTrackingConfig> {
+        self.sources
+            .iter()
+            .find_map(|source| source.fetcher.time_tracking())
+    }
+
+    fn source_failures(&self) -> Vec<SourceFailure> {
+        self.failures.borrow().clone()
+    }
+}
+
+impl CompositeUserWorklogsFetcher {
+    pub fn new() -> Self {
+        Self {
+            sources: vec![],
+            timezone: None,
+            allow_partial_failures: false,
+            failures: RefCell::new(vec![]),
+        }
+    }
+
+    pub fn with_source(mut self, name: &str, fetcher: Box<dyn UserWorklogsFetcherTrait>) -> Self {
+        self.sources.push(WorklogSource {
+            name: name.to_string(),
+            user_name: None,
+            fetcher,
+        });
+        self
+    }
+
+    pub fn with_source_for_user(
+        mut self,
+        name: &str,
+        user_name: &str,
+        fetcher: Box<dyn UserWorklogsFetcherTrait>,
+    ) -> Self {
+        self.sources.push(WorklogSource {
+            name: name.to_string(),
+            user_name: Some(user_name.to_string()),
+            fetcher,
+        });
+        self
+    }
+
+    /// Timezone the merged worklogs are assigned to days in. Sources keep their own, so
+    /// configure them with the same one; without it the sources' offsets are kept.
+    pub fn with_timezone(mut self, timezone: Tz) -> Self {
+        self.timezone = Some(timezone);
+        self
+    }
+
+    /// Returns worklogs of the sources that succeeded instead of failing the whole fetch;
+    /// the failed ones are reported through `source_failures`, also by `Summary`.
+    pub fn with_partial_failures(mut self, allow_partial_failures: bool) -> Self {
+        self.allow_partial_failures = allow_partial_failures;
+        self
+    }
+
+    /// Merges the sources for one user, adding the sources that failed to `all_failures`.
+    fn fetch_merged(
+        &self,
+        user_name: &str,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+        all_failures: &mut Vec<SourceFailure>,
+    ) -> anyhow::Result<UserWorklogs> {
+        let mut worklogs: Vec<(usize, Worklog)> = vec![];
+        let mut failures = vec![];
+
+        for (source_index, source) in self.sources.iter().enumerate() {
+            let source_user_name = source.user_name.as_deref().unwrap_or(user_name);
+            match source.fetcher.fetch(source_user_name, start_date, end_date) {
+                Ok(user_worklogs) => {
+                    for worklog in user_worklogs.worklogs {
+                        if !worklogs.iter().any(|(known_source_index, known)| {
+                            *known_source_index != source_index
+                                && Self::is_same_worklog(known, &worklog)
+                        }) {
+                            worklogs.push((
+                                source_index,
+                                worklog.with_metadata(SOURCE_METADATA_KEY, &source.name),
+                            ));
+                        }
+                    }
+                }
+                Err(error) if self.allow_partial_failures => failures.push(SourceFailure {
+                    source: source.name.clone(),
+                    user_name: user_name.to_string(),
+                    error: format!("{:#}", error),
+                }),
+                Err(error) => {
+                    return Err(error.context(format!("Source '{}' failed", source.name)));
+                }
+            }
+        }
+
+        if !self.sources.is_empty() && failures.len() == self.sources.len() {
+            let message = failures
+                .iter()
+                .map(|failure| format!("{}: {}", failure.source, failure.error))
+                .collect::<Vec<_>>()
+                .join("; ");
+            all_failures.extend(failures);
+            return Err(anyhow!("All worklog sources failed: {}", message));
+        }
+        all_failures.extend(failures);
+
+        let mut worklogs: Vec<Worklog> = worklogs.into_iter().map(|(_, worklog)| worklog).collect();
+        worklogs.sort_by_key(|worklog| worklog.started);
+        Ok(UserWorklogs::new(user_name, start_date, end_date, worklogs))
+    }
+
+    /// Whether `mirror` is a copy of `worklog` carrying its Jira worklog id, as Tempo's are.
+    fn mirrors(mirror: &Worklog, worklog: &Worklog) -> bool {
+        worklog.id.is_some()
+            && mirror.metadata.get(JIRA_WORKLOG_ID_METADATA_KEY) == worklog.id.as_ref()
+    }
+
+    /// Whether entries of two different sources point at the same underlying worklog. Ids
+    /// alone don't tell, as separate Jira instances can share project keys and worklog ids.
+    fn is_same_worklog(known: &Worklog, worklog: &Worklog) -> bool {
+        let same_issue = known.issue_key == worklog.issue_key
+            || (known.issue_id.is_some() && known.issue_id == worklog.issue_id);
+        same_issue && (Self::mirrors(known, worklog) || Self::mirrors(worklog, known))
+    }
+}
+
+impl Default for CompositeUserWorklogsFetcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jira::testing::MockUserWorklogsFetcherTrait;
+    use crate::test_helpers::started_at_hour;
+    use crate::time_tracker::{Summary, SummaryTrait};
+    use chrono::Duration;
+    use mockall::predicate::eq;
+
+    fn create_fetcher(
+        user_name: &'static str,
+        worklogs: anyhow::Result<Vec<Worklog>>,
+    ) -> Box<dyn UserWorklogsFetcherTrait> {
+        let mut fetcher = MockUserWorklogsFetcherTrait::new();
+        fetcher
+            .expect_fetch()
+            .with(
+                eq(user_name),
+                mockall::predicate::always(),
+                mockall::predicate::always(),
+            )
+            .return_once(move |user_name, start_date, end_date| {
+                worklogs
+                    .map(|worklogs| UserWorklogs::new(user_name, start_date, end_date, worklogs))
+            });
+        Box::new(fetcher)
+    }
+
+    fn create_team_fetcher() -> Box<dyn UserWorklogsFetcherTrait> {
+        let mut fetcher = MockUserWorklogsFetcherTrait::new();
+        fetcher
+            .expect_fetch()
+            .returning(|user_name, start_date, end_date| {
+                Ok(UserWorklogs::new(user_name, start_date, end_date, vec![]))
+            });
+        Box::new(fetcher)
+    }
+
+    #[test]
+    fn fetch_merges_sources_tags_worklogs_and_drops_duplicates() {
+        let date = NaiveDate::from_ymd(2022, 9, 15);
+        let shared = Worklog::new(
+            started_at_hour(date, 9),
+            "ABC-1",
+            "Task",
+            Duration::hours(2),
+        )
+        .with_id("10100");
+        let mirrored = Worklog::new(
+            started_at_hour(date, 9),
+            "ABC-1",
+            "Task",
+            Duration::hours(2),
+        )
+        .with_id("42")
+        .with_metadata(JIRA_WORKLOG_ID_METADATA_KEY, "10100");
+        let client_b = Worklog::new(
+            started_at_hour(date, 8),
+            "XYZ-5",
+            "Other",
+            Duration::hours(3),
+        );
+
+        let uut = CompositeUserWorklogsFetcher::new()
+            .with_source("client-a", create_fetcher("john", Ok(vec![shared.clone()])))
+            .with_source("tempo", create_fetcher("john", Ok(vec![mirrored])))
+            .with_source_for_user(
+                "client-b",
+                "jdoe",
+                create_fetcher("jdoe", Ok(vec![client_b.clone()])),
+            );
+
+        let result = uut.fetch("john", date, date).unwrap();
+
+        assert_eq!(
+            result,
+            UserWorklogs::new(
+                "john",
+                date,
+                date,
+                vec![
+                    client_b.with_metadata(SOURCE_METADATA_KEY, "client-b"),
+                    shared.with_metadata(SOURCE_METADATA_KEY, "client-a"),
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn fetch_keeps_entries_that_only_look_alike() {
+        let date = NaiveDate::from_ymd(2022, 9, 15);
+        let worklog = Worklog::new(
+            started_at_hour(date, 9),
+            "ABC-1",
+            "Task",
+            Duration::hours(1),
+        );
+        let twice_in_source = vec![
+            worklog.clone().with_id("10100"),
+            worklog.clone().with_id("10101"),
+        ];
+        let without_id = vec![worklog.clone()];
+
+        let uut = CompositeUserWorklogsFetcher::new()
+            .with_source("client-a", create_fetcher("john", Ok(twice_in_source)))
+            .with_source("client-b", create_fetcher("john", Ok(without_id.clone())))
+            .with_source("client-c", create_fetcher("john", Ok(without_id)));
+
+        assert_eq!(uut.fetch("john", date, date).unwrap().worklogs.len(), 4);
+    }
+
+    #[test]
+    fn fetch_keeps_worklogs_of_instances_sharing_issue_key_and_id() {
+        let date = NaiveDate::from_ymd(2022, 9, 15);
+        let worklog_a = Worklog::new(
+            started_at_hour(date, 9),
+            "ABC-1",
+            "Login",
+            Duration::hours(1),
+        )
+        .with_id("10100");
+        let worklog_b = Worklog::new(
+            started_at_hour(date, 11),
+            "ABC-1",
+            "Billing",
+            Duration::hours(2),
+        )
+        .with_id("10100");
+
+        let uut = CompositeUserWorklogsFetcher::new()
+            .with_source("client-a", create_fetcher("john", Ok(vec![worklog_a])))
+            .with_source("client-b", create_fetcher("john", Ok(vec![worklog_b])));
+
+        let result = uut.fetch("john", date, date).unwrap();
+        assert_eq!(
+            result
+                .worklogs
+                .iter()
+                .map(|worklog| worklog.issue_summary.as_str())
+                .collect::<Vec<_>>(),
+            vec!["Login", "Billing"]
+        );
+    }
+
+    #[test]
+    fn fetch_reports_failed_source() {
+        let date = NaiveDate::from_ymd(2022, 9, 15);
+        let worklog = Worklog::new(
+            started_at_hour(date, 9),
+            "ABC-1",
+            "Task",
+            Duration::hours(2),
+        );
+
+        let create_uut = |allow_partial_failures| {
+            CompositeUserWorklogsFetcher::new()
+                .with_source(
+                    "client-a",
+                    create_fetcher("john", Ok(vec![worklog.clone()])),
+                )
+                .with_source(
+                    "client-b",
+                    create_fetcher("john", Err(anyhow!("unauthorized"))),
+                )
+                .with_partial_failures(allow_partial_failures)
+        };
+
+        let error = create_uut(false).fetch("john", date, date).unwrap_err();
+        assert_eq!(error.to_string(), "Source 'client-b' failed");
+
+        let summary = Summary::new(create_uut(true));
+        assert_eq!(
+            summary
+                .get_user_worklogs_summary("john", date, date)
+                .unwrap()
+                .len(),
+            1
+        );
+        assert_eq!(
+            summary.source_failures(),
+            vec![SourceFailure {
+                source: "client-b".to_string(),
+                user_name: "john".to_string(),
+                error: "unauthorized".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn fetch_team_reports_failures_of_all_members_with_error_context() {
+        let date = NaiveDate::from_ymd(2022, 9, 15);
+        let mut failing = MockUserWorklogsFetcherTrait::new();
+        failing.expect_fetch().returning(|user_name, _, _| {
+            Err(anyhow!("unauthorized").context(format!("Can't fetch '{}'", user_name)))
+        });
+
+        let uut = CompositeUserWorklogsFetcher::new()
+            .with_source("client-a", create_team_fetcher())
+            .with_source("client-b", Box::new(failing))
+            .with_partial_failures(true);
+
+        let result = uut
+            .fetch_team(&["john".to_string(), "jane".to_string()], date, date)
+            .unwrap();
+
+        assert_eq!(result.len(), 2);
+        assert_eq!(
+            uut.source_failures(),
+            vec![
+                SourceFailure {
+                    source: "client-b".to_string(),
+                    user_name: "john".to_string(),
+                    error: "Can't fetch 'john': unauthorized".to_string(),
+                },
+                SourceFailure {
+                    source: "client-b".to_string(),
+                    user_name: "jane".to_string(),
+                    error: "Can't fetch 'jane': unauthorized".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn timezone_comes_from_composite_configuration() {
+        let mut tempo = MockUserWorklogsFetcherTrait::new();
+        tempo.expect_timezone().return_const(Some(chrono_tz::UTC));
+
+        let uut = CompositeUserWorklogsFetcher::new().with_source("tempo", Box::new(tempo));
+        assert_eq!(uut.timezone(), None);
+
+        let uut = uut.with_timezone(chrono_tz::Europe::Warsaw);
+        assert_eq!(uut.timezone(), Some(chrono_tz::Europe::Warsaw));
+    }
+}
@@ -0,0 +1,7 @@
+mod composite_user_worklogs_fetcher;
+mod source_failure;
+
+pub use composite_user_worklogs_fetcher::{
+    CompositeUserWorklogsFetcher, WorklogSource, SOURCE_METADATA_KEY,
+};
+pub use source_failure::SourceFailure;
@@ -0,0 +1,9 @@
+/// A worklog source that failed while the others were still used.
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SourceFailure {
+    pub source: String,
+    /// User the source was asked for, as requested from the composite fetcher.
+    pub user_name: String,
+    pub error: String,
+}
@@ -0,0 +1,5 @@
+mod user_worklogs_fetcher;
+
+/// Tempo REST client authenticating with an API token.
+pub type Client = crate::jira::BearerClient;
+pub use user_worklogs_fetcher::UserWorklogsFetcher;
@@ -0,0 +1,552 @@
+use crate::jira::{
+    checked_seconds, ClientTrait, IssueResolverTrait, ResolvedIssue, UserWorklogs,
+    UserWorklogsFetcherTrait, Worklog, WorklogAuthor, JIRA_WORKLOG_ID_METADATA_KEY,
+};
+use anyhow::anyhow;
+use chrono::{
+    DateTime, Duration, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, Offset, TimeZone,
+    Timelike,
+};
+use chrono_tz::Tz;
+use itertools::Itertools;
+use reqwest::IntoUrl;
+use std::collections::BTreeMap;
+
+const PAGE_SIZE: usize = 50;
+const ACCOUNT_ATTRIBUTE_KEY: &str = "_Account_";
+
+/// Reads worklogs from Tempo Timesheets; `user_name` is the Atlassian account id. Tempo
+/// only reports issue ids, so keys, summaries and fields come from the issue resolver; issues
+/// it can't resolve (deleted or not visible to the user) keep the numeric id as their key.
+pub struct UserWorklogsFetcher<UrlType, ClientType>
+where
+    UrlType: IntoUrl,
+    ClientType: ClientTrait,
+{
+    tempo_api_root_url: UrlType,
+    tempo_client: ClientType,
+    issue_resolver: Box<dyn IssueResolverTrait>,
+    timezone: Option<Tz>,
+}
+
+impl<UrlType, ClientType> UserWorklogsFetcherTrait for UserWorklogsFetcher<UrlType, ClientType>
+where
+    UrlType: IntoUrl,
+    ClientType: ClientTrait,
+{
+    fn fetch(
+        &self,
+        user_name: &str,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> anyhow::Result<UserWorklogs> {
+        let mut results = vec![];
+        let mut page_url = Some(format!(
+            "{}{}",
+            self.tempo_api_root_url.as_str(),
+            self.create_tempo_user_worklogs_endpoint(user_name, &start_date, &end_date)
+        ));
+
+        while let Some(url) = page_url {
+            let page = self
+                .tempo_client
+                .request_get(url)?
+                .error_for_status()?
+                .json::<serde_json::Value>()?;
+            results.extend(
+                page["results"]
+                    .as_array()
+                    .ok_or(anyhow!("Can't parse 'results' as array"))?
+                    .iter()
+                    .cloned(),
+            );
+            page_url = page["metadata"]["next"]
+                .as_str()
+                .map(|next| next.to_string());
+        }
+
+        let issue_ids = results
+            .iter()
+            .map(Self::parse_issue_id)
+            .collect::<anyhow::Result<Vec<_>>>()?
+            .into_iter()
+            .unique()
+            .collect::<Vec<_>>();
+        let issues = match issue_ids.is_empty() {
+            true => BTreeMap::new(),
+            false => self.issue_resolver.resolve_issues(&issue_ids)?,
+        };
+
+        let mut worklogs = vec![];
+        for result in &results {
+            let worklog = self.create_worklog(result, &issues)?;
+            let date = match &self.timezone {
+                Some(timezone) => worklog.date_in(timezone),
+                None => worklog.date(),
+            };
+            if date >= start_date && date <= end_date {
+                worklogs.push(worklog);
+            }
+        }
+
+        Ok(UserWorklogs::new(user_name, start_date, end_date, worklogs))
+    }
+
+    fn timezone(&self) -> Option<Tz> {
+        self.timezone
+    }
+}
+
+impl<UrlType, ClientType> UserWorklogsFetcher<UrlType, ClientType>
+where
+    UrlType: IntoUrl,
+    ClientType: ClientTrait,
+{
+    /// `issue_resolver` is typically the Jira `UserWorklogsFetcher` of the same instance.
+    pub fn new(
+        tempo_api_root_url: UrlType,
+        tempo_client: ClientType,
+        issue_resolver: Box<dyn IssueResolverTrait>,
+    ) -> Self {
+        Self {
+            tempo_api_root_url,
+            tempo_client,
+            issue_resolver,
+            timezone: None,
+        }
+    }
+
+    /// Timezone of Tempo's local `startDate`/`startTime`; without it they're read as UTC.
+    pub fn with_timezone(mut self, timezone: Tz) -> Self {
+        self.timezone = Some(timezone);
+        self
+    }
+
+    fn create_tempo_user_worklogs_endpoint(
+        &self,
+        account_id: &str,
+        start_date: &NaiveDate,
+        end_date: &NaiveDate,
+    ) -> String {
+        format!(
+            "/worklogs/user/{}?from={}&to={}&offset=0&limit={}",
+            form_urlencoded::byte_serialize(account_id.as_bytes()).collect::<String>(),
+            start_date.format("%Y-%m-%d"),
+            end_date.format("%Y-%m-%d"),
+            PAGE_SIZE
+        )
+    }
+
+    fn parse_issue_id(result: &serde_json::Value) -> anyhow::Result<String> {
+        result["issue"]["id"]
+            .as_u64()
+            .map(|id| id.to_string())
+            .ok_or(anyhow!("Can't parse Tempo worklog issue"))
+    }
+
+    fn create_worklog(
+        &self,
+        result: &serde_json::Value,
+        issues: &BTreeMap<String, ResolvedIssue>,
+    ) -> anyhow::Result<Worklog> {
+        let issue_id = Self::parse_issue_id(result)?;
+        let time_spent = result["timeSpentSeconds"]
+            .as_i64()
+            .and_then(checked_seconds)
+            .ok_or(anyhow!("Can't parse Tempo worklog time spent"))?;
+
+        let mut worklog = match issues.get(&issue_id) {
+            Some(issue) => Worklog::new(
+                self.parse_started(result)?,
+                &issue.key,
+                &issue.summary,
+                time_spent,
+            )
+            .with_issue(issue.info.clone()),
+            None => Worklog::new(self.parse_started(result)?, &issue_id, "", time_spent),
+        }
+        .with_author(WorklogAuthor {
+            account_id: result["author"]["accountId"]
+                .as_str()
+                .map(|id| id.to_string()),
+            display_name: result["author"]["displayName"]
+                .as_str()
+                .map(|name| name.to_string()),
+            ..Default::default()
+        });
+        worklog.id = result["tempoWorklogId"].as_u64().map(|id| id.to_string());
+        worklog.issue_id = Some(issue_id);
+        worklog.comment = result["description"]
+            .as_str()
+            .filter(|description| !description.trim().is_empty())
+            .map(|description| description.to_string());
+        worklog.created = Self::parse_time(&result["createdAt"]);
+        worklog.updated = Self::parse_time(&result["updatedAt"]);
+
+        if let Some(jira_worklog_id) = result["jiraWorklogId"].as_u64() {
+            worklog =
+                worklog.with_metadata(JIRA_WORKLOG_ID_METADATA_KEY, &jira_worklog_id.to_string());
+        }
+        if let Some(billable_seconds) = result["billableSeconds"].as_i64() {
+            worklog = worklog.with_metadata("tempo.billableSeconds", &billable_seconds.to_string());
+        }
+        for attribute in result["attributes"]["values"]
+            .as_array()
+            .into_iter()
+            .flatten()
+        {
+            let (Some(key), Some(value)) = (attribute["key"].as_str(), attribute["value"].as_str())
+            else {
+                continue;
+            };
+            worklog = if key == ACCOUNT_ATTRIBUTE_KEY {
+                worklog.with_metadata("tempo.account", value)
+            } else {
+                worklog.with_metadata(&format!("tempo.attribute.{}", key), value)
+            };
+        }
+
+        Ok(worklog)
+    }
+
+    fn parse_started(&self, result: &serde_json::Value) -> anyhow::Result<DateTime<FixedOffset>> {
+        let date = NaiveDate::parse_from_str(
+            result["startDate"]
+                .as_str()
+                .ok_or(anyhow!("Can't parse Tempo worklog start date"))?,
+            "%Y-%m-%d",
+        )?;
+        let time = match result["startTime"].as_str() {
+            Some(time) => NaiveTime::parse_from_str(time, "%H:%M:%S")?,
+            None => NaiveTime::from_hms(0, 0, 0),
+        };
+        let started = Self::resolve_local_time(
+            self.timezone.unwrap_or(chrono_tz::UTC),
+            NaiveDateTime::new(date, time),
+        )
+        .ok_or(anyhow!("Tempo worklog starts at nonexistent local time"))?;
+        Ok(started.with_timezone(&started.offset().fix()))
+    }
+
+    /// Maps ambiguous local times to the earlier instant and ones skipped by a DST change
+    /// to the first instant after the gap, so such worklogs still count.
+    fn resolve_local_time(timezone: Tz, local_time: NaiveDateTime) -> Option<DateTime<Tz>> {
+        if let Some(resolved) = timezone.from_local_datetime(&local_time).earliest() {
+            return Some(resolved);
+        }
+        let minute_start = local_time.with_second(0)?.with_nanosecond(0)?;
+        (1..=24 * 60).find_map(|minutes| {
+            timezone
+                .from_local_datetime(&(minute_start + Duration::minutes(minutes)))
+                .earliest()
+        })
+    }
+
+    fn parse_time(time: &serde_json::Value) -> Option<DateTime<FixedOffset>> {
+        DateTime::parse_from_rfc3339(time.as_str()?).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jira::{testing::MockIssueResolverTrait, IssueInfo};
+    use crate::tempo::Client;
+
+    const ACCOUNT_ID: &str = "557058:0001";
+
+    fn resolved_issue(key: &str, summary: &str) -> ResolvedIssue {
+        ResolvedIssue {
+            key: key.to_string(),
+            summary: summary.to_string(),
+            info: IssueInfo {
+                project_key: key.split('-').next().map(|project| project.to_string()),
+                ..Default::default()
+            },
+        }
+    }
+
+    fn create_issue_resolver() -> MockIssueResolverTrait {
+        let mut issue_resolver = MockIssueResolverTrait::new();
+        issue_resolver
+            .expect_resolve_issues()
+            .withf(|issue_ids| issue_ids == ["10001".to_string(), "10002".to_string()])
+            .times(1)
+            .returning(|_| {
+                Ok(BTreeMap::from([
+                    ("10001".to_string(), resolved_issue("ABC-1", "Design")),
+                    ("10002".to_string(), resolved_issue("ABC-2", "Review")),
+                ]))
+            });
+        issue_resolver
+    }
+
+    fn create_uut() -> UserWorklogsFetcher<String, Client> {
+        UserWorklogsFetcher::new(
+            mockito::server_url(),
+            Client::new("dummy_token"),
+            Box::new(create_issue_resolver()),
+        )
+        .with_timezone(chrono_tz::Europe::Warsaw)
+    }
+
+    fn create_get_endpoint_mock(endpoint: &str, body: &str) -> mockito::Mock {
+        mockito::mock("GET", endpoint)
+            .match_header("authorization", "Bearer dummy_token")
+            .with_status(200)
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .with_body(body)
+            .create()
+    }
+
+    #[test]
+    fn fetch_pages_through_results_resolves_issues_and_maps_tempo_attributes() {
+        let uut = create_uut();
+        let start_date = NaiveDate::from_ymd(2022, 9, 10);
+        let end_date = NaiveDate::from_ymd(2022, 9, 17);
+
+        let _first_page_mock = create_get_endpoint_mock(
+            "/worklogs/user/557058%3A0001?from=2022-09-10&to=2022-09-17&offset=0&limit=50",
+            &format!(
+                r###"{{
+                    "metadata": {{ "count": 1, "next": "{}/worklogs/user/557058%3A0001?page=2" }},
+                    "results": [{{
+                        "tempoWorklogId": 42,
+                        "jiraWorklogId": 10100,
+                        "issue": {{ "id": 10001 }},
+                        "timeSpentSeconds": 3600,
+                        "billableSeconds": 1800,
+                        "startDate": "2022-09-15",
+                        "startTime": "09:30:00",
+                        "description": "Planning",
+                        "author": {{ "accountId": "{}" }},
+                        "createdAt": "2022-09-15T10:00:00Z",
+                        "attributes": {{ "values": [
+                            {{ "key": "_Account_", "value": "ACME" }},
+                            {{ "key": "_Phase_", "value": "Design" }}
+                        ] }}
+                    }}]
+                }}"###,
+                mockito::server_url(),
+                ACCOUNT_ID
+            ),
+        );
+        let _second_page_mock = create_get_endpoint_mock(
+            "/worklogs/user/557058%3A0001?page=2",
+            r###"{
+                "metadata": { "count": 1 },
+                "results": [{
+                    "tempoWorklogId": 43,
+                    "issue": { "id": 10002 },
+                    "timeSpentSeconds": 600,
+                    "startDate": "2022-09-18",
+                    "startTime": "08:00:00",
+                    "description": ""
+                }]
+            }"###,
+        );
+
+        let result = uut.fetch(ACCOUNT_ID, start_date, end_date).unwrap();
+
+        let expected_worklog = Worklog::new(
+            DateTime::parse_from_rfc3339("2022-09-15T09:30:00+02:00").unwrap(),
+            "ABC-1",
+            "Design",
+            Duration::hours(1),
+        )
+        .with_issue(resolved_issue("ABC-1", "Design").info)
+        .with_id("42")
+        .with_issue_id("10001")
+        .with_comment("Planning")
+        .with_author(WorklogAuthor {
+            account_id: Some(ACCOUNT_ID.to_string()),
+            ..Default::default()
+        })
+        .with_created(DateTime::parse_from_rfc3339("2022-09-15T10:00:00Z").unwrap())
+        .with_metadata(JIRA_WORKLOG_ID_METADATA_KEY, "10100")
+        .with_metadata("tempo.account", "ACME")
+        .with_metadata("tempo.attribute._Phase_", "Design")
+        .with_metadata("tempo.billableSeconds", "1800");
+        assert_eq!(
+            result,
+            UserWorklogs::new(ACCOUNT_ID, start_date, end_date, vec![expected_worklog])
+        );
+    }
+
+    #[test]
+    fn fetch_returns_status_error_if_token_is_rejected() {
+        let uut = UserWorklogsFetcher::new(
+            mockito::server_url(),
+            Client::new("dummy_token"),
+            Box::new(MockIssueResolverTrait::new()),
+        );
+        let date = NaiveDate::from_ymd(2022, 9, 10);
+
+        let _unauthorized_mock = mockito::mock(
+            "GET",
+            "/worklogs/user/557058%3A0001?from=2022-09-10&to=2022-09-10&offset=0&limit=50",
+        )
+        .with_status(401)
+        .create();
+
+        let error = uut
+            .fetch(ACCOUNT_ID, date, date)
+            .unwrap_err()
+            .downcast::<reqwest::Error>()
+            .unwrap();
+        assert!(error.is_status());
+    }
+
+    #[test]
+    fn fetch_reads_local_times_as_utc_and_reports_no_timezone_unless_configured() {
+        let uut = UserWorklogsFetcher::new(
+            mockito::server_url(),
+            Client::new("dummy_token"),
+            Box::new(create_issue_resolver()),
+        );
+        let date = NaiveDate::from_ymd(2022, 9, 12);
+
+        let _worklogs_mock = create_get_endpoint_mock(
+            "/worklogs/user/557058%3A0001?from=2022-09-12&to=2022-09-12&offset=0&limit=50",
+            r###"{
+                "metadata": { "count": 2 },
+                "results": [
+                    {
+                        "issue": { "id": 10001 },
+                        "timeSpentSeconds": 600,
+                        "startDate": "2022-09-12",
+                        "startTime": "23:30:00"
+                    },
+                    {
+                        "issue": { "id": 10002 },
+                        "timeSpentSeconds": 600,
+                        "startDate": "2022-09-13",
+                        "startTime": "00:30:00"
+                    }
+                ]
+            }"###,
+        );
+
+        let result = uut.fetch(ACCOUNT_ID, date, date).unwrap();
+
+        assert_eq!(uut.timezone(), None);
+        assert_eq!(result.worklogs.len(), 1);
+        assert_eq!(result.worklogs[0].issue_key, "ABC-1");
+        assert_eq!(
+            result.worklogs[0].started,
+            DateTime::parse_from_rfc3339("2022-09-12T23:30:00+00:00").unwrap()
+        );
+    }
+
+    #[test]
+    fn fetch_moves_start_times_in_dst_gap_to_its_end() {
+        let uut = create_uut();
+        let date = NaiveDate::from_ymd(2022, 3, 27);
+
+        let _worklogs_mock = create_get_endpoint_mock(
+            "/worklogs/user/557058%3A0001?from=2022-03-27&to=2022-03-27&offset=0&limit=50",
+            r###"{
+                "metadata": { "count": 2 },
+                "results": [
+                    {
+                        "issue": { "id": 10001 },
+                        "timeSpentSeconds": 600,
+                        "startDate": "2022-03-27",
+                        "startTime": "02:30:00"
+                    },
+                    {
+                        "issue": { "id": 10002 },
+                        "timeSpentSeconds": 600,
+                        "startDate": "2022-03-27",
+                        "startTime": "10:00:00"
+                    }
+                ]
+            }"###,
+        );
+
+        let result = uut.fetch(ACCOUNT_ID, date, date).unwrap();
+
+        assert_eq!(
+            result
+                .worklogs
+                .iter()
+                .map(|worklog| worklog.started)
+                .collect::<Vec<_>>(),
+            vec![
+                DateTime::parse_from_rfc3339("2022-03-27T03:00:00+02:00").unwrap(),
+                DateTime::parse_from_rfc3339("2022-03-27T10:00:00+02:00").unwrap(),
+            ]
+        );
+    }
+
+    #[test]
+    fn fetch_keeps_issue_id_as_key_if_issue_cannot_be_resolved() {
+        let mut issue_resolver = MockIssueResolverTrait::new();
+        issue_resolver
+            .expect_resolve_issues()
+            .returning(|_| Ok(BTreeMap::new()));
+        let uut = UserWorklogsFetcher::new(
+            mockito::server_url(),
+            Client::new("dummy_token"),
+            Box::new(issue_resolver),
+        );
+        let date = NaiveDate::from_ymd(2022, 9, 14);
+
+        let _worklogs_mock = create_get_endpoint_mock(
+            "/worklogs/user/557058%3A0001?from=2022-09-14&to=2022-09-14&offset=0&limit=50",
+            r###"{
+                "metadata": { "count": 1 },
+                "results": [{
+                    "issue": { "id": 10003 },
+                    "timeSpentSeconds": 600,
+                    "startDate": "2022-09-14"
+                }]
+            }"###,
+        );
+
+        let result = uut.fetch(ACCOUNT_ID, date, date).unwrap();
+
+        assert_eq!(
+            result.worklogs,
+            vec![Worklog::new(
+                DateTime::parse_from_rfc3339("2022-09-14T00:00:00+00:00").unwrap(),
+                "10003",
+                "",
+                Duration::minutes(10),
+            )
+            .with_issue_id("10003")
+            .with_author(WorklogAuthor::default())]
+        );
+    }
+
+    #[test]
+    fn fetch_returns_error_if_time_spent_is_out_of_range() {
+        let uut = UserWorklogsFetcher::new(
+            mockito::server_url(),
+            Client::new("dummy_token"),
+            Box::new(create_issue_resolver()),
+        );
+        let date = NaiveDate::from_ymd(2022, 9, 15);
+
+        let _worklogs_mock = create_get_endpoint_mock(
+            "/worklogs/user/557058%3A0001?from=2022-09-15&to=2022-09-15&offset=0&limit=50",
+            r###"{
+                "metadata": { "count": 2 },
+                "results": [
+                    {
+                        "issue": { "id": 10001 },
+                        "timeSpentSeconds": 9223372036854775807,
+                        "startDate": "2022-09-15"
+                    },
+                    {
+                        "issue": { "id": 10002 },
+                        "timeSpentSeconds": 600,
+                        "startDate": "2022-09-15"
+                    }
+                ]
+            }"###,
+        );
+
+        assert!(uut.fetch(ACCOUNT_ID, date, date).is_err());
+    }
+}
@@ -0,0 +1,12 @@
+use chrono::{DateTime, FixedOffset, NaiveDate, TimeZone, Utc};
+
+/// Worklog start time at noon UTC of `date`. It stays on `date` from UTC-12 up to UTC+11:59;
+/// from UTC+12 (New Zealand, Kiribati) it falls on the next day, so tests in those timezones
+/// use `started_at_hour`.
+pub fn started_at(date: NaiveDate) -> DateTime<FixedOffset> {
+    started_at_hour(date, 12)
+}
+
+pub fn started_at_hour(date: NaiveDate, hour: u32) -> DateTime<FixedOffset> {
+    Utc.from_utc_datetime(&date.and_hms(hour, 0, 0)).into()
+}
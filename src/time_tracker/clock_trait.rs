@@ -0,0 +1,9 @@
+use chrono::{DateTime, Utc};
+
+#[cfg(test)]
+use mockall::{automock, predicate::*};
+
+#[cfg_attr(test, automock)]
+pub trait ClockTrait {
+    fn now(&self) -> DateTime<Utc>;
+}
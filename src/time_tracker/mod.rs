@@ -1,14 +1,27 @@
+mod clock_trait;
+mod period;
+mod period_summary;
 mod summary;
 mod summary_trait;
+mod system_clock;
+mod time_breakdown;
 mod user_worklogs_summary;
+mod work_schedule;
 mod worklog_summary;
 
+pub use clock_trait::ClockTrait;
+pub use period::{Period, PeriodGranularity};
+pub use period_summary::PeriodSummary;
 pub use summary::Summary;
 pub use summary_trait::SummaryTrait;
+pub use system_clock::SystemClock;
+pub use time_breakdown::{BreakdownEntry, BreakdownGrouping, TimeBreakdown};
 pub use user_worklogs_summary::UserWorklogsSummary;
+pub use work_schedule::WorkSchedule;
 pub use worklog_summary::WorklogSummary;
 
 #[cfg(test)]
 pub mod testing {
+    pub use super::clock_trait::MockClockTrait;
     pub use super::summary_trait::MockSummaryTrait;
 }
@@ -0,0 +1,92 @@
+use chrono::{Datelike, NaiveDate};
+use std::fmt::Display;
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub enum PeriodGranularity {
+    Week,
+    Month,
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub enum Period {
+    Week { year: i32, week: u32 },
+    Month { year: i32, month: u32 },
+}
+
+impl PeriodGranularity {
+    pub fn period_of(&self, date: &NaiveDate) -> Period {
+        match self {
+            PeriodGranularity::Week => Period::week_of(date),
+            PeriodGranularity::Month => Period::month_of(date),
+        }
+    }
+}
+
+impl Period {
+    pub fn week_of(date: &NaiveDate) -> Self {
+        let iso_week = date.iso_week();
+        Period::Week {
+            year: iso_week.year(),
+            week: iso_week.week(),
+        }
+    }
+
+    pub fn month_of(date: &NaiveDate) -> Self {
+        Period::Month {
+            year: date.year(),
+            month: date.month(),
+        }
+    }
+}
+
+impl Display for Period {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Period::Week { year, week } => write!(f, "{}-W{:02}", year, week),
+            Period::Month { year, month } => write!(f, "{}-{:02}", year, month),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn week_of_returns_iso_week_of_the_date() {
+        assert_eq!(
+            Period::week_of(&NaiveDate::from_ymd(2023, 1, 1)),
+            Period::Week {
+                year: 2022,
+                week: 52
+            }
+        );
+        assert_eq!(
+            Period::week_of(&NaiveDate::from_ymd(2023, 1, 2)),
+            Period::Week {
+                year: 2023,
+                week: 1
+            }
+        );
+    }
+
+    #[test]
+    fn period_is_displayed_in_iso_format() {
+        assert_eq!(
+            Period::Week {
+                year: 2022,
+                week: 7
+            }
+            .to_string(),
+            "2022-W07"
+        );
+        assert_eq!(
+            Period::Month {
+                year: 2022,
+                month: 9
+            }
+            .to_string(),
+            "2022-09"
+        );
+    }
+}
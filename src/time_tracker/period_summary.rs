@@ -0,0 +1,13 @@
+use super::period::Period;
+use chrono::{Duration, NaiveDate};
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct PeriodSummary {
+    pub period: Period,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub logged: Duration,
+    pub expected: Duration,
+    pub delta: Duration,
+    pub balance: Duration,
+}
@@ -1,9 +1,12 @@
 use super::{
     user_worklogs_summary::UserWorklogsSummary, worklog_summary::WorklogSummary, SummaryTrait,
 };
-use crate::jira::UserWorklogsFetcherTrait;
+use crate::jira::{TimeTrackingConfig, UserWorklogs, UserWorklogsFetcherTrait};
+use crate::sources::SourceFailure;
 use chrono::Duration;
+use chrono_tz::Tz;
 use itertools::Itertools;
+use std::collections::BTreeMap;
 
 pub struct Summary<UserWorklogsFetcherType>
 where
@@ -26,10 +29,65 @@ where
             .user_worklog_fetcher
             .fetch(user_name, start_date, end_date)?;
 
-        let user_worklogs_by_date = user_worklogs
-            .worklogs
+        Ok(self.summarize(user_worklogs, start_date, end_date))
+    }
+
+    fn timezone(&self) -> Option<Tz> {
+        self.user_worklog_fetcher.timezone()
+    }
+
+    fn time_tracking(&self) -> Option<TimeTrackingConfig> {
+        self.user_worklog_fetcher.time_tracking()
+    }
+
+    fn source_failures(&self) -> Vec<SourceFailure> {
+        self.user_worklog_fetcher.source_failures()
+    }
+
+    fn get_team_worklogs_summary(
+        &self,
+        user_names: &[String],
+        start_date: chrono::NaiveDate,
+        end_date: chrono::NaiveDate,
+    ) -> anyhow::Result<BTreeMap<String, UserWorklogsSummary>> {
+        Ok(self
+            .user_worklog_fetcher
+            .fetch_team(user_names, start_date, end_date)?
             .into_iter()
-            .into_group_map_by(|worklog| worklog.data);
+            .map(|(user_name, user_worklogs)| {
+                (
+                    user_name,
+                    self.summarize(user_worklogs, start_date, end_date),
+                )
+            })
+            .collect())
+    }
+}
+
+impl<UserWorklogsFetcherType> Summary<UserWorklogsFetcherType>
+where
+    UserWorklogsFetcherType: UserWorklogsFetcherTrait,
+{
+    pub fn new(user_worklog_fetcher: UserWorklogsFetcherType) -> Self {
+        Self {
+            user_worklog_fetcher,
+        }
+    }
+
+    fn summarize(
+        &self,
+        user_worklogs: UserWorklogs,
+        start_date: chrono::NaiveDate,
+        end_date: chrono::NaiveDate,
+    ) -> UserWorklogsSummary {
+        let user_worklogs_by_date =
+            user_worklogs
+                .worklogs
+                .into_iter()
+                .into_group_map_by(|worklog| match &self.timezone() {
+                    Some(timezone) => worklog.date_in(timezone),
+                    None => worklog.date(),
+                });
 
         let summary = start_date
             .iter_days()
@@ -53,34 +111,24 @@ where
             })
             .collect();
 
-        Ok(UserWorklogsSummary(summary))
-    }
-}
-
-impl<UserWorklogsFetcherType> Summary<UserWorklogsFetcherType>
-where
-    UserWorklogsFetcherType: UserWorklogsFetcherTrait,
-{
-    pub fn new(user_worklog_fetcher: UserWorklogsFetcherType) -> Self {
-        Self {
-            user_worklog_fetcher,
-        }
+        UserWorklogsSummary(summary)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::jira::{testing::MockUserWorklogsFetcherTrait, UserWorklogs, Worklog};
+    use crate::jira::{testing::MockUserWorklogsFetcherTrait, Worklog};
+    use crate::test_helpers::started_at;
     use anyhow::anyhow;
-    use chrono::{Duration, NaiveDate};
+    use chrono::{DateTime, Duration, NaiveDate};
     use mockall::predicate::eq;
 
     #[test]
     fn get_user_worklogs_summary_returns_error_if_worklog_fetcher_fail() {
         let user_name = "dummy_user_name";
-        let start_date = NaiveDate::from_ymd(2022, 09, 10);
-        let end_date = NaiveDate::from_ymd(2022, 09, 19);
+        let start_date = NaiveDate::from_ymd(2022, 9, 10);
+        let end_date = NaiveDate::from_ymd(2022, 9, 19);
 
         let error_message = "some dummy error message";
 
@@ -90,6 +138,7 @@ mod tests {
             .with(eq(user_name), eq(start_date), eq(end_date))
             .return_once(move |_, _, _| Err(anyhow!(error_message)));
 
+        user_worklog_fetcher.expect_timezone().return_const(None);
         let uut = Summary::new(user_worklog_fetcher);
 
         let result = uut.get_user_worklogs_summary(user_name, start_date, end_date);
@@ -99,9 +148,9 @@ mod tests {
     #[test]
     fn get_user_worklogs_summary_return_valid_summary() {
         let user_name = "dummy_user_name";
-        let start_date = NaiveDate::from_ymd(2022, 09, 10);
-        let end_date = NaiveDate::from_ymd(2022, 09, 19);
-        let other_date = NaiveDate::from_ymd(2022, 09, 15);
+        let start_date = NaiveDate::from_ymd(2022, 9, 10);
+        let end_date = NaiveDate::from_ymd(2022, 9, 19);
+        let other_date = NaiveDate::from_ymd(2022, 9, 15);
 
         let dummy_duration1 = Duration::minutes(20);
         let dummy_duration2 = Duration::hours(4);
@@ -109,43 +158,43 @@ mod tests {
         let dummy_duration4 = Duration::hours(1);
 
         let worklog1_start_date = Worklog::new(
-            start_date,
+            started_at(start_date),
             "start_date-key1",
             "start_date-summary1",
             dummy_duration1,
         );
         let worklog1_end_date = Worklog::new(
-            end_date,
+            started_at(end_date),
             "end_date-key1",
             "end_date-summary1",
             dummy_duration2,
         );
         let worklog2_end_date = Worklog::new(
-            end_date,
+            started_at(end_date),
             "end_date-key2",
             "end_date-summary2",
             dummy_duration3,
         );
         let worklog1_other_date = Worklog::new(
-            other_date,
+            started_at(other_date),
             "other_date-key1",
             "other_date-summary1",
             dummy_duration1,
         );
         let worklog2_other_date = Worklog::new(
-            other_date,
+            started_at(other_date),
             "other_date-key2",
             "other_date-summary2",
             dummy_duration2,
         );
         let worklog3_other_date = Worklog::new(
-            other_date,
+            started_at(other_date),
             "other_date-key3",
             "other_date-summary3",
             dummy_duration3,
         );
         let worklog4_other_date = Worklog::new(
-            other_date,
+            started_at(other_date),
             "other_date-key4",
             "other_date-summary4",
             dummy_duration4,
@@ -172,6 +221,7 @@ mod tests {
             .with(eq(user_name), eq(start_date), eq(end_date))
             .return_once(move |_, _, _| Ok(user_worklogs));
 
+        user_worklog_fetcher.expect_timezone().return_const(None);
         let uut = Summary::new(user_worklog_fetcher);
 
         let mut expected_result = UserWorklogsSummary::new();
@@ -218,4 +268,95 @@ mod tests {
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), expected_result);
     }
+
+    #[test]
+    fn get_user_worklogs_summary_assigns_worklogs_to_days_in_configured_timezone() {
+        let user_name = "dummy_user_name";
+        let start_date = NaiveDate::from_ymd(2022, 10, 29);
+        let end_date = NaiveDate::from_ymd(2022, 10, 31);
+
+        let worklog_1 = Worklog::new(
+            DateTime::parse_from_rfc3339("2022-10-29T23:30:00+02:00").unwrap(),
+            "key1",
+            "summary1",
+            Duration::hours(1),
+        );
+        let worklog_2 = Worklog::new(
+            DateTime::parse_from_rfc3339("2022-10-30T23:30:00+00:00").unwrap(),
+            "key2",
+            "summary2",
+            Duration::hours(2),
+        );
+
+        let user_worklogs = UserWorklogs::new(
+            user_name,
+            start_date,
+            end_date,
+            vec![worklog_1.clone(), worklog_2.clone()],
+        );
+        let mut user_worklog_fetcher = MockUserWorklogsFetcherTrait::new();
+        user_worklog_fetcher
+            .expect_fetch()
+            .return_once(move |_, _, _| Ok(user_worklogs));
+
+        user_worklog_fetcher
+            .expect_timezone()
+            .return_const(Some(chrono_tz::Europe::Warsaw));
+        let uut = Summary::new(user_worklog_fetcher);
+
+        let result = uut
+            .get_user_worklogs_summary(user_name, start_date, end_date)
+            .unwrap();
+        assert_eq!(
+            result[&NaiveDate::from_ymd(2022, 10, 29)].worklogs,
+            vec![worklog_1]
+        );
+        assert!(result[&NaiveDate::from_ymd(2022, 10, 30)]
+            .worklogs
+            .is_empty());
+        assert_eq!(
+            result[&NaiveDate::from_ymd(2022, 10, 31)].worklogs,
+            vec![worklog_2]
+        );
+    }
+
+    #[test]
+    fn get_team_worklogs_summary_returns_summary_per_user_from_single_team_fetch() {
+        let user_names = vec!["user_a".to_string(), "user_b".to_string()];
+        let start_date = NaiveDate::from_ymd(2022, 9, 10);
+        let end_date = NaiveDate::from_ymd(2022, 9, 11);
+
+        let worklog = Worklog::new(started_at(end_date), "key1", "summary1", Duration::hours(3));
+        let team_worklogs = BTreeMap::from([
+            (
+                "user_a".to_string(),
+                UserWorklogs::new("user_a", start_date, end_date, vec![worklog.clone()]),
+            ),
+            (
+                "user_b".to_string(),
+                UserWorklogs::new("user_b", start_date, end_date, vec![]),
+            ),
+        ]);
+
+        let mut user_worklog_fetcher = MockUserWorklogsFetcherTrait::new();
+        user_worklog_fetcher.expect_fetch().never();
+        user_worklog_fetcher
+            .expect_fetch_team()
+            .withf(move |names, start, end| {
+                names == ["user_a", "user_b"] && *start == start_date && *end == end_date
+            })
+            .return_once(move |_, _, _| Ok(team_worklogs));
+
+        user_worklog_fetcher.expect_timezone().return_const(None);
+        let uut = Summary::new(user_worklog_fetcher);
+
+        let result = uut
+            .get_team_worklogs_summary(&user_names, start_date, end_date)
+            .unwrap();
+        assert_eq!(result.len(), 2);
+        assert_eq!(result["user_a"][&end_date].spent_time, Duration::hours(3));
+        assert_eq!(result["user_a"][&end_date].worklogs, vec![worklog]);
+        assert_eq!(result["user_b"][&start_date].spent_time, Duration::zero());
+        assert_eq!(result["user_b"].len(), 2);
+    }
 }
@@ -1,6 +1,10 @@
 use super::user_worklogs_summary::UserWorklogsSummary;
+use crate::jira::TimeTrackingConfig;
+use crate::sources::SourceFailure;
+use chrono_tz::Tz;
 #[cfg(test)]
 use mockall::{automock, predicate::*};
+use std::collections::BTreeMap;
 
 #[cfg_attr(test, automock)]
 pub trait SummaryTrait {
@@ -10,4 +14,34 @@ pub trait SummaryTrait {
         start_date: chrono::NaiveDate,
         end_date: chrono::NaiveDate,
     ) -> anyhow::Result<UserWorklogsSummary>;
+
+    /// Timezone the summary days are in, see `UserWorklogsFetcherTrait::timezone`.
+    fn timezone(&self) -> Option<Tz> {
+        None
+    }
+
+    /// See `UserWorklogsFetcherTrait::time_tracking`.
+    fn time_tracking(&self) -> Option<TimeTrackingConfig> {
+        None
+    }
+
+    /// See `UserWorklogsFetcherTrait::source_failures`.
+    fn source_failures(&self) -> Vec<SourceFailure> {
+        vec![]
+    }
+
+    fn get_team_worklogs_summary(
+        &self,
+        user_names: &[String],
+        start_date: chrono::NaiveDate,
+        end_date: chrono::NaiveDate,
+    ) -> anyhow::Result<BTreeMap<String, UserWorklogsSummary>> {
+        user_names
+            .iter()
+            .map(|user_name| {
+                self.get_user_worklogs_summary(user_name, start_date, end_date)
+                    .map(|summary| (user_name.clone(), summary))
+            })
+            .collect()
+    }
 }
@@ -0,0 +1,11 @@
+use super::clock_trait::ClockTrait;
+use chrono::{DateTime, Utc};
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemClock;
+
+impl ClockTrait for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
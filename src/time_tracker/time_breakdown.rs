@@ -0,0 +1,213 @@
+use crate::jira::Worklog;
+use chrono::{DateTime, Duration, FixedOffset};
+use itertools::Itertools;
+use std::collections::BTreeMap;
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum BreakdownGrouping {
+    Issue,
+    Project,
+    Parent,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct BreakdownEntry {
+    pub key: String,
+    pub label: String,
+    pub spent_time: Duration,
+    pub percentage: f64,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct TimeBreakdown {
+    pub grouping: BreakdownGrouping,
+    pub total: Duration,
+    pub entries: Vec<BreakdownEntry>,
+}
+
+impl BreakdownGrouping {
+    // Issues without a parent are top-level items, so they are reported under their own key.
+    fn key_and_label(&self, worklog: &Worklog) -> (String, String) {
+        match self {
+            BreakdownGrouping::Issue => (worklog.issue_key.clone(), worklog.issue_summary.clone()),
+            BreakdownGrouping::Project => (
+                worklog.project_key().to_string(),
+                worklog.project_key().to_string(),
+            ),
+            BreakdownGrouping::Parent => match worklog.parent_key() {
+                Some(parent_key) => (parent_key.to_string(), parent_key.to_string()),
+                None => (worklog.issue_key.clone(), worklog.issue_summary.clone()),
+            },
+        }
+    }
+}
+
+impl TimeBreakdown {
+    /// Groups by key only; an entry is labelled like its latest worklog, so an issue renamed
+    /// mid-period, or summarized differently by two sources, stays on one line.
+    pub fn new<'a>(
+        grouping: BreakdownGrouping,
+        worklogs: impl IntoIterator<Item = &'a Worklog>,
+    ) -> Self {
+        let mut time_by_key: BTreeMap<String, (String, DateTime<FixedOffset>, Duration)> =
+            BTreeMap::new();
+        for worklog in worklogs {
+            let (key, label) = grouping.key_and_label(worklog);
+            let (entry_label, latest_started, spent_time) = time_by_key
+                .entry(key)
+                .or_insert_with(|| (label.clone(), worklog.started, Duration::seconds(0)));
+            if worklog.started > *latest_started {
+                *entry_label = label;
+                *latest_started = worklog.started;
+            }
+            *spent_time = *spent_time + worklog.time_spent;
+        }
+        let total = time_by_key
+            .values()
+            .fold(Duration::seconds(0), |acc, (_, _, time_spent)| {
+                acc + *time_spent
+            });
+
+        let entries = time_by_key
+            .into_iter()
+            .map(|(key, (label, _, spent_time))| BreakdownEntry {
+                key,
+                label,
+                spent_time,
+                percentage: Self::percentage(spent_time, total),
+            })
+            .sorted_by(|lhs, rhs| {
+                rhs.spent_time
+                    .cmp(&lhs.spent_time)
+                    .then_with(|| lhs.key.cmp(&rhs.key))
+            })
+            .collect();
+
+        Self {
+            grouping,
+            total,
+            entries,
+        }
+    }
+
+    pub fn top(&self, count: usize) -> &[BreakdownEntry] {
+        &self.entries[..count.min(self.entries.len())]
+    }
+
+    fn percentage(spent_time: Duration, total: Duration) -> f64 {
+        if total.is_zero() {
+            0.0
+        } else {
+            100.0 * spent_time.num_seconds() as f64 / total.num_seconds() as f64
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::{started_at, started_at_hour};
+    use chrono::NaiveDate;
+    use lazy_static::lazy_static;
+
+    lazy_static! {
+        static ref DATE: NaiveDate = NaiveDate::from_ymd(2022, 9, 15);
+        static ref WORKLOGS: Vec<Worklog> = vec![
+            Worklog::new(started_at(*DATE), "PROJ-1", "Story 1", Duration::hours(2))
+                .with_parent_key("PROJ-10"),
+            Worklog::new(started_at(*DATE), "PROJ-2", "Story 2", Duration::hours(1))
+                .with_parent_key("PROJ-10"),
+            Worklog::new(started_at(*DATE), "PROJ-1", "Story 1", Duration::hours(3))
+                .with_parent_key("PROJ-10"),
+            Worklog::new(started_at(*DATE), "OPS-7", "Support", Duration::hours(2)),
+        ];
+    }
+
+    fn entry(key: &str, label: &str, hours: i64, percentage: f64) -> BreakdownEntry {
+        BreakdownEntry {
+            key: key.to_string(),
+            label: label.to_string(),
+            spent_time: Duration::hours(hours),
+            percentage,
+        }
+    }
+
+    #[test]
+    fn new_groups_time_by_issue_ordered_by_spent_time() {
+        let uut = TimeBreakdown::new(BreakdownGrouping::Issue, WORKLOGS.iter());
+
+        assert_eq!(uut.total, Duration::hours(8));
+        assert_eq!(
+            uut.entries,
+            vec![
+                entry("PROJ-1", "Story 1", 5, 62.5),
+                entry("OPS-7", "Support", 2, 25.0),
+                entry("PROJ-2", "Story 2", 1, 12.5),
+            ]
+        );
+    }
+
+    #[test]
+    fn new_groups_time_by_project() {
+        let uut = TimeBreakdown::new(BreakdownGrouping::Project, WORKLOGS.iter());
+
+        assert_eq!(
+            uut.entries,
+            vec![entry("PROJ", "PROJ", 6, 75.0), entry("OPS", "OPS", 2, 25.0)]
+        );
+    }
+
+    #[test]
+    fn new_groups_time_by_parent_and_keeps_issues_without_parent() {
+        let uut = TimeBreakdown::new(BreakdownGrouping::Parent, WORKLOGS.iter());
+
+        assert_eq!(
+            uut.entries,
+            vec![
+                entry("PROJ-10", "PROJ-10", 6, 75.0),
+                entry("OPS-7", "Support", 2, 25.0)
+            ]
+        );
+    }
+
+    #[test]
+    fn new_keeps_renamed_issue_on_one_line_labelled_by_latest_worklog() {
+        let worklogs = [
+            Worklog::new(
+                started_at_hour(*DATE, 15),
+                "PROJ-1",
+                "Renamed story",
+                Duration::hours(1),
+            ),
+            Worklog::new(
+                started_at_hour(*DATE, 9),
+                "PROJ-1",
+                "Story 1",
+                Duration::hours(2),
+            ),
+        ];
+
+        let uut = TimeBreakdown::new(BreakdownGrouping::Issue, worklogs.iter());
+
+        assert_eq!(
+            uut.entries,
+            vec![entry("PROJ-1", "Renamed story", 3, 100.0)]
+        );
+    }
+
+    #[test]
+    fn top_returns_at_most_requested_number_of_entries() {
+        let uut = TimeBreakdown::new(BreakdownGrouping::Issue, WORKLOGS.iter());
+
+        assert_eq!(uut.top(1), &[entry("PROJ-1", "Story 1", 5, 62.5)]);
+        assert_eq!(uut.top(10).len(), 3);
+    }
+
+    #[test]
+    fn new_returns_empty_breakdown_for_no_worklogs() {
+        let uut = TimeBreakdown::new(BreakdownGrouping::Issue, &[]);
+
+        assert_eq!(uut.total, Duration::seconds(0));
+        assert!(uut.entries.is_empty());
+    }
+}
@@ -1,13 +1,21 @@
-use super::worklog_summary::WorklogSummary;
-use chrono::NaiveDate;
-use std::collections::HashMap;
+use super::{
+    period::PeriodGranularity,
+    period_summary::PeriodSummary,
+    time_breakdown::{BreakdownGrouping, TimeBreakdown},
+    work_schedule::WorkSchedule,
+    worklog_summary::WorklogSummary,
+};
+use chrono::{Datelike, Duration, NaiveDate};
+use itertools::Itertools;
+use std::collections::{btree_map::Range, BTreeMap};
 use std::ops::{Deref, DerefMut};
 
 #[derive(Debug, PartialEq, Eq, Clone)]
-pub struct UserWorklogsSummary(pub HashMap<NaiveDate, WorklogSummary>);
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UserWorklogsSummary(pub BTreeMap<NaiveDate, WorklogSummary>);
 
 impl Deref for UserWorklogsSummary {
-    type Target = HashMap<NaiveDate, WorklogSummary>;
+    type Target = BTreeMap<NaiveDate, WorklogSummary>;
 
     fn deref(&self) -> &Self::Target {
         &self.0
@@ -20,8 +28,371 @@ impl DerefMut for UserWorklogsSummary {
     }
 }
 
+impl Default for UserWorklogsSummary {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FromIterator<(NaiveDate, WorklogSummary)> for UserWorklogsSummary {
+    fn from_iter<T: IntoIterator<Item = (NaiveDate, WorklogSummary)>>(iter: T) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
 impl UserWorklogsSummary {
     pub fn new() -> Self {
-        Self(HashMap::new())
+        Self(BTreeMap::new())
+    }
+
+    pub fn days_between(
+        &self,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> Range<'_, NaiveDate, WorklogSummary> {
+        if start_date > end_date {
+            return self.0.range(start_date..start_date);
+        }
+        self.0.range(start_date..=end_date)
+    }
+
+    pub fn week_of(&self, date: NaiveDate) -> Range<'_, NaiveDate, WorklogSummary> {
+        let monday = date - Duration::days(date.weekday().num_days_from_monday() as i64);
+        self.days_between(monday, monday + Duration::days(6))
+    }
+
+    pub fn month_of(&self, date: NaiveDate) -> Range<'_, NaiveDate, WorklogSummary> {
+        let first_day = NaiveDate::from_ymd(date.year(), date.month(), 1);
+        let first_day_of_next_month = match date.month() {
+            12 => NaiveDate::from_ymd(date.year() + 1, 1, 1),
+            month => NaiveDate::from_ymd(date.year(), month + 1, 1),
+        };
+        self.days_between(first_day, first_day_of_next_month - Duration::days(1))
+    }
+
+    pub fn merge(mut self, other: UserWorklogsSummary) -> Self {
+        other
+            .0
+            .into_iter()
+            .for_each(|(date, worklog_summary)| match self.0.get_mut(&date) {
+                Some(existing_summary) => existing_summary.merge(worklog_summary),
+                None => {
+                    self.0.insert(date, worklog_summary);
+                }
+            });
+        self
+    }
+
+    pub fn breakdown(&self, grouping: BreakdownGrouping) -> TimeBreakdown {
+        TimeBreakdown::new(
+            grouping,
+            self.values()
+                .flat_map(|worklog_summary| worklog_summary.worklogs.iter()),
+        )
+    }
+
+    pub fn weekly_summaries(
+        &self,
+        schedule: &WorkSchedule,
+        opening_balance: Duration,
+    ) -> Vec<PeriodSummary> {
+        self.period_summaries(PeriodGranularity::Week, schedule, opening_balance)
+    }
+
+    pub fn monthly_summaries(
+        &self,
+        schedule: &WorkSchedule,
+        opening_balance: Duration,
+    ) -> Vec<PeriodSummary> {
+        self.period_summaries(PeriodGranularity::Month, schedule, opening_balance)
+    }
+
+    pub fn period_summaries(
+        &self,
+        granularity: PeriodGranularity,
+        schedule: &WorkSchedule,
+        opening_balance: Duration,
+    ) -> Vec<PeriodSummary> {
+        let mut balance = opening_balance;
+        let dates_by_period = self.keys().group_by(|date| granularity.period_of(date));
+
+        dates_by_period
+            .into_iter()
+            .map(|(period, dates)| {
+                let dates = dates.collect_vec();
+                let logged = dates.iter().fold(Duration::seconds(0), |acc, date| {
+                    acc + self[*date].spent_time
+                });
+                let expected = dates.iter().fold(Duration::seconds(0), |acc, date| {
+                    acc + schedule.expected_time(date)
+                });
+                let delta = logged - expected;
+                balance = balance + delta;
+                PeriodSummary {
+                    period,
+                    start_date: *dates[0],
+                    end_date: *dates[dates.len() - 1],
+                    logged,
+                    expected,
+                    delta,
+                    balance,
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jira::Worklog;
+    use crate::test_helpers::started_at;
+    use crate::time_tracker::Period;
+    use chrono::Weekday;
+
+    fn create_summary(start_date: NaiveDate, spent_times: &[Duration]) -> UserWorklogsSummary {
+        let mut summary = UserWorklogsSummary::new();
+        start_date
+            .iter_days()
+            .zip(spent_times)
+            .for_each(|(date, spent_time)| {
+                summary.insert(
+                    date,
+                    WorklogSummary {
+                        spent_time: *spent_time,
+                        worklogs: vec![],
+                    },
+                );
+            });
+        summary
+    }
+
+    fn create_schedule() -> WorkSchedule {
+        WorkSchedule::new(Duration::hours(8)).with_working_days(&[
+            Weekday::Mon,
+            Weekday::Tue,
+            Weekday::Wed,
+            Weekday::Thu,
+            Weekday::Fri,
+        ])
+    }
+
+    #[test]
+    fn weekly_summaries_aggregate_days_by_iso_week_and_carry_balance() {
+        let monday = NaiveDate::from_ymd(2022, 9, 12);
+        let mut spent_times = vec![Duration::hours(9), Duration::hours(7)];
+        spent_times.extend([Duration::hours(8); 3]);
+        spent_times.extend([Duration::hours(0); 2]);
+        spent_times.extend([Duration::hours(6), Duration::hours(8)]);
+        let summary = create_summary(monday, &spent_times);
+
+        let result = summary.weekly_summaries(&create_schedule(), Duration::hours(1));
+
+        assert_eq!(
+            result,
+            vec![
+                PeriodSummary {
+                    period: Period::Week {
+                        year: 2022,
+                        week: 37
+                    },
+                    start_date: monday,
+                    end_date: NaiveDate::from_ymd(2022, 9, 18),
+                    logged: Duration::hours(40),
+                    expected: Duration::hours(40),
+                    delta: Duration::hours(0),
+                    balance: Duration::hours(1),
+                },
+                PeriodSummary {
+                    period: Period::Week {
+                        year: 2022,
+                        week: 38
+                    },
+                    start_date: NaiveDate::from_ymd(2022, 9, 19),
+                    end_date: NaiveDate::from_ymd(2022, 9, 20),
+                    logged: Duration::hours(14),
+                    expected: Duration::hours(16),
+                    delta: Duration::hours(-2),
+                    balance: Duration::hours(-1),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn monthly_summaries_aggregate_days_by_calendar_month() {
+        let friday = NaiveDate::from_ymd(2022, 9, 30);
+        let summary = create_summary(
+            friday,
+            &[
+                Duration::hours(10),
+                Duration::hours(0),
+                Duration::hours(0),
+                Duration::hours(4),
+            ],
+        );
+
+        let result = summary.monthly_summaries(&create_schedule(), Duration::hours(0));
+
+        assert_eq!(
+            result
+                .iter()
+                .map(|period_summary| (
+                    period_summary.period,
+                    period_summary.delta,
+                    period_summary.balance
+                ))
+                .collect_vec(),
+            vec![
+                (
+                    Period::Month {
+                        year: 2022,
+                        month: 9
+                    },
+                    Duration::hours(2),
+                    Duration::hours(2)
+                ),
+                (
+                    Period::Month {
+                        year: 2022,
+                        month: 10
+                    },
+                    Duration::hours(-4),
+                    Duration::hours(-2)
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn iteration_is_ordered_chronologically() {
+        let mut summary = UserWorklogsSummary::new();
+        [17, 12, 15].iter().for_each(|day| {
+            summary.insert(
+                NaiveDate::from_ymd(2022, 9, *day),
+                WorklogSummary {
+                    spent_time: Duration::hours(8),
+                    worklogs: vec![],
+                },
+            );
+        });
+
+        assert_eq!(
+            summary.keys().copied().collect_vec(),
+            vec![
+                NaiveDate::from_ymd(2022, 9, 12),
+                NaiveDate::from_ymd(2022, 9, 15),
+                NaiveDate::from_ymd(2022, 9, 17),
+            ]
+        );
+    }
+
+    #[test]
+    fn range_queries_return_days_in_requested_range() {
+        let summary = create_summary(NaiveDate::from_ymd(2022, 9, 25), &[Duration::hours(8); 10]);
+
+        assert_eq!(
+            summary
+                .days_between(
+                    NaiveDate::from_ymd(2022, 9, 27),
+                    NaiveDate::from_ymd(2022, 9, 28)
+                )
+                .map(|(date, _)| *date)
+                .collect_vec(),
+            vec![
+                NaiveDate::from_ymd(2022, 9, 27),
+                NaiveDate::from_ymd(2022, 9, 28)
+            ]
+        );
+        assert_eq!(
+            summary
+                .days_between(
+                    NaiveDate::from_ymd(2022, 9, 28),
+                    NaiveDate::from_ymd(2022, 9, 27)
+                )
+                .count(),
+            0
+        );
+        assert_eq!(
+            summary
+                .week_of(NaiveDate::from_ymd(2022, 9, 28))
+                .map(|(date, _)| *date)
+                .collect_vec(),
+            NaiveDate::from_ymd(2022, 9, 26)
+                .iter_days()
+                .take(7)
+                .collect_vec()
+        );
+        assert_eq!(
+            summary
+                .month_of(NaiveDate::from_ymd(2022, 9, 28))
+                .map(|(date, _)| *date)
+                .collect_vec(),
+            NaiveDate::from_ymd(2022, 9, 25)
+                .iter_days()
+                .take(6)
+                .collect_vec()
+        );
+        assert_eq!(
+            summary.month_of(NaiveDate::from_ymd(2022, 10, 1)).count(),
+            4
+        );
+    }
+
+    #[test]
+    fn merge_combines_days_of_both_summaries() {
+        let date_1 = NaiveDate::from_ymd(2022, 9, 15);
+        let date_2 = NaiveDate::from_ymd(2022, 9, 16);
+        let worklog_1 = Worklog::new(started_at(date_1), "key1", "summary1", Duration::hours(3));
+        let worklog_2 = Worklog::new(started_at(date_1), "key2", "summary2", Duration::hours(5));
+        let worklog_3 = Worklog::new(started_at(date_2), "key3", "summary3", Duration::hours(8));
+
+        let lhs: UserWorklogsSummary = [(
+            date_1,
+            WorklogSummary {
+                spent_time: Duration::hours(3),
+                worklogs: vec![worklog_1.clone()],
+            },
+        )]
+        .into_iter()
+        .collect();
+        let rhs: UserWorklogsSummary = [
+            (
+                date_1,
+                WorklogSummary {
+                    spent_time: Duration::hours(5),
+                    worklogs: vec![worklog_2.clone()],
+                },
+            ),
+            (
+                date_2,
+                WorklogSummary {
+                    spent_time: Duration::hours(8),
+                    worklogs: vec![worklog_3.clone()],
+                },
+            ),
+        ]
+        .into_iter()
+        .collect();
+
+        let expected_result: UserWorklogsSummary = [
+            (
+                date_1,
+                WorklogSummary {
+                    spent_time: Duration::hours(8),
+                    worklogs: vec![worklog_1, worklog_2],
+                },
+            ),
+            (
+                date_2,
+                WorklogSummary {
+                    spent_time: Duration::hours(8),
+                    worklogs: vec![worklog_3],
+                },
+            ),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(lhs.merge(rhs), expected_result);
     }
 }
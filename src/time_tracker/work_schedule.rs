@@ -0,0 +1,107 @@
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use std::collections::BTreeSet;
+
+const ALL_WEEKDAYS: [Weekday; 7] = [
+    Weekday::Mon,
+    Weekday::Tue,
+    Weekday::Wed,
+    Weekday::Thu,
+    Weekday::Fri,
+    Weekday::Sat,
+    Weekday::Sun,
+];
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct WorkSchedule {
+    pub daily_time: Duration,
+    pub working_days: Vec<Weekday>,
+    pub holidays: BTreeSet<NaiveDate>,
+    pub absences: BTreeSet<NaiveDate>,
+}
+
+impl WorkSchedule {
+    pub fn new(daily_time: Duration) -> Self {
+        Self {
+            daily_time,
+            working_days: ALL_WEEKDAYS.to_vec(),
+            holidays: BTreeSet::new(),
+            absences: BTreeSet::new(),
+        }
+    }
+
+    pub fn with_working_days(mut self, working_days: &[Weekday]) -> Self {
+        self.working_days = working_days.to_vec();
+        self
+    }
+
+    pub fn with_holidays(mut self, holidays: &[NaiveDate]) -> Self {
+        self.holidays.extend(holidays);
+        self
+    }
+
+    pub fn with_absences(mut self, absences: &[NaiveDate]) -> Self {
+        self.absences.extend(absences);
+        self
+    }
+
+    pub fn is_holiday(&self, date: &NaiveDate) -> bool {
+        self.holidays.contains(date)
+    }
+
+    pub fn is_absence(&self, date: &NaiveDate) -> bool {
+        self.absences.contains(date)
+    }
+
+    pub fn is_working_day(&self, date: &NaiveDate) -> bool {
+        self.working_days.contains(&date.weekday())
+            && !self.is_holiday(date)
+            && !self.is_absence(date)
+    }
+
+    pub fn expected_time(&self, date: &NaiveDate) -> Duration {
+        if self.is_working_day(date) {
+            self.daily_time
+        } else {
+            Duration::seconds(0)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expected_time_returns_daily_time_only_for_working_days() {
+        let uut = WorkSchedule::new(Duration::hours(8)).with_working_days(&[
+            Weekday::Mon,
+            Weekday::Tue,
+            Weekday::Wed,
+            Weekday::Thu,
+            Weekday::Fri,
+        ]);
+
+        let friday = NaiveDate::from_ymd(2022, 9, 16);
+        let saturday = NaiveDate::from_ymd(2022, 9, 17);
+        assert_eq!(uut.expected_time(&friday), Duration::hours(8));
+        assert_eq!(uut.expected_time(&saturday), Duration::seconds(0));
+    }
+
+    #[test]
+    fn expected_time_is_zero_on_holidays_and_absences() {
+        let holiday = NaiveDate::from_ymd(2022, 11, 1);
+        let absence = NaiveDate::from_ymd(2022, 11, 2);
+        let uut = WorkSchedule::new(Duration::hours(8))
+            .with_holidays(&[holiday])
+            .with_absences(&[absence]);
+
+        assert_eq!(uut.expected_time(&holiday), Duration::seconds(0));
+        assert_eq!(uut.expected_time(&absence), Duration::seconds(0));
+        assert!(uut.is_holiday(&holiday) && !uut.is_absence(&holiday));
+        assert!(uut.is_absence(&absence) && !uut.is_holiday(&absence));
+        assert_eq!(
+            uut.expected_time(&NaiveDate::from_ymd(2022, 11, 3)),
+            Duration::hours(8)
+        );
+    }
+}
@@ -1,8 +1,28 @@
+use super::time_breakdown::{BreakdownGrouping, TimeBreakdown};
 use crate::jira::Worklog;
 use chrono::Duration;
 
 #[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct WorklogSummary {
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            rename = "spent_time_seconds",
+            with = "crate::serialization::duration_seconds"
+        )
+    )]
     pub spent_time: Duration,
     pub worklogs: Vec<Worklog>,
 }
+
+impl WorklogSummary {
+    pub fn merge(&mut self, other: WorklogSummary) {
+        self.spent_time = self.spent_time + other.spent_time;
+        self.worklogs.extend(other.worklogs);
+    }
+
+    pub fn breakdown(&self, grouping: BreakdownGrouping) -> TimeBreakdown {
+        TimeBreakdown::new(grouping, self.worklogs.iter())
+    }
+}